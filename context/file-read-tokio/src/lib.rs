@@ -18,7 +18,9 @@
 //! Tokio-based file reading implementation for reqsign.
 //!
 //! This crate provides `TokioFileRead`, an async file reader that implements
-//! the `FileRead` trait from `reqsign_core` using Tokio's file system operations.
+//! the `FileRead` trait from `reqsign_core` using Tokio's file system operations,
+//! and `TokioFileWrite`, its `FileWrite` counterpart used to persist refreshed
+//! credential caches.
 //!
 //! ## Overview
 //!
@@ -63,7 +65,7 @@
 //! # Ok(())
 //! # }
 //! ```
-use reqsign_core::{Error, FileRead, Result};
+use reqsign_core::{Error, FileRead, FileWrite, Result};
 
 /// Tokio-based implementation of the `FileRead` trait.
 ///
@@ -89,3 +91,82 @@ impl FileRead for TokioFileRead {
         ))
     }
 }
+
+/// Tokio-based implementation of the `FileWrite` trait.
+///
+/// This struct provides async file writing capabilities using Tokio's
+/// file system operations. Content is written to a temporary file in the same
+/// directory and renamed over the target, so readers never see a partially
+/// written file. On unix, a newly written file is only readable by its owner.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioFileWrite;
+
+#[cfg(not(target_family = "wasm"))]
+impl FileWrite for TokioFileWrite {
+    async fn file_write(&self, path: &str, content: &[u8]) -> Result<()> {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use tokio::io::AsyncWriteExt;
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let tmp_path = format!(
+            "{path}.{}.{}.tmp",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let write = async {
+            let mut options = tokio::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            options.mode(0o600);
+            let mut file = options.open(&tmp_path).await?;
+            file.write_all(content).await?;
+            file.sync_all().await?;
+            tokio::fs::rename(&tmp_path, path).await
+        };
+        if let Err(e) = write.await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(Error::unexpected("failed to write file")
+                .with_source(e)
+                .with_context(format!("file: {path}")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl FileWrite for TokioFileWrite {
+    async fn file_write(&self, _path: &str, _content: &[u8]) -> Result<()> {
+        Err(Error::unexpected(
+            "TokioFileWrite is unsupported on wasm targets",
+        ))
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_write_replaces_file() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("reqsign-file-write-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cache.json");
+        let path = path.to_str().unwrap();
+
+        TokioFileWrite.file_write(path, b"first").await?;
+        TokioFileWrite.file_write(path, b"second").await?;
+        assert_eq!(TokioFileRead.file_read(path).await?, b"second");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        Ok(())
+    }
+}
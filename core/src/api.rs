@@ -261,6 +261,23 @@ pub trait SignRequest: Debug + Send + Sync + Unpin + 'static {
         Timestamp::now() + expires_in.unwrap_or_default()
     }
 
+    /// Return the timestamp through which the credential must remain usable
+    /// for signing `req`.
+    ///
+    /// `expires_in` already includes any [`crate::SigningOverrides`] override.
+    /// Signers that honour a signing time override must derive the deadline
+    /// from it, as [`SignRequest::sign_request`] does. The default ignores
+    /// `req` and returns [`SignRequest::required_valid_until`].
+    fn required_valid_until_for(
+        &self,
+        req: &http::request::Parts,
+        credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        let _ = req;
+        self.required_valid_until(credential, expires_in)
+    }
+
     /// Sign a request head.
     ///
     /// On `Err`, an implementation must leave the entire request head unchanged. On
@@ -306,6 +323,17 @@ pub trait SignRequestDyn: Debug + Send + Sync + Unpin + 'static {
         Timestamp::now() + expires_in.unwrap_or_default()
     }
 
+    /// Dyn version of [`SignRequest::required_valid_until_for`].
+    fn required_valid_until_for_dyn(
+        &self,
+        req: &http::request::Parts,
+        credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        let _ = req;
+        self.required_valid_until_dyn(credential, expires_in)
+    }
+
     /// Dyn version of [`SignRequest::sign_request`].
     fn sign_request_dyn<'a>(
         &'a self,
@@ -330,6 +358,15 @@ where
         self.required_valid_until(credential, expires_in)
     }
 
+    fn required_valid_until_for_dyn(
+        &self,
+        req: &http::request::Parts,
+        credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        self.required_valid_until_for(req, credential, expires_in)
+    }
+
     fn sign_request_dyn<'a>(
         &'a self,
         ctx: &'a Context,
@@ -356,6 +393,16 @@ where
             .required_valid_until_dyn(credential, expires_in)
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &http::request::Parts,
+        credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        self.deref()
+            .required_valid_until_for_dyn(req, credential, expires_in)
+    }
+
     async fn sign_request(
         &self,
         ctx: &Context,
@@ -43,6 +43,7 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct Context {
     fs: Arc<dyn FileReadDyn>,
+    fs_write: Arc<dyn FileWriteDyn>,
     http: Arc<dyn HttpSendDyn>,
     env: Arc<dyn Env>,
     cmd: Arc<dyn CommandExecuteDyn>,
@@ -52,6 +53,7 @@ impl Debug for Context {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Context")
             .field("fs", &self.fs)
+            .field("fs_write", &self.fs_write)
             .field("http", &self.http)
             .field("env", &self.env)
             .field("cmd", &self.cmd)
@@ -84,6 +86,7 @@ impl Context {
     pub fn new() -> Self {
         Self {
             fs: Arc::new(NoopFileRead),
+            fs_write: Arc::new(NoopFileWrite),
             http: Arc::new(NoopHttpSend),
             env: Arc::new(NoopEnv),
             cmd: Arc::new(NoopCommandExecute),
@@ -96,6 +99,12 @@ impl Context {
         self
     }
 
+    /// Replace the file writer implementation.
+    pub fn with_file_write(mut self, fs: impl FileWrite) -> Self {
+        self.fs_write = Arc::new(fs);
+        self
+    }
+
     /// Replace the HTTP client implementation.
     pub fn with_http_send(mut self, http: impl HttpSend) -> Self {
         self.http = Arc::new(http);
@@ -126,6 +135,12 @@ impl Context {
         Ok(String::from_utf8_lossy(&bytes).to_string())
     }
 
+    /// Write the content to the file, replacing any existing content.
+    #[inline]
+    pub async fn file_write(&self, path: &str, content: &[u8]) -> Result<()> {
+        self.fs_write.file_write_dyn(path, content).await
+    }
+
     /// Send http request and return the response.
     #[inline]
     pub async fn http_send(&self, req: http::Request<Bytes>) -> Result<http::Response<Bytes>> {
@@ -212,6 +227,47 @@ impl<T: FileReadDyn + ?Sized> FileRead for Arc<T> {
     }
 }
 
+/// FileWrite is used to write the file content entirely.
+///
+/// This could be used by `Load` to persist refreshed credential caches.
+pub trait FileWrite: Debug + Send + Sync + 'static {
+    /// Write the content to the file, replacing any existing content.
+    ///
+    /// Implementations should replace the file atomically and keep it
+    /// private, since it may hold secrets such as refresh tokens.
+    fn file_write(
+        &self,
+        path: &str,
+        content: &[u8],
+    ) -> impl Future<Output = Result<()>> + MaybeSend;
+}
+
+/// FileWriteDyn is the dyn version of [`FileWrite`].
+pub trait FileWriteDyn: Debug + Send + Sync + 'static {
+    /// Dyn version of [`FileWrite::file_write`].
+    fn file_write_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        content: &'a [u8],
+    ) -> BoxedFuture<'a, Result<()>>;
+}
+
+impl<T: FileWrite + ?Sized> FileWriteDyn for T {
+    fn file_write_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        content: &'a [u8],
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(self.file_write(path, content))
+    }
+}
+
+impl<T: FileWriteDyn + ?Sized> FileWrite for Arc<T> {
+    async fn file_write(&self, path: &str, content: &[u8]) -> Result<()> {
+        self.deref().file_write_dyn(path, content).await
+    }
+}
+
 /// HttpSend is used to send http request during the signing process.
 ///
 /// For example, fetch IMDS token from AWS or OAuth2 refresh token. This trait is designed
@@ -397,6 +453,20 @@ impl FileRead for NoopFileRead {
     }
 }
 
+/// NoopFileWrite is a no-op implementation that always returns an error.
+///
+/// This is used when no file writer is configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopFileWrite;
+
+impl FileWrite for NoopFileWrite {
+    async fn file_write(&self, _path: &str, _content: &[u8]) -> Result<()> {
+        Err(Error::unexpected(
+            "file writing not supported: no file writer configured",
+        ))
+    }
+}
+
 /// NoopHttpSend is a no-op implementation that always returns an error.
 ///
 /// This is used when no HTTP client is configured.
@@ -50,9 +50,13 @@
 //! header and query authentication. The service and credential type determine the
 //! authentication mode.
 //!
+//! A [`SigningOverrides`] request extension changes the region, service, signing time,
+//! signed headers, or `expires_in` of one request. Signers reject any field their
+//! scheme cannot honour rather than ignoring it.
+//!
 //! [`SigningCredential::is_valid`] controls whether a cached credential can be reused
 //! without refresh. [`SigningCredential::is_valid_at`] checks exact usability at the
-//! timestamp returned by [`SignRequest::required_valid_until`]. A refreshed credential
+//! timestamp returned by [`SignRequest::required_valid_until_for`]. A refreshed credential
 //! only needs to satisfy the exact operation requirement; provider errors are returned
 //! without retrying internally or falling back to the old cached credential.
 //!
@@ -160,6 +164,7 @@
 //! This crate defines several important traits:
 //!
 //! - [`FileRead`]: For asynchronous file reading
+//! - [`FileWrite`]: For asynchronous file writing
 //! - [`HttpSend`]: For sending HTTP requests
 //! - [`Env`]: For environment variable access
 //! - [`ProvideCredential`]: For loading credentials from various sources
@@ -198,11 +203,14 @@ pub use context::Context;
 pub use context::Env;
 pub use context::FileRead;
 pub use context::FileReadDyn;
+pub use context::FileWrite;
+pub use context::FileWriteDyn;
 pub use context::HttpSend;
 pub use context::HttpSendDyn;
 pub use context::NoopCommandExecute;
 pub use context::NoopEnv;
 pub use context::NoopFileRead;
+pub use context::NoopFileWrite;
 pub use context::NoopHttpSend;
 pub use context::OsEnv;
 pub use context::StaticEnv;
@@ -218,6 +226,8 @@ pub use api::SignRequestDyn;
 pub use api::SigningCredential;
 mod request;
 pub use request::{SigningMethod, SigningRequest};
+mod overrides;
+pub use overrides::{SigningOverrideField, SigningOverrides};
 mod signer;
 pub use signer::Signer;
 mod granter;
@@ -0,0 +1,328 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use http::header::HeaderName;
+
+use crate::time::Timestamp;
+use crate::{Error, Result};
+
+/// Per-request overrides for a [`crate::SignRequest`].
+///
+/// Insert a value into `http::request::Parts::extensions` to change how one
+/// request is signed without building another signer:
+///
+/// ```
+/// use reqsign_core::SigningOverrides;
+///
+/// let mut req = http::Request::get("https://example.com/").body(()).unwrap();
+/// req.extensions_mut()
+///     .insert(SigningOverrides::new().with_region("eu-west-1"));
+/// ```
+///
+/// [`crate::Signer::sign`] preserves extensions, so the overrides stay on the
+/// request. Built-in signers apply every field their signing scheme uses and
+/// reject any other set field with [`crate::ErrorKind::RequestInvalid`]
+/// instead of silently ignoring it. Each signer documents the fields it
+/// supports.
+///
+/// Signers resolve the `expires_in` and signing time of a request with
+/// [`SigningOverrides::resolve`], and derive their credential deadline in
+/// [`crate::SignRequest::required_valid_until_for`] from
+/// [`SigningOverrides::signing_time_or`], so both use the same time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SigningOverrides {
+    region: Option<String>,
+    service: Option<String>,
+    signing_time: Option<Timestamp>,
+    signed_headers: Option<Vec<HeaderName>>,
+    expires_in: Option<Option<Duration>>,
+}
+
+/// One field of [`SigningOverrides`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SigningOverrideField {
+    /// [`SigningOverrides::with_region`].
+    Region,
+    /// [`SigningOverrides::with_service`].
+    Service,
+    /// [`SigningOverrides::with_signing_time`].
+    SigningTime,
+    /// [`SigningOverrides::with_signed_headers`].
+    SignedHeaders,
+    /// [`SigningOverrides::with_expires_in`] and [`SigningOverrides::without_expiry`].
+    ExpiresIn,
+}
+
+impl Display for SigningOverrideField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Region => "region",
+            Self::Service => "service",
+            Self::SigningTime => "signing time",
+            Self::SignedHeaders => "signed headers",
+            Self::ExpiresIn => "expires in",
+        })
+    }
+}
+
+impl SigningOverrides {
+    /// Create overrides that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the overrides attached to a request head, if any.
+    pub fn from_request(req: &http::request::Parts) -> Option<&Self> {
+        req.extensions.get::<Self>()
+    }
+
+    /// Sign for this region instead of the signer's region.
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Sign for this service instead of the signer's service.
+    pub fn with_service(mut self, service: impl Into<String>) -> Self {
+        self.service = Some(service.into());
+        self
+    }
+
+    /// Sign at this time instead of the current time.
+    pub fn with_signing_time(mut self, signing_time: Timestamp) -> Self {
+        self.signing_time = Some(signing_time);
+        self
+    }
+
+    /// Sign only these headers in addition to the headers the scheme requires.
+    ///
+    /// Headers outside the list are still sent but are not covered by the
+    /// signature.
+    pub fn with_signed_headers(mut self, headers: impl IntoIterator<Item = HeaderName>) -> Self {
+        self.signed_headers = Some(headers.into_iter().collect());
+        self
+    }
+
+    /// Replace the `expires_in` passed to the signer.
+    pub fn with_expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_in = Some(Some(expires_in));
+        self
+    }
+
+    /// Sign as if no `expires_in` were passed to the signer.
+    pub fn without_expiry(mut self) -> Self {
+        self.expires_in = Some(None);
+        self
+    }
+
+    /// Return the region override.
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Return the service override.
+    pub fn service(&self) -> Option<&str> {
+        self.service.as_deref()
+    }
+
+    /// Return the signing time override.
+    pub fn signing_time(&self) -> Option<Timestamp> {
+        self.signing_time
+    }
+
+    /// Return the signed header override.
+    pub fn signed_headers(&self) -> Option<&[HeaderName]> {
+        self.signed_headers.as_deref()
+    }
+
+    /// Resolve the effective `expires_in` for a caller-provided value.
+    pub fn expires_in(&self, requested: Option<Duration>) -> Option<Duration> {
+        self.expires_in.unwrap_or(requested)
+    }
+
+    /// Return whether a header is selected for signing.
+    ///
+    /// Every header is selected when no signed header override is set.
+    /// Signers still sign the headers their scheme requires.
+    pub fn signs_header(&self, name: &str) -> bool {
+        self.signed_headers
+            .as_ref()
+            .is_none_or(|headers| headers.iter().any(|header| header.as_str() == name))
+    }
+
+    /// Resolve the `expires_in` and signing time to sign `req` with.
+    ///
+    /// Rejects overrides outside `supported`, applies the `expires_in`
+    /// override to the caller-provided value, and returns the signing time
+    /// override or `now()`.
+    pub fn resolve(
+        req: &http::request::Parts,
+        supported: &[SigningOverrideField],
+        expires_in: Option<Duration>,
+        now: impl FnOnce() -> Timestamp,
+    ) -> Result<(Option<Duration>, Timestamp)> {
+        let Some(overrides) = Self::from_request(req) else {
+            return Ok((expires_in, now()));
+        };
+        overrides.ensure_supported(supported)?;
+        Ok((
+            overrides.expires_in(expires_in),
+            overrides.signing_time.unwrap_or_else(now),
+        ))
+    }
+
+    /// Return the signing time override of `req`, or `now()`.
+    pub fn signing_time_or(
+        req: &http::request::Parts,
+        now: impl FnOnce() -> Timestamp,
+    ) -> Timestamp {
+        Self::from_request(req)
+            .and_then(Self::signing_time)
+            .unwrap_or_else(now)
+    }
+
+    /// Reject any set field that is not in `supported`.
+    pub fn ensure_supported(&self, supported: &[SigningOverrideField]) -> Result<()> {
+        let set = [
+            (SigningOverrideField::Region, self.region.is_some()),
+            (SigningOverrideField::Service, self.service.is_some()),
+            (
+                SigningOverrideField::SigningTime,
+                self.signing_time.is_some(),
+            ),
+            (
+                SigningOverrideField::SignedHeaders,
+                self.signed_headers.is_some(),
+            ),
+            (SigningOverrideField::ExpiresIn, self.expires_in.is_some()),
+        ];
+        for (field, is_set) in set {
+            if is_set && !supported.contains(&field) {
+                return Err(Error::request_invalid(format!(
+                    "signing override for {field} is not supported by this signer"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resolve the `expires_in` to sign with after applying request overrides.
+pub(crate) fn effective_expires_in(
+    req: &http::request::Parts,
+    expires_in: Option<Duration>,
+) -> Option<Duration> {
+    SigningOverrides::from_request(req)
+        .map_or(expires_in, |overrides| overrides.expires_in(expires_in))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn resolves_fields_and_rejects_unsupported_ones() {
+        let overrides = SigningOverrides::new()
+            .with_region("eu-west-1")
+            .with_signed_headers([http::header::CONTENT_TYPE]);
+        assert_eq!(overrides.region(), Some("eu-west-1"));
+        assert!(overrides.signs_header("content-type"));
+        assert!(!overrides.signs_header("x-custom"));
+        assert!(SigningOverrides::new().signs_header("x-custom"));
+
+        overrides
+            .ensure_supported(&[
+                SigningOverrideField::Region,
+                SigningOverrideField::SignedHeaders,
+            ])
+            .expect("supported fields must pass");
+        let err = overrides
+            .ensure_supported(&[SigningOverrideField::Region])
+            .expect_err("signed headers are unsupported");
+        assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+        assert!(err.to_string().contains("signed headers"));
+    }
+
+    #[test]
+    fn resolves_expiry_semantics() {
+        let requested = Some(Duration::from_secs(60));
+        assert_eq!(SigningOverrides::new().expires_in(requested), requested);
+        assert_eq!(
+            SigningOverrides::new()
+                .with_expires_in(Duration::from_secs(5))
+                .expires_in(requested),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            SigningOverrides::new()
+                .without_expiry()
+                .expires_in(requested),
+            None
+        );
+
+        let mut req = http::Request::get("https://example.com/")
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0;
+        assert_eq!(effective_expires_in(&req, requested), requested);
+        req.extensions
+            .insert(SigningOverrides::new().without_expiry());
+        assert_eq!(effective_expires_in(&req, requested), None);
+    }
+
+    #[test]
+    fn resolves_expiry_and_signing_time_of_request() {
+        let now: Timestamp = "2026-07-22T00:00:00Z".parse().unwrap();
+        let later: Timestamp = "2026-07-22T01:00:00Z".parse().unwrap();
+        let requested = Some(Duration::from_secs(60));
+        let supported = [
+            SigningOverrideField::SigningTime,
+            SigningOverrideField::ExpiresIn,
+        ];
+        let mut req = http::Request::get("https://example.com/")
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0;
+        assert_eq!(
+            SigningOverrides::resolve(&req, &[], requested, || now).unwrap(),
+            (requested, now)
+        );
+        assert_eq!(SigningOverrides::signing_time_or(&req, || now), now);
+
+        req.extensions.insert(
+            SigningOverrides::new()
+                .with_signing_time(later)
+                .without_expiry(),
+        );
+        assert_eq!(
+            SigningOverrides::resolve(&req, &supported, requested, || now).unwrap(),
+            (None, later)
+        );
+        assert_eq!(SigningOverrides::signing_time_or(&req, || now), later);
+
+        let err =
+            SigningOverrides::resolve(&req, &[SigningOverrideField::ExpiresIn], requested, || now)
+                .expect_err("signing time is unsupported");
+        assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+    }
+}
@@ -23,6 +23,7 @@ use crate::Result;
 use crate::SignRequest;
 use crate::SignRequestDyn;
 use crate::SigningCredential;
+use crate::overrides::effective_expires_in;
 use std::any::type_name;
 use std::fmt::{Debug, Formatter};
 use std::sync::{Arc, Mutex};
@@ -101,8 +102,12 @@ impl<K: SigningCredential> Signer<K> {
     /// select query authentication. The configured service signer and credential type
     /// determine how it is interpreted.
     ///
+    /// A [`crate::SigningOverrides`] extension on `req` replaces `expires_in` before
+    /// the credential deadline is checked, and the service signer applies its other
+    /// fields.
+    ///
     /// Cached credentials must be fresh according to [`SigningCredential::is_valid`]
-    /// and usable through [`SignRequest::required_valid_until`]. A refreshed credential
+    /// and usable through [`SignRequest::required_valid_until_for`]. A refreshed credential
     /// only needs to satisfy the exact operation deadline. Provider errors are returned
     /// without internal retry or fallback to the previous cached credential.
     pub async fn sign(
@@ -110,14 +115,16 @@ impl<K: SigningCredential> Signer<K> {
         req: &mut http::request::Parts,
         expires_in: Option<Duration>,
     ) -> Result<()> {
+        let expires_in = effective_expires_in(req, expires_in);
         let credential = self.credential.lock().expect("lock poisoned").clone();
         let credential = match credential {
             Some(credential)
                 if credential.is_valid()
-                    && credential.is_valid_at(
-                        self.builder
-                            .required_valid_until_dyn(&credential, expires_in),
-                    ) =>
+                    && credential.is_valid_at(self.builder.required_valid_until_for_dyn(
+                        req,
+                        &credential,
+                        expires_in,
+                    )) =>
             {
                 credential
             }
@@ -133,9 +140,9 @@ impl<K: SigningCredential> Signer<K> {
 
                 *self.credential.lock().expect("lock poisoned") = Some(credential.clone());
 
-                let required_until = self
-                    .builder
-                    .required_valid_until_dyn(&credential, expires_in);
+                let required_until =
+                    self.builder
+                        .required_valid_until_for_dyn(req, &credential, expires_in);
                 if !credential.is_valid_at(required_until) {
                     return Err(Error::credential_invalid(
                         "refreshed signing credential expires before the requested operation deadline",
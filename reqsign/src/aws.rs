@@ -18,7 +18,8 @@
 //! AWS signing support.
 //!
 //! SigV4 remains available directly under `reqsign::aws` for compatibility.
-//! The explicit algorithm modules are `v4` and `v4a`.
+//! The explicit algorithm modules are `v4` and `v4a`. With both enabled,
+//! [`AutoRequestSigner`] chooses between them per request.
 
 /// AWS Signature Version 4 support.
 #[cfg(feature = "aws-v4")]
@@ -35,6 +36,7 @@ pub mod v4 {
     /// Create a default SigV4 signer for an AWS service and region.
     ///
     /// The signer uses the default context and AWS credential provider chain.
+    /// Role profiles in the shared config files sign their STS calls with SigV4.
     #[cfg(feature = "default-context")]
     pub fn default_signer(service: &str, region: &str) -> DefaultSigner {
         Signer::new(
@@ -64,12 +66,31 @@ pub mod v4a {
     /// Create a default SigV4a signer for an AWS service and signing region set.
     ///
     /// The signer uses the default context and AWS credential provider chain.
+    /// Role profiles in the shared config files sign their STS calls with SigV4.
     #[cfg(feature = "default-context")]
     pub fn default_signer(service: &str, region_set: SigningRegionSet) -> DefaultSigner {
         Signer::new(
             default_context(),
-            DefaultCredentialProvider::new(),
+            reqsign_aws_v4::DefaultCredentialProvider::new(),
             RequestSigner::new(service, region_set),
         )
     }
 }
+
+#[cfg(all(feature = "aws-v4", feature = "aws-v4a"))]
+mod auto;
+#[cfg(all(feature = "aws-v4", feature = "aws-v4a"))]
+pub use auto::{AutoRequestSigner, SigningAlgorithm};
+
+/// Create a default signer that chooses SigV4 or SigV4a per request.
+///
+/// The signer uses the default context and AWS credential provider chain, and
+/// signs SigV4a requests for every region.
+#[cfg(all(feature = "aws-v4", feature = "aws-v4a", feature = "default-context"))]
+pub fn default_auto_signer(service: &str, region: &str) -> DefaultSigner {
+    crate::Signer::new(
+        crate::default_context(),
+        DefaultCredentialProvider::new(),
+        AutoRequestSigner::new(service, region),
+    )
+}
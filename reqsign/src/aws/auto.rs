@@ -0,0 +1,248 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::time::Duration;
+
+use http::request::Parts;
+use reqsign_core::time::Timestamp;
+use reqsign_core::{Context, Result, SignRequest};
+
+use reqsign_aws_v4a::SigningRegionSet;
+
+use super::Credential;
+
+/// Host suffixes of AWS endpoints that only accept SigV4a.
+const SIGV4A_HOST_SUFFIXES: &[&str] = &[
+    // S3 Multi-Region Access Points.
+    ".accesspoint.s3-global.amazonaws.com",
+    // EventBridge global endpoints.
+    ".endpoint.events.amazonaws.com",
+    // CloudFront KeyValueStore.
+    ".cloudfront-kvs.global.api.aws",
+];
+
+/// The AWS signature algorithm used for one request.
+///
+/// Insert a value into the request's `http::Extensions` to override the
+/// endpoint-based choice made by [`AutoRequestSigner`]:
+///
+/// ```
+/// use reqsign::aws::SigningAlgorithm;
+///
+/// let mut req = http::Request::get("https://example.com/").body(()).unwrap();
+/// req.extensions_mut().insert(SigningAlgorithm::V4a);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SigningAlgorithm {
+    /// AWS Signature Version 4.
+    V4,
+    /// AWS Signature Version 4A.
+    V4a,
+}
+
+impl SigningAlgorithm {
+    /// Choose the algorithm required by an endpoint host.
+    ///
+    /// Multi-Region Access Points, EventBridge global endpoints and CloudFront
+    /// KeyValueStore require SigV4a; every other host uses SigV4.
+    pub fn for_host(host: &str) -> Self {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        if SIGV4A_HOST_SUFFIXES
+            .iter()
+            .any(|suffix| host.ends_with(suffix))
+        {
+            Self::V4a
+        } else {
+            Self::V4
+        }
+    }
+}
+
+/// AWS request signer that selects SigV4 or SigV4a per request.
+///
+/// The algorithm comes from a [`SigningAlgorithm`] in the request extensions
+/// when present, and otherwise from [`SigningAlgorithm::for_host`]. SigV4
+/// signs for the configured region and SigV4a for the configured region set,
+/// which defaults to every region. Both algorithms use the same
+/// [`Credential`], so one [`crate::Signer`] and its credential cache serve
+/// every request.
+#[derive(Debug)]
+pub struct AutoRequestSigner {
+    v4: reqsign_aws_v4::RequestSigner,
+    v4a: reqsign_aws_v4a::RequestSigner,
+    service: String,
+}
+
+impl AutoRequestSigner {
+    /// Create a signer for an AWS service and SigV4 region.
+    pub fn new(service: &str, region: &str) -> Self {
+        Self {
+            v4: reqsign_aws_v4::RequestSigner::new(service, region),
+            v4a: reqsign_aws_v4a::RequestSigner::new(service, SigningRegionSet::all()),
+            service: service.to_string(),
+        }
+    }
+
+    /// Set the region set used for SigV4a signatures.
+    pub fn with_region_set(mut self, region_set: SigningRegionSet) -> Self {
+        self.v4a = reqsign_aws_v4a::RequestSigner::new(&self.service, region_set);
+        self
+    }
+
+    /// Return the algorithm that will sign this request.
+    pub fn algorithm(&self, req: &Parts) -> SigningAlgorithm {
+        if let Some(algorithm) = req.extensions.get::<SigningAlgorithm>() {
+            return *algorithm;
+        }
+        req.uri
+            .host()
+            .map(SigningAlgorithm::for_host)
+            .unwrap_or(SigningAlgorithm::V4)
+    }
+}
+
+impl SignRequest for AutoRequestSigner {
+    type Credential = Credential;
+
+    fn required_valid_until(
+        &self,
+        credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        // Both algorithms require the credential for the same window.
+        self.v4.required_valid_until(credential, expires_in)
+    }
+
+    async fn sign_request(
+        &self,
+        ctx: &Context,
+        req: &mut Parts,
+        credential: Option<&Self::Credential>,
+        expires_in: Option<Duration>,
+    ) -> Result<()> {
+        match self.algorithm(req) {
+            SigningAlgorithm::V4 => self.v4.sign_request(ctx, req, credential, expires_in).await,
+            SigningAlgorithm::V4a => {
+                self.v4a
+                    .sign_request(ctx, req, credential, expires_in)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+    use http::header::AUTHORIZATION;
+    use reqsign_aws_v4::StaticCredentialProvider;
+    use reqsign_core::Signer;
+
+    use super::*;
+
+    fn parts(uri: &str) -> Parts {
+        Request::get(uri).body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn selects_algorithm_from_host() {
+        for (host, algorithm) in [
+            (
+                "mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com",
+                SigningAlgorithm::V4a,
+            ),
+            (
+                "abcde.veo.endpoint.events.amazonaws.com",
+                SigningAlgorithm::V4a,
+            ),
+            (
+                "123456789012.cloudfront-kvs.global.api.aws.",
+                SigningAlgorithm::V4a,
+            ),
+            ("bucket.s3.us-east-1.amazonaws.com", SigningAlgorithm::V4),
+            ("s3-global.amazonaws.com", SigningAlgorithm::V4),
+            ("events.us-east-1.amazonaws.com", SigningAlgorithm::V4),
+        ] {
+            assert_eq!(SigningAlgorithm::for_host(host), algorithm, "{host}");
+        }
+    }
+
+    #[tokio::test]
+    async fn signs_each_request_with_selected_algorithm() {
+        let signer = Signer::new(
+            Context::new(),
+            StaticCredentialProvider::new("AKIDEXAMPLE", "secret"),
+            AutoRequestSigner::new("s3", "us-east-1"),
+        );
+
+        let mut v4 = parts("https://bucket.s3.us-east-1.amazonaws.com/key");
+        signer.sign(&mut v4, None).await.unwrap();
+        let authorization = v4.headers[AUTHORIZATION].to_str().unwrap();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 "));
+        assert!(authorization.contains("/us-east-1/s3/aws4_request"));
+        assert!(!v4.headers.contains_key("x-amz-region-set"));
+
+        let mut mrap = parts("https://mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com/key");
+        signer.sign(&mut mrap, None).await.unwrap();
+        assert!(
+            mrap.headers[AUTHORIZATION]
+                .to_str()
+                .unwrap()
+                .starts_with("AWS4-ECDSA-P256-SHA256 ")
+        );
+        assert_eq!(mrap.headers["x-amz-region-set"], "*");
+
+        let mut hinted = parts("https://bucket.s3.us-east-1.amazonaws.com/key");
+        hinted.extensions.insert(SigningAlgorithm::V4a);
+        signer.sign(&mut hinted, None).await.unwrap();
+        assert!(
+            hinted.headers[AUTHORIZATION]
+                .to_str()
+                .unwrap()
+                .starts_with("AWS4-ECDSA-P256-SHA256 ")
+        );
+        assert_eq!(hinted.extensions.get(), Some(&SigningAlgorithm::V4a));
+
+        let mut forced_v4 =
+            parts("https://mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com/key");
+        forced_v4.extensions.insert(SigningAlgorithm::V4);
+        signer.sign(&mut forced_v4, None).await.unwrap();
+        assert!(
+            forced_v4.headers[AUTHORIZATION]
+                .to_str()
+                .unwrap()
+                .starts_with("AWS4-HMAC-SHA256 ")
+        );
+    }
+
+    #[tokio::test]
+    async fn uses_configured_region_set() {
+        let signer = AutoRequestSigner::new("s3", "us-east-1")
+            .with_region_set(SigningRegionSet::new("us-east-1,us-west-2").unwrap());
+        let mut req = parts("https://mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com/key");
+        let credential = Credential {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "secret".to_string(),
+            session_token: None,
+            expires_in: None,
+        };
+        signer
+            .sign_request(&Context::new(), &mut req, Some(&credential), None)
+            .await
+            .unwrap();
+        assert_eq!(req.headers["x-amz-region-set"], "us-east-1,us-west-2");
+    }
+}
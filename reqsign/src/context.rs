@@ -20,7 +20,7 @@ use reqsign_core::{Context, OsEnv};
 #[cfg(not(target_arch = "wasm32"))]
 use reqsign_command_execute_tokio::TokioCommandExecute;
 #[cfg(not(target_arch = "wasm32"))]
-use reqsign_file_read_tokio::TokioFileRead;
+use reqsign_file_read_tokio::{TokioFileRead, TokioFileWrite};
 use reqsign_http_send_reqwest::ReqwestHttpSend;
 
 /// Create a Context with default implementations.
@@ -28,6 +28,7 @@ use reqsign_http_send_reqwest::ReqwestHttpSend;
 /// This function returns a Context configured with:
 /// - `TokioCommandExecute` for command execution (non-WASM only)
 /// - `TokioFileRead` for file reading (non-WASM only)
+/// - `TokioFileWrite` for file writing (non-WASM only)
 /// - `ReqwestHttpSend` for HTTP requests
 /// - `OsEnv` for environment variable access
 ///
@@ -70,6 +71,7 @@ pub fn default_context() -> Context {
         Context::new()
             .with_command_execute(TokioCommandExecute)
             .with_file_read(TokioFileRead)
+            .with_file_write(TokioFileWrite)
             .with_http_send(ReqwestHttpSend::default())
             .with_env(OsEnv)
     }
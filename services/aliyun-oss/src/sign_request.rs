@@ -24,7 +24,10 @@ use reqsign_core::hash::{
     base64_hmac_sha1, base64_hmac_sha256, hex_hmac_sha256, hex_sha256, hmac_sha256,
 };
 use reqsign_core::time::Timestamp;
-use reqsign_core::{Context, Error, SignRequest, SigningCredential, SigningRequest};
+use reqsign_core::{
+    Context, Error, SignRequest, SigningCredential, SigningOverrideField, SigningOverrides,
+    SigningRequest,
+};
 use std::collections::HashSet;
 use std::fmt::Write;
 use std::sync::LazyLock;
@@ -88,6 +91,9 @@ pub enum SigningVersion {
 }
 
 /// RequestSigner for Aliyun OSS signature.
+///
+/// Supports the signing time and `expires_in` [`SigningOverrides`], and the
+/// region when signing with V4.
 #[derive(Debug)]
 pub struct RequestSigner {
     bucket: String,
@@ -159,6 +165,16 @@ impl SignRequest for RequestSigner {
         self.required_valid_until_at(self.get_time(), expires_in)
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &http::request::Parts,
+        _credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        let signing_time = SigningOverrides::signing_time_or(req, || self.get_time());
+        self.required_valid_until_at(signing_time, expires_in)
+    }
+
     async fn sign_request(
         &self,
         _ctx: &Context,
@@ -170,7 +186,23 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let signing_time = self.get_time();
+        let supported: &[SigningOverrideField] = match self.signing_version {
+            SigningVersion::V4 => &[
+                SigningOverrideField::Region,
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::ExpiresIn,
+            ],
+            SigningVersion::V1 | SigningVersion::V2 => &[
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::ExpiresIn,
+            ],
+        };
+        let (expires_in, signing_time) =
+            SigningOverrides::resolve(req, supported, expires_in, || self.get_time())?;
+        let region = SigningOverrides::from_request(req)
+            .and_then(SigningOverrides::region)
+            .or(self.region.as_deref());
+
         let required_until = self.required_valid_until_at(signing_time, expires_in);
         if !cred.is_valid_at(required_until) {
             return Err(Error::credential_invalid(
@@ -192,7 +224,7 @@ impl SignRequest for RequestSigner {
                 self.sign_v2(&mut candidate, cred, signing_time, expires_in)?;
             }
             SigningVersion::V4 => {
-                self.sign_v4(&mut candidate, cred, signing_time, expires_in)?;
+                self.sign_v4(&mut candidate, cred, region, signing_time, expires_in)?;
             }
         }
 
@@ -466,10 +498,11 @@ impl RequestSigner {
         &self,
         req: &mut http::request::Parts,
         cred: &Credential,
+        region: Option<&str>,
         signing_time: Timestamp,
         expires_in: Option<Duration>,
     ) -> Result<()> {
-        let region = self.region.as_deref().ok_or_else(|| {
+        let region = region.ok_or_else(|| {
             Error::config_invalid(
                 "OSS V4 signing requires region; call RequestSigner::with_region(...)",
             )
@@ -1695,6 +1728,64 @@ mod tests {
         assert!(err.to_string().contains("OSS V4 signing requires region"));
     }
 
+    #[tokio::test]
+    async fn test_signing_overrides_supply_v4_region_and_time() {
+        let uri = "https://examplebucket.oss-cn-hangzhou.aliyuncs.com/exampleobject";
+        let credential = test_credential(None);
+        let time = Timestamp::from_second(1_744_353_684).expect("timestamp must build");
+
+        let mut expected = Request::get(uri)
+            .body(())
+            .expect("request must build")
+            .into_parts()
+            .0;
+        RequestSigner::new("examplebucket")
+            .with_region("cn-hangzhou")
+            .with_signing_version(SigningVersion::V4)
+            .with_time(time)
+            .sign_request(
+                &Context::new(),
+                &mut expected,
+                Some(&credential),
+                Some(Duration::from_secs(60)),
+            )
+            .await
+            .expect("v4 presign must succeed");
+
+        let mut actual = Request::get(uri)
+            .body(())
+            .expect("request must build")
+            .into_parts()
+            .0;
+        actual.extensions.insert(
+            SigningOverrides::new()
+                .with_region("cn-hangzhou")
+                .with_signing_time(time)
+                .with_expires_in(Duration::from_secs(60)),
+        );
+        RequestSigner::new("examplebucket")
+            .with_signing_version(SigningVersion::V4)
+            .sign_request(&Context::new(), &mut actual, Some(&credential), None)
+            .await
+            .expect("v4 presign with overrides must succeed");
+
+        assert_eq!(actual.uri, expected.uri);
+        assert_eq!(actual.headers, expected.headers);
+
+        let mut v1 = Request::get(uri)
+            .body(())
+            .expect("request must build")
+            .into_parts()
+            .0;
+        v1.extensions
+            .insert(SigningOverrides::new().with_region("cn-hangzhou"));
+        let err = RequestSigner::new("examplebucket")
+            .sign_request(&Context::new(), &mut v1, Some(&credential), None)
+            .await
+            .expect_err("v1 must reject region overrides");
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::RequestInvalid);
+    }
+
     #[test]
     fn test_v4_header_signature_matches_golden_output() {
         let credential = Credential {
@@ -1766,7 +1857,7 @@ mod tests {
                 .0;
 
         signer
-            .sign_v4(&mut req, &credential, time, None)
+            .sign_v4(&mut req, &credential, Some("cn-hangzhou"), time, None)
             .expect("v4 header signing must succeed");
 
         assert_eq!(
@@ -1844,6 +1935,7 @@ mod tests {
             .sign_v4(
                 &mut req,
                 &credential,
+                Some("cn-hangzhou"),
                 time,
                 Some(Duration::from_secs(86_400)),
             )
@@ -1876,7 +1968,13 @@ mod tests {
             .with_region("cn-hangzhou")
             .with_signing_version(SigningVersion::V4)
             .with_time(time)
-            .sign_v4(&mut req, &credential, time, Some(Duration::from_secs(60)))
+            .sign_v4(
+                &mut req,
+                &credential,
+                Some("cn-hangzhou"),
+                time,
+                Some(Duration::from_secs(60)),
+            )
             .expect("v4 presign must succeed");
 
         let query = req.uri.query().expect("query must exist");
@@ -0,0 +1,554 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Endpoint resolution for the AWS services called by credential providers.
+
+use reqsign_core::{Context, Error, Result};
+
+use crate::provide_credential::utils::{partition_for_region, sts_endpoint};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::shared_config::{ProfileFiles, ProfileSet};
+
+const AWS_USE_FIPS_ENDPOINT: &str = "AWS_USE_FIPS_ENDPOINT";
+const AWS_USE_DUALSTACK_ENDPOINT: &str = "AWS_USE_DUALSTACK_ENDPOINT";
+const AWS_ENDPOINT_URL: &str = "AWS_ENDPOINT_URL";
+const AWS_IGNORE_CONFIGURED_ENDPOINT_URLS: &str = "AWS_IGNORE_CONFIGURED_ENDPOINT_URLS";
+
+/// An AWS service reached by credential providers.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AwsService {
+    /// Service identifier used by `AWS_ENDPOINT_URL_<ID>` and `services` sections.
+    id: &'static str,
+    /// Leading labels of the service hostname.
+    host_prefix: &'static str,
+}
+
+/// AWS Security Token Service.
+pub(crate) const STS: AwsService = AwsService {
+    id: "sts",
+    host_prefix: "sts",
+};
+/// IAM Identity Center portal.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const SSO: AwsService = AwsService {
+    id: "sso",
+    host_prefix: "portal.sso",
+};
+/// IAM Identity Center OIDC.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const SSO_OIDC: AwsService = AwsService {
+    id: "sso_oidc",
+    host_prefix: "oidc",
+};
+/// IAM Roles Anywhere.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const ROLES_ANYWHERE: AwsService = AwsService {
+    id: "rolesanywhere",
+    host_prefix: "rolesanywhere",
+};
+/// Amazon Cognito identity pools.
+pub(crate) const COGNITO_IDENTITY: AwsService = AwsService {
+    id: "cognito_identity",
+    host_prefix: "cognito-identity",
+};
+
+/// Endpoint settings for the AWS service called by a credential provider.
+///
+/// Settings that are not configured explicitly are loaded, in order, from:
+///
+/// | Setting | Environment variable | Profile key |
+/// |---------|----------------------|-------------|
+/// | FIPS | `AWS_USE_FIPS_ENDPOINT` | `use_fips_endpoint` |
+/// | Dual-stack | `AWS_USE_DUALSTACK_ENDPOINT` | `use_dualstack_endpoint` |
+/// | Endpoint URL | `AWS_ENDPOINT_URL_<SERVICE>`, then `AWS_ENDPOINT_URL` | `endpoint_url` in the profile's `services` section, then `endpoint_url` |
+///
+/// Configured endpoint URLs from the environment and shared config are
+/// ignored when `AWS_IGNORE_CONFIGURED_ENDPOINT_URLS` or the profile key
+/// `ignore_configured_endpoint_urls` is `true`.
+///
+/// # Example
+///
+/// ```
+/// use reqsign_aws_core::{AssumeRoleWithWebIdentityCredentialProvider, EndpointConfig};
+///
+/// let provider = AssumeRoleWithWebIdentityCredentialProvider::new()
+///     .with_region("us-gov-west-1".to_string())
+///     .with_endpoint_config(EndpointConfig::new().with_use_fips(true));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EndpointConfig {
+    use_fips: Option<bool>,
+    use_dual_stack: Option<bool>,
+    endpoint_url: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    profile: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    files: Option<ProfileFiles>,
+    /// Set once every setting has been loaded for one service.
+    loaded: bool,
+}
+
+impl EndpointConfig {
+    /// Create a new `EndpointConfig` that loads all settings from the environment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use FIPS endpoints.
+    pub fn with_use_fips(mut self, use_fips: bool) -> Self {
+        self.use_fips = Some(use_fips);
+        self
+    }
+
+    /// Use dual-stack (IPv4 and IPv6) endpoints.
+    pub fn with_use_dual_stack(mut self, use_dual_stack: bool) -> Self {
+        self.use_dual_stack = Some(use_dual_stack);
+        self
+    }
+
+    /// Send requests to this URL instead of the resolved endpoint.
+    pub fn with_endpoint_url(mut self, endpoint_url: impl Into<String>) -> Self {
+        self.endpoint_url = Some(endpoint_url.into());
+        self
+    }
+
+    /// Set the profile used to read endpoint settings from the shared config.
+    ///
+    /// Defaults to `AWS_PROFILE`, then `default`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Use the profile and shared config files of the calling provider unless
+    /// they were configured explicitly.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn or_profile_files(&self, profile: Option<&String>, files: &ProfileFiles) -> Self {
+        let mut config = self.clone();
+        config.profile = config.profile.or_else(|| profile.cloned());
+        config.files = config.files.or_else(|| Some(files.clone()));
+        config
+    }
+
+    /// Load the settings for `service` from the environment and the shared
+    /// config files.
+    pub(crate) async fn load(&self, ctx: &Context, service: &AwsService) -> Result<Self> {
+        if self.loaded {
+            return Ok(self.clone());
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let profile = self
+                .profile
+                .clone()
+                .or_else(|| ctx.env_var(crate::constants::AWS_PROFILE))
+                .unwrap_or_else(|| "default".to_string());
+            let profiles = self.files.clone().unwrap_or_default().load(ctx).await?;
+            self.load_from_profiles(ctx, &profiles, &profile, service)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.load_with(ctx, service, |_| None, None)
+        }
+    }
+
+    /// Load the settings for `service` from the environment and an already
+    /// loaded profile set.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn load_from_profiles(
+        &self,
+        ctx: &Context,
+        profiles: &ProfileSet,
+        profile: &str,
+        service: &AwsService,
+    ) -> Result<Self> {
+        if self.loaded {
+            return Ok(self.clone());
+        }
+
+        let section = profiles.profile(profile);
+        let service_url = profiles
+            .service_endpoint_url(profile, service.id)
+            .map(str::to_string);
+        self.load_with(
+            ctx,
+            service,
+            |key| section.and_then(|s| s.get(key)).map(str::to_string),
+            service_url,
+        )
+    }
+
+    fn load_with(
+        &self,
+        ctx: &Context,
+        service: &AwsService,
+        profile_value: impl Fn(&str) -> Option<String>,
+        profile_service_url: Option<String>,
+    ) -> Result<Self> {
+        let setting = |env: &str, key: &str| ctx.env_var(env).or_else(|| profile_value(key));
+
+        let use_fips = match self.use_fips {
+            Some(v) => v,
+            None => parse_bool(
+                AWS_USE_FIPS_ENDPOINT,
+                setting(AWS_USE_FIPS_ENDPOINT, "use_fips_endpoint"),
+            )?,
+        };
+        let use_dual_stack = match self.use_dual_stack {
+            Some(v) => v,
+            None => parse_bool(
+                AWS_USE_DUALSTACK_ENDPOINT,
+                setting(AWS_USE_DUALSTACK_ENDPOINT, "use_dualstack_endpoint"),
+            )?,
+        };
+
+        let endpoint_url = match &self.endpoint_url {
+            Some(url) => Some(url.clone()),
+            None => {
+                let ignore = parse_bool(
+                    AWS_IGNORE_CONFIGURED_ENDPOINT_URLS,
+                    setting(
+                        AWS_IGNORE_CONFIGURED_ENDPOINT_URLS,
+                        "ignore_configured_endpoint_urls",
+                    ),
+                )?;
+                if ignore {
+                    None
+                } else {
+                    let service_env =
+                        format!("{AWS_ENDPOINT_URL}_{}", service.id.to_ascii_uppercase());
+                    ctx.env_var(&service_env)
+                        .or(profile_service_url)
+                        .or_else(|| ctx.env_var(AWS_ENDPOINT_URL))
+                        .or_else(|| profile_value("endpoint_url"))
+                }
+            }
+        }
+        .filter(|url| !url.is_empty());
+
+        Ok(Self {
+            use_fips: Some(use_fips),
+            use_dual_stack: Some(use_dual_stack),
+            endpoint_url,
+            #[cfg(not(target_arch = "wasm32"))]
+            profile: self.profile.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            files: self.files.clone(),
+            loaded: true,
+        })
+    }
+
+    /// Resolve the regional endpoint of `service`.
+    pub(crate) fn resolve(&self, service: &AwsService, region: &str) -> Result<Endpoint> {
+        if let Some(url) = &self.endpoint_url {
+            return Endpoint::from_url(url, region);
+        }
+
+        let host = self.hostname(service, region)?;
+        Ok(Endpoint {
+            url: format!("https://{host}"),
+            signing_region: region.to_string(),
+        })
+    }
+
+    /// Resolve the STS endpoint.
+    ///
+    /// The legacy global endpoint is only used when neither regional endpoints,
+    /// FIPS, nor dual-stack are requested.
+    pub(crate) fn resolve_sts(&self, region: Option<&str>, use_regional: bool) -> Result<Endpoint> {
+        let variant = self.use_fips.unwrap_or_default() || self.use_dual_stack.unwrap_or_default();
+        if let Some(url) = &self.endpoint_url {
+            return Endpoint::from_url(url, region.unwrap_or("us-east-1"));
+        }
+        if use_regional || variant {
+            let region = region.ok_or_else(|| {
+                Error::config_invalid("regional, FIPS, or dual-stack STS endpoint requires region")
+            })?;
+            return self.resolve(&STS, region);
+        }
+
+        let host = sts_endpoint(region, false)?;
+        let signing_region = match region {
+            Some(region) if partition_for_region(region)?.id == "aws-cn" => "cn-north-1",
+            _ => "us-east-1",
+        };
+        Ok(Endpoint {
+            url: format!("https://{host}"),
+            signing_region: signing_region.to_string(),
+        })
+    }
+
+    fn hostname(&self, service: &AwsService, region: &str) -> Result<String> {
+        let partition = partition_for_region(region)?;
+        let use_fips = self.use_fips.unwrap_or_default();
+        let use_dual_stack = self.use_dual_stack.unwrap_or_default();
+
+        // STS endpoints in GovCloud are FIPS validated without the `-fips` label.
+        if use_fips && !use_dual_stack && service.id == STS.id && partition.id == "aws-us-gov" {
+            return Ok(format!("sts.{region}.{}", partition.dns_suffix));
+        }
+
+        let prefix = if use_fips {
+            format!("{}-fips", service.host_prefix)
+        } else {
+            service.host_prefix.to_string()
+        };
+        let suffix = if use_dual_stack {
+            partition.dual_stack_dns_suffix.ok_or_else(|| {
+                Error::config_invalid(format!(
+                    "dual-stack endpoints are not available in AWS partition {}",
+                    partition.id
+                ))
+            })?
+        } else {
+            partition.dns_suffix
+        };
+        Ok(format!("{prefix}.{region}.{suffix}"))
+    }
+}
+
+/// A resolved AWS service endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    url: String,
+    signing_region: String,
+}
+
+impl Endpoint {
+    fn from_url(url: &str, signing_region: &str) -> Result<Self> {
+        let uri: http::Uri = url
+            .parse()
+            .map_err(|e| Error::config_invalid("AWS endpoint URL is invalid").with_source(e))?;
+        let valid = matches!(uri.scheme_str(), Some("https" | "http"))
+            && uri.authority().is_some_and(|a| !a.as_str().contains('@'))
+            && uri.query().is_none();
+        if !valid {
+            return Err(Error::config_invalid("AWS endpoint URL is invalid")
+                .with_context(format!("endpoint_url: {url}")));
+        }
+
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            signing_region: signing_region.to_string(),
+        })
+    }
+
+    /// The endpoint URL without a trailing slash.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The region used to sign requests sent to this endpoint.
+    pub fn signing_region(&self) -> &str {
+        &self.signing_region
+    }
+}
+
+fn parse_bool(name: &str, value: Option<String>) -> Result<bool> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(false),
+        Some(v) if v.eq_ignore_ascii_case("true") => Ok(true),
+        Some(v) if v.eq_ignore_ascii_case("false") => Ok(false),
+        Some(v) => Err(Error::config_invalid(format!(
+            "{name} must be 'true' or 'false', got '{v}'"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqsign_core::StaticEnv;
+    use std::collections::HashMap;
+
+    fn context(envs: &[(&str, &str)]) -> Context {
+        Context::new().with_env(StaticEnv {
+            home_dir: None,
+            envs: envs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+        })
+    }
+
+    fn sts_url(config: EndpointConfig, region: Option<&str>, regional: bool) -> Result<String> {
+        let config = config.load_with(&context(&[]), &STS, |_| None, None)?;
+        Ok(config.resolve_sts(region, regional)?.url().to_string())
+    }
+
+    #[test]
+    fn test_resolve_sts_variants() -> Result<()> {
+        let fips = EndpointConfig::new().with_use_fips(true);
+        let dual_stack = EndpointConfig::new().with_use_dual_stack(true);
+        let both = fips.clone().with_use_dual_stack(true);
+
+        for (config, region, expected) in [
+            (
+                EndpointConfig::new(),
+                "us-east-1",
+                "https://sts.us-east-1.amazonaws.com",
+            ),
+            (
+                fips.clone(),
+                "us-east-1",
+                "https://sts-fips.us-east-1.amazonaws.com",
+            ),
+            (
+                fips.clone(),
+                "us-gov-west-1",
+                "https://sts.us-gov-west-1.amazonaws.com",
+            ),
+            (
+                dual_stack.clone(),
+                "eu-west-1",
+                "https://sts.eu-west-1.api.aws",
+            ),
+            (
+                dual_stack.clone(),
+                "cn-north-1",
+                "https://sts.cn-north-1.api.amazonwebservices.com.cn",
+            ),
+            (
+                both.clone(),
+                "us-gov-east-1",
+                "https://sts-fips.us-gov-east-1.api.aws",
+            ),
+        ] {
+            assert_eq!(sts_url(config, Some(region), true)?, expected);
+        }
+
+        let err = sts_url(dual_stack, Some("us-iso-east-1"), true)
+            .expect_err("dual-stack is unavailable in aws-iso");
+        assert!(err.to_string().contains("aws-iso"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_sts_global_endpoint() -> Result<()> {
+        let config = EndpointConfig::new().load_with(&context(&[]), &STS, |_| None, None)?;
+        let endpoint = config.resolve_sts(Some("eu-west-1"), false)?;
+        assert_eq!(endpoint.url(), "https://sts.amazonaws.com");
+        assert_eq!(endpoint.signing_region(), "us-east-1");
+
+        // FIPS and dual-stack endpoints are always regional.
+        assert_eq!(
+            sts_url(
+                EndpointConfig::new().with_use_fips(true),
+                Some("eu-west-1"),
+                false
+            )?,
+            "https://sts-fips.eu-west-1.amazonaws.com"
+        );
+        sts_url(EndpointConfig::new().with_use_fips(true), None, false)
+            .expect_err("FIPS endpoint requires region");
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_service_prefixes() -> Result<()> {
+        let config = EndpointConfig::new().with_use_fips(true).load_with(
+            &context(&[]),
+            &SSO,
+            |_| None,
+            None,
+        )?;
+        assert_eq!(
+            config.resolve(&SSO, "us-east-1")?.url(),
+            "https://portal.sso-fips.us-east-1.amazonaws.com"
+        );
+        assert_eq!(
+            config.resolve(&COGNITO_IDENTITY, "us-west-2")?.url(),
+            "https://cognito-identity-fips.us-west-2.amazonaws.com"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_endpoint_url_precedence() -> Result<()> {
+        let profiles = ProfileSet::parse(
+            "[profile dev]\n\
+             services = dev-services\n\
+             endpoint_url = https://profile.example.com\n\
+             use_fips_endpoint = true\n\
+             [services dev-services]\n\
+             sts =\n  endpoint_url = https://services.example.com/\n",
+            "",
+        );
+        let load = |envs: &[(&str, &str)]| {
+            EndpointConfig::new().load_from_profiles(&context(envs), &profiles, "dev", &STS)
+        };
+
+        let config = load(&[])?;
+        assert_eq!(config.use_fips, Some(true));
+        let endpoint = config.resolve_sts(Some("us-west-2"), true)?;
+        assert_eq!(endpoint.url(), "https://services.example.com");
+        assert_eq!(endpoint.signing_region(), "us-west-2");
+
+        let config = load(&[(AWS_ENDPOINT_URL, "http://localhost:4566")])?;
+        assert_eq!(
+            config.endpoint_url.as_deref(),
+            Some("https://services.example.com/")
+        );
+
+        let config = load(&[("AWS_ENDPOINT_URL_STS", "http://localhost:4566")])?;
+        assert_eq!(
+            config.endpoint_url.as_deref(),
+            Some("http://localhost:4566")
+        );
+
+        let config = load(&[(AWS_IGNORE_CONFIGURED_ENDPOINT_URLS, "true")])?;
+        assert_eq!(config.endpoint_url, None);
+
+        let config = EndpointConfig::new()
+            .with_endpoint_url("https://explicit.example.com")
+            .load_from_profiles(
+                &context(&[(AWS_IGNORE_CONFIGURED_ENDPOINT_URLS, "true")]),
+                &profiles,
+                "dev",
+                &STS,
+            )?;
+        assert_eq!(
+            config.endpoint_url.as_deref(),
+            Some("https://explicit.example.com")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_settings() {
+        let profiles = ProfileSet::parse("[default]\n", "");
+        let err = EndpointConfig::new()
+            .load_from_profiles(
+                &context(&[(AWS_USE_FIPS_ENDPOINT, "yes")]),
+                &profiles,
+                "default",
+                &STS,
+            )
+            .expect_err("invalid boolean must be rejected");
+        assert!(err.to_string().contains(AWS_USE_FIPS_ENDPOINT));
+
+        let config = EndpointConfig::new()
+            .with_endpoint_url("ftp://sts.example.com")
+            .load_from_profiles(&context(&[]), &profiles, "default", &STS)
+            .unwrap();
+        config
+            .resolve_sts(Some("us-east-1"), true)
+            .expect_err("non-HTTP endpoint URL must be rejected");
+    }
+}
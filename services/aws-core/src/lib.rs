@@ -26,9 +26,25 @@ pub use assume_role::AssumeRoleGrant;
 mod credential;
 pub use credential::Credential;
 
+pub mod endpoint;
+pub use endpoint::{Endpoint, EndpointConfig};
+
 mod provide_credential;
 pub use provide_credential::*;
 
+mod session_token;
+pub use session_token::{GetFederationTokenGrant, GetSessionTokenGrant};
+#[doc(hidden)]
+pub use session_token::{
+    MAX_SESSION_DURATION_SECONDS, MIN_SESSION_DURATION_SECONDS, SessionTokenOperation,
+    resolve_sts_endpoint,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod shared_config;
+#[cfg(not(target_arch = "wasm32"))]
+pub use shared_config::{ProfileFiles, ProfileSet};
+
 #[doc(hidden)]
 pub mod signing;
 
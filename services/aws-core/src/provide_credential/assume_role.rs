@@ -21,7 +21,7 @@ use reqsign_core::{Context, ProvideCredential, Result, Signer};
 
 use crate::Credential;
 use crate::assume_role::{AssumeRoleGrant, AssumeRoleOperation};
-use crate::provide_credential::utils::sts_endpoint;
+use crate::endpoint::{EndpointConfig, STS};
 
 /// Loads credentials through one fixed AWS STS `AssumeRole` flow.
 ///
@@ -33,6 +33,7 @@ pub struct AssumeRoleCredentialProvider {
     duration_seconds: Option<u32>,
     region: Option<String>,
     use_regional_sts_endpoint: bool,
+    endpoint_config: EndpointConfig,
     sts_signer: Signer<Credential>,
 }
 
@@ -51,6 +52,7 @@ impl AssumeRoleCredentialProvider {
             duration_seconds: Some(3_600),
             region: None,
             use_regional_sts_endpoint: false,
+            endpoint_config: EndpointConfig::default(),
             sts_signer,
         }
     }
@@ -103,6 +105,12 @@ impl AssumeRoleCredentialProvider {
         self
     }
 
+    /// Set the FIPS, dual-stack, and endpoint URL settings for STS.
+    pub fn with_endpoint_config(mut self, config: EndpointConfig) -> Self {
+        self.endpoint_config = config;
+        self
+    }
+
     /// Set the MFA serial number.
     pub fn with_mfa_serial(mut self, serial_number: String) -> Self {
         self.grant.serial_number = Some(serial_number);
@@ -145,8 +153,13 @@ impl ProvideCredential for AssumeRoleCredentialProvider {
             Some(region) => self.grant.validate_for_region(region)?,
             None => self.grant.validate_for_partition("aws")?,
         }
-        let endpoint = sts_endpoint(self.region.as_deref(), self.use_regional_sts_endpoint)?;
-        let operation = AssumeRoleOperation::new(endpoint, &self.grant, self.duration_seconds)?;
+        let endpoint = self
+            .endpoint_config
+            .load(ctx, &STS)
+            .await?
+            .resolve_sts(self.region.as_deref(), self.use_regional_sts_endpoint)?;
+        let operation =
+            AssumeRoleOperation::from_endpoint(&endpoint, &self.grant, self.duration_seconds)?;
         operation.execute(ctx, &self.sts_signer).await.map(Some)
     }
 }
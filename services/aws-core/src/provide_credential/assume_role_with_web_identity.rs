@@ -16,7 +16,8 @@
 // under the License.
 
 use crate::Credential;
-use crate::provide_credential::utils::{parse_sts_error, sts_endpoint};
+use crate::endpoint::{EndpointConfig, STS};
+use crate::provide_credential::utils::parse_sts_error;
 use bytes::Bytes;
 use form_urlencoded::Serializer;
 use quick_xml::de;
@@ -43,6 +44,7 @@ pub struct AssumeRoleWithWebIdentityCredentialProvider {
     // STS configuration
     region: Option<String>,
     use_regional_sts_endpoint: Option<bool>,
+    endpoint_config: EndpointConfig,
 }
 
 impl AssumeRoleWithWebIdentityCredentialProvider {
@@ -62,6 +64,7 @@ impl AssumeRoleWithWebIdentityCredentialProvider {
             policy_arns: None,
             region: None,
             use_regional_sts_endpoint: None,
+            endpoint_config: EndpointConfig::default(),
         }
     }
 
@@ -112,6 +115,12 @@ impl AssumeRoleWithWebIdentityCredentialProvider {
         self.use_regional_sts_endpoint = Some(true);
         self
     }
+
+    /// Set the FIPS, dual-stack, and endpoint URL settings for STS.
+    pub fn with_endpoint_config(mut self, config: EndpointConfig) -> Self {
+        self.endpoint_config = config;
+        self
+    }
 }
 impl ProvideCredential for AssumeRoleWithWebIdentityCredentialProvider {
     type Credential = Credential;
@@ -161,8 +170,13 @@ impl ProvideCredential for AssumeRoleWithWebIdentityCredentialProvider {
                 .unwrap_or(false)
         });
 
-        let endpoint = sts_endpoint(region.as_deref(), use_regional)
+        let endpoint = self
+            .endpoint_config
+            .load(ctx, &STS)
+            .await
+            .and_then(|config| config.resolve_sts(region.as_deref(), use_regional))
             .map_err(|e| e.with_context(format!("role_arn: {role_arn}")))?;
+        let endpoint = endpoint.url();
 
         // Get session name from config or environment or use default
         let session_name = self
@@ -196,7 +210,7 @@ impl ProvideCredential for AssumeRoleWithWebIdentityCredentialProvider {
 
             serializer.finish()
         };
-        let url = format!("https://{endpoint}/?{query}");
+        let url = format!("{endpoint}/?{query}");
         let req = http::request::Request::builder()
             .method("GET")
             .uri(url)
@@ -209,14 +223,14 @@ impl ProvideCredential for AssumeRoleWithWebIdentityCredentialProvider {
                 Error::request_invalid("failed to build STS AssumeRoleWithWebIdentity request")
                     .with_source(e)
                     .with_context(format!("role_arn: {role_arn}"))
-                    .with_context(format!("endpoint: https://{endpoint}"))
+                    .with_context(format!("endpoint: {endpoint}"))
             })?;
 
         let resp = ctx.http_send_as_string(req).await.map_err(|e| {
             Error::unexpected("failed to send AssumeRoleWithWebIdentity request to STS")
                 .with_source(e)
                 .with_context(format!("role_arn: {role_arn}"))
-                .with_context(format!("endpoint: https://{endpoint}"))
+                .with_context(format!("endpoint: {endpoint}"))
                 .set_retryable(true)
         })?;
 
@@ -446,6 +460,51 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_assume_role_with_web_identity_uses_fips_endpoint() -> Result<()> {
+        let token_path = "/mock/token";
+        let file_read = TestFileRead {
+            expected_path: token_path.to_string(),
+            content: b"token".to_vec(),
+        };
+        let http_body = r#"<AssumeRoleWithWebIdentityResponse xmlns="https://sts.amazonaws.com/doc/2011-06-15/">
+  <AssumeRoleWithWebIdentityResult>
+    <Credentials>
+      <AccessKeyId>access_key_id</AccessKeyId>
+      <SecretAccessKey>secret_access_key</SecretAccessKey>
+      <SessionToken>session_token</SessionToken>
+      <Expiration>2124-05-25T11:45:17Z</Expiration>
+    </Credentials>
+  </AssumeRoleWithWebIdentityResult>
+</AssumeRoleWithWebIdentityResponse>"#;
+        let http_send = CaptureHttpSend::new(http_body);
+
+        let ctx = Context::new()
+            .with_file_read(file_read)
+            .with_http_send(http_send.clone())
+            .with_env(StaticEnv {
+                home_dir: None,
+                envs: HashMap::from([
+                    ("AWS_REGION".to_string(), "us-west-2".to_string()),
+                    ("AWS_USE_FIPS_ENDPOINT".to_string(), "true".to_string()),
+                ]),
+            });
+
+        AssumeRoleWithWebIdentityCredentialProvider::with_config(
+            "arn:aws:iam::123456789012:role/test-role".to_string(),
+            token_path.into(),
+        )
+        .provide_credential(&ctx)
+        .await?
+        .expect("credential must be loaded");
+
+        let recorded_uri = http_send
+            .uri()
+            .expect("http_send must capture outgoing uri");
+        assert!(recorded_uri.starts_with("https://sts-fips.us-west-2.amazonaws.com/?"));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_assume_role_with_web_identity_supports_policy_and_policy_arns() -> Result<()> {
         let _ = env_logger::builder().is_test(true).try_init();
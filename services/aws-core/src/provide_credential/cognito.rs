@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::Credential;
+use crate::endpoint::{COGNITO_IDENTITY, EndpointConfig};
 use http::{Method, Request, StatusCode};
 use log::debug;
 use reqsign_core::time::Timestamp;
@@ -53,6 +54,7 @@ pub struct CognitoIdentityCredentialProvider {
     region: Option<String>,
     identity_id: Option<String>,
     logins: Option<std::collections::HashMap<String, String>>,
+    endpoint_config: EndpointConfig,
 }
 
 impl Default for CognitoIdentityCredentialProvider {
@@ -69,6 +71,7 @@ impl CognitoIdentityCredentialProvider {
             region: None,
             identity_id: None,
             logins: None,
+            endpoint_config: EndpointConfig::new(),
         }
     }
 
@@ -96,6 +99,25 @@ impl CognitoIdentityCredentialProvider {
         self
     }
 
+    /// Set the FIPS, dual-stack, and endpoint URL settings for Cognito Identity.
+    pub fn with_endpoint_config(mut self, config: EndpointConfig) -> Self {
+        self.endpoint_config = config;
+        self
+    }
+
+    async fn endpoint(&self, ctx: &Context, region: &str) -> Result<String> {
+        // Allow endpoint override for testing
+        if let Some(endpoint) = ctx.env_var("AWS_COGNITO_ENDPOINT") {
+            return Ok(endpoint);
+        }
+        let endpoint = self
+            .endpoint_config
+            .load(ctx, &COGNITO_IDENTITY)
+            .await?
+            .resolve(&COGNITO_IDENTITY, region)?;
+        Ok(format!("{}/", endpoint.url()))
+    }
+
     /// Get or create an identity ID
     async fn get_identity_id(&self, ctx: &Context) -> Result<String> {
         // Check for explicit identity ID or from environment
@@ -119,10 +141,7 @@ impl CognitoIdentityCredentialProvider {
             .or_else(|| ctx.env_var("AWS_DEFAULT_REGION"))
             .ok_or_else(|| Error::config_invalid("region is required".to_string()))?;
 
-        // Allow endpoint override for testing
-        let endpoint = ctx
-            .env_var("AWS_COGNITO_ENDPOINT")
-            .unwrap_or_else(|| format!("https://cognito-identity.{region}.amazonaws.com/"));
+        let endpoint = self.endpoint(ctx, &region).await?;
 
         let body = if let Some(logins) = &self.logins {
             json!({
@@ -177,10 +196,7 @@ impl CognitoIdentityCredentialProvider {
             .or_else(|| ctx.env_var("AWS_DEFAULT_REGION"))
             .ok_or_else(|| Error::config_invalid("region is required".to_string()))?;
 
-        // Allow endpoint override for testing
-        let endpoint = ctx
-            .env_var("AWS_COGNITO_ENDPOINT")
-            .unwrap_or_else(|| format!("https://cognito-identity.{region}.amazonaws.com/"));
+        let endpoint = self.endpoint(ctx, &region).await?;
 
         let body = if let Some(logins) = &self.logins {
             json!({
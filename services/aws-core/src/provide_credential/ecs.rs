@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::Credential;
+use crate::provide_credential::static_stability::StaticStability;
 use http::{HeaderValue, Method, Request, StatusCode};
 use log::debug;
 use reqsign_core::{Context, Error, ProvideCredential, Result};
@@ -56,6 +57,13 @@ const ECS_METADATA_ENDPOINT: &str = "http://169.254.170.2";
 /// - `AWS_CONTAINER_AUTHORIZATION_TOKEN`: Authorization token for the request
 /// - `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE`: File containing the authorization token
 ///
+/// # Static Stability
+///
+/// If the endpoint returns expired credentials or fails transiently after
+/// credentials were loaded once, the last known credentials are reused with
+/// their expiration extended by 15 to 20 minutes. Errors that are not
+/// retryable are returned as is.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -78,6 +86,7 @@ pub struct ECSCredentialProvider {
     auth_token_file: Option<String>,
     relative_uri: Option<String>,
     metadata_uri_override: Option<String>,
+    static_stability: StaticStability,
 }
 
 impl Default for ECSCredentialProvider {
@@ -95,6 +104,7 @@ impl ECSCredentialProvider {
             auth_token_file: None,
             relative_uri: None,
             metadata_uri_override: None,
+            static_stability: StaticStability::default(),
         }
     }
 
@@ -224,9 +234,19 @@ impl ProvideCredential for ECSCredentialProvider {
 
         debug!("ECS credential provider: fetching credentials from {endpoint}");
 
+        let cred = match self.load_credential(ctx, &endpoint).await {
+            Ok(cred) => self.static_stability.record("ECS", cred),
+            Err(err) => self.static_stability.fallback("ECS", err)?,
+        };
+        Ok(Some(cred))
+    }
+}
+
+impl ECSCredentialProvider {
+    async fn load_credential(&self, ctx: &Context, endpoint: &str) -> Result<Credential> {
         let mut req = Request::builder()
             .method(Method::GET)
-            .uri(&endpoint)
+            .uri(endpoint)
             .body(bytes::Bytes::new())
             .map_err(|e| {
                 Error::request_invalid("failed to build ECS credentials request")
@@ -292,19 +312,19 @@ impl ProvideCredential for ECSCredentialProvider {
                 .with_context(format!("expiration_value: {}", creds.expiration))
         })?;
 
-        Ok(Some(Credential {
+        Ok(Credential {
             access_key_id: creds.access_key_id,
             secret_access_key: creds.secret_access_key,
             session_token: Some(creds.token),
             expires_in: Some(expires_in),
-        }))
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reqsign_core::StaticEnv;
+    use reqsign_core::{HttpSend, SigningCredential, StaticEnv};
     use reqsign_file_read_tokio::TokioFileRead;
     use reqsign_http_send_reqwest::ReqwestHttpSend;
     use std::collections::HashMap;
@@ -568,4 +588,37 @@ mod tests {
         let token = provider.load_auth_token(&ctx).await.unwrap();
         assert_eq!(token, Some("file-token".to_string()));
     }
+
+    #[derive(Clone, Debug)]
+    struct ExpiredECS;
+
+    impl HttpSend for ExpiredECS {
+        async fn http_send(
+            &self,
+            _: http::Request<bytes::Bytes>,
+        ) -> Result<http::Response<bytes::Bytes>> {
+            Ok(http::Response::builder()
+                .status(StatusCode::OK)
+                .body(bytes::Bytes::from_static(
+                    br#"{"AccessKeyId":"ASIAECSEXAMPLE","SecretAccessKey":"secret","Token":"token","Expiration":"2020-01-01T00:00:00Z"}"#,
+                ))
+                .unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ecs_provider_extends_expired_credentials() {
+        let ctx = Context::new()
+            .with_file_read(TokioFileRead)
+            .with_http_send(ExpiredECS);
+        let provider = ECSCredentialProvider::new().with_endpoint("http://ecs.test/creds");
+
+        let cred = provider
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .expect("credential must be loaded");
+        assert_eq!(cred.access_key_id, "ASIAECSEXAMPLE");
+        assert!(cred.is_valid());
+    }
 }
@@ -0,0 +1,503 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::Credential;
+use http::{HeaderValue, Method, Request, StatusCode};
+use log::debug;
+use reqsign_core::{Context, Error, ProvideCredential, Result};
+use serde::Deserialize;
+
+const AWS_CONTAINER_CREDENTIALS_FULL_URI: &str = "AWS_CONTAINER_CREDENTIALS_FULL_URI";
+const AWS_CONTAINER_AUTHORIZATION_TOKEN: &str = "AWS_CONTAINER_AUTHORIZATION_TOKEN";
+const AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE: &str = "AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE";
+const EKS_POD_IDENTITY_IPV4_HOST: &str = "169.254.170.23";
+const EKS_POD_IDENTITY_IPV6_HOST: &str = "fd00:ec2::23";
+
+/// EKS Pod Identity Credentials Provider
+///
+/// This provider fetches IAM credentials from the EKS Pod Identity agent that
+/// runs on every node of an EKS cluster with Pod Identity enabled. The agent is
+/// reachable at `http://169.254.170.23/v1/credentials` or, on IPv6 clusters,
+/// at `http://[fd00:ec2::23]/v1/credentials`.
+///
+/// The EKS Pod Identity webhook injects `AWS_CONTAINER_CREDENTIALS_FULL_URI` and
+/// `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE` into the pod. The provider only
+/// activates when the full URI from the environment points at one of the agent
+/// addresses, leaving other container endpoints to [`crate::ECSCredentialProvider`].
+///
+/// The projected service account token is rotated by kubelet, so the token file
+/// is re-read on every credential refresh instead of being cached.
+///
+/// # Configuration
+///
+/// ## Builder Methods
+/// - [`EKSPodIdentityCredentialProvider::with_endpoint`]: Set the agent endpoint explicitly
+/// - [`EKSPodIdentityCredentialProvider::with_auth_token_file`]: Set the path to the authorization token file
+///
+/// ## Environment Variables (Fallback)
+/// - `AWS_CONTAINER_CREDENTIALS_FULL_URI`: Full URI of the EKS Pod Identity agent
+/// - `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE`: File containing the authorization token
+/// - `AWS_CONTAINER_AUTHORIZATION_TOKEN`: Authorization token, used when no token file is set
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use reqsign_aws_core::EKSPodIdentityCredentialProvider;
+///
+/// let provider = EKSPodIdentityCredentialProvider::new()
+///     .with_endpoint("http://169.254.170.23/v1/credentials")
+///     .with_auth_token_file(
+///         "/var/run/secrets/pods.eks.amazonaws.com/serviceaccount/eks-pod-identity-token",
+///     );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EKSPodIdentityCredentialProvider {
+    endpoint: Option<String>,
+    auth_token_file: Option<String>,
+}
+
+impl EKSPodIdentityCredentialProvider {
+    /// Create a new EKS Pod Identity credential provider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the EKS Pod Identity agent endpoint.
+    ///
+    /// An explicitly configured endpoint is used as-is, which allows pointing
+    /// the provider at a local stand-in of the agent.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Set the path of the authorization token file.
+    pub fn with_auth_token_file(mut self, file_path: impl Into<String>) -> Self {
+        self.auth_token_file = Some(file_path.into());
+        self
+    }
+
+    fn get_endpoint(&self, ctx: &Context) -> Option<String> {
+        if let Some(endpoint) = &self.endpoint {
+            return Some(endpoint.clone());
+        }
+
+        let full_uri = ctx.env_var(AWS_CONTAINER_CREDENTIALS_FULL_URI)?;
+        if is_eks_pod_identity_endpoint(&full_uri) {
+            Some(full_uri)
+        } else {
+            debug!("EKS Pod Identity provider: full URI {full_uri} is not an agent endpoint");
+            None
+        }
+    }
+
+    async fn load_auth_token(&self, ctx: &Context) -> Result<String> {
+        let token_file = self
+            .auth_token_file
+            .clone()
+            .or_else(|| ctx.env_var(AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE));
+
+        let token = match token_file {
+            Some(token_file) => {
+                let content = ctx.file_read(&token_file).await.map_err(|e| {
+                    Error::config_invalid("failed to read EKS Pod Identity token file")
+                        .with_source(e)
+                        .with_context(format!("file: {token_file}"))
+                        .with_context("hint: check if the pod identity association is configured")
+                })?;
+                String::from_utf8_lossy(&content).trim().to_string()
+            }
+            None => ctx
+                .env_var(AWS_CONTAINER_AUTHORIZATION_TOKEN)
+                .ok_or_else(|| {
+                    Error::config_invalid("EKS Pod Identity authorization token not configured")
+                        .with_context("hint: use with_auth_token_file() or set AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE")
+                })?,
+        };
+
+        if token.is_empty() || token.contains(['\r', '\n']) {
+            return Err(Error::config_invalid(
+                "EKS Pod Identity authorization token is invalid",
+            ));
+        }
+
+        Ok(token)
+    }
+}
+
+/// Check whether the endpoint targets the EKS Pod Identity agent.
+pub(crate) fn is_eks_pod_identity_endpoint(endpoint: &str) -> bool {
+    let Ok(uri) = endpoint.parse::<http::Uri>() else {
+        return false;
+    };
+    if !matches!(uri.scheme_str(), Some("http" | "https")) {
+        return false;
+    }
+
+    let host = uri.host().unwrap_or_default();
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    host == EKS_POD_IDENTITY_IPV4_HOST || host.eq_ignore_ascii_case(EKS_POD_IDENTITY_IPV6_HOST)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EKSPodIdentityCredentialResponse {
+    access_key_id: String,
+    secret_access_key: String,
+    token: String,
+    expiration: String,
+}
+
+impl ProvideCredential for EKSPodIdentityCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let Some(endpoint) = self.get_endpoint(ctx) else {
+            debug!("EKS Pod Identity provider: no agent endpoint found");
+            return Ok(None);
+        };
+
+        // Always read the token again: kubelet rotates the projected token.
+        let token = self.load_auth_token(ctx).await?;
+
+        debug!("EKS Pod Identity provider: fetching credentials from {endpoint}");
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(&endpoint)
+            .header(
+                http::header::AUTHORIZATION,
+                HeaderValue::from_str(&token).map_err(|e| {
+                    Error::config_invalid("invalid EKS Pod Identity authorization token")
+                        .with_source(e)
+                })?,
+            )
+            .body(bytes::Bytes::new())
+            .map_err(|e| {
+                Error::request_invalid("failed to build EKS Pod Identity credentials request")
+                    .with_source(e)
+                    .with_context(format!("endpoint: {endpoint}"))
+            })?;
+
+        let resp = ctx.http_send(req).await.map_err(|e| {
+            Error::unexpected("failed to fetch EKS Pod Identity credentials")
+                .with_source(e)
+                .with_context(format!("endpoint: {endpoint}"))
+                .with_context("hint: check if the EKS Pod Identity agent is running on the node")
+                .set_retryable(true)
+        })?;
+
+        if resp.status() != StatusCode::OK {
+            let status = resp.status();
+            let body = String::from_utf8_lossy(resp.body());
+
+            let error = match status.as_u16() {
+                400 => Error::config_invalid(format!(
+                    "EKS Pod Identity agent rejected the request: {body}"
+                ))
+                .with_context("hint: check the pod identity association of the service account"),
+                401 | 403 => Error::permission_denied(format!(
+                    "EKS Pod Identity agent denied the request: {body}"
+                ))
+                .with_context("hint: check if the service account token is valid"),
+                429 => Error::rate_limited(format!(
+                    "EKS Pod Identity agent throttled the request: {body}"
+                )),
+                500..=599 => Error::unexpected(format!("EKS Pod Identity agent error: {body}"))
+                    .set_retryable(true),
+                _ => Error::unexpected(format!(
+                    "EKS Pod Identity agent returned unexpected status {status}: {body}"
+                )),
+            };
+
+            return Err(error
+                .with_context(format!("http_status: {status}"))
+                .with_context(format!("endpoint: {endpoint}")));
+        }
+
+        let body = resp.into_body();
+        let creds: EKSPodIdentityCredentialResponse =
+            serde_json::from_slice(&body).map_err(|e| {
+                Error::unexpected("failed to parse EKS Pod Identity credentials response")
+                    .with_source(e)
+                    .with_context(format!("response_length: {}", body.len()))
+                    .with_context(format!("endpoint: {endpoint}"))
+            })?;
+
+        let expires_in = creds.expiration.parse().map_err(|e| {
+            Error::unexpected("failed to parse EKS Pod Identity credential expiration")
+                .with_source(e)
+                .with_context(format!("expiration_value: {}", creds.expiration))
+        })?;
+
+        Ok(Some(Credential {
+            access_key_id: creds.access_key_id,
+            secret_access_key: creds.secret_access_key,
+            session_token: Some(creds.token),
+            expires_in: Some(expires_in),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use reqsign_core::{ErrorKind, HttpSend, StaticEnv};
+    use reqsign_file_read_tokio::TokioFileRead;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    const RESPONSE: &str = r#"{
+        "AccessKeyId": "ASIAEKSPODIDENTITY",
+        "SecretAccessKey": "eks/secret/key",
+        "Token": "eks-session-token",
+        "AccountId": "123456789012",
+        "Expiration": "2124-05-25T11:45:17Z"
+    }"#;
+
+    /// Request URI and `Authorization` header seen by the agent.
+    type RecordedRequest = (String, Option<String>);
+
+    #[derive(Clone, Debug)]
+    struct CaptureHttpSend {
+        requests: Arc<Mutex<Vec<RecordedRequest>>>,
+        status: StatusCode,
+        body: &'static str,
+    }
+
+    impl CaptureHttpSend {
+        fn new(status: StatusCode, body: &'static str) -> Self {
+            Self {
+                requests: Arc::new(Mutex::new(Vec::new())),
+                status,
+                body,
+            }
+        }
+
+        fn requests(&self) -> Vec<RecordedRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl HttpSend for CaptureHttpSend {
+        async fn http_send(&self, req: http::Request<Bytes>) -> Result<http::Response<Bytes>> {
+            let authorization = req
+                .headers()
+                .get(http::header::AUTHORIZATION)
+                .map(|v| v.to_str().unwrap().to_string());
+            self.requests
+                .lock()
+                .unwrap()
+                .push((req.uri().to_string(), authorization));
+            Ok(http::Response::builder()
+                .status(self.status)
+                .body(Bytes::from_static(self.body.as_bytes()))
+                .expect("response must build"))
+        }
+    }
+
+    fn context(http: CaptureHttpSend, envs: HashMap<String, String>) -> Context {
+        Context::new()
+            .with_file_read(TokioFileRead)
+            .with_http_send(http)
+            .with_env(StaticEnv {
+                home_dir: None,
+                envs,
+            })
+    }
+
+    #[test]
+    fn test_detects_agent_endpoints() {
+        assert!(is_eks_pod_identity_endpoint(
+            "http://169.254.170.23/v1/credentials"
+        ));
+        assert!(is_eks_pod_identity_endpoint(
+            "http://[fd00:ec2::23]/v1/credentials"
+        ));
+        assert!(is_eks_pod_identity_endpoint(
+            "http://[FD00:EC2::23]:80/v1/credentials"
+        ));
+        assert!(!is_eks_pod_identity_endpoint(
+            "http://169.254.170.2/v2/credentials"
+        ));
+        assert!(!is_eks_pod_identity_endpoint(
+            "http://localhost:8080/credentials"
+        ));
+        assert!(!is_eks_pod_identity_endpoint(
+            "169.254.170.23/v1/credentials"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_skips_non_agent_full_uri() {
+        let http = CaptureHttpSend::new(StatusCode::OK, RESPONSE);
+        let ctx = context(
+            http.clone(),
+            HashMap::from([(
+                AWS_CONTAINER_CREDENTIALS_FULL_URI.to_string(),
+                "http://169.254.170.2/v2/credentials".to_string(),
+            )]),
+        );
+
+        let cred = EKSPodIdentityCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+        assert!(cred.is_none());
+        assert!(http.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rereads_token_file_on_each_refresh() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let token_file = tmp_dir.path().join("eks-pod-identity-token");
+        std::fs::write(&token_file, "first-token\n")?;
+
+        let http = CaptureHttpSend::new(StatusCode::OK, RESPONSE);
+        let ctx = context(
+            http.clone(),
+            HashMap::from([
+                (
+                    AWS_CONTAINER_CREDENTIALS_FULL_URI.to_string(),
+                    "http://[fd00:ec2::23]/v1/credentials".to_string(),
+                ),
+                (
+                    AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE.to_string(),
+                    token_file.to_string_lossy().to_string(),
+                ),
+                (
+                    AWS_CONTAINER_AUTHORIZATION_TOKEN.to_string(),
+                    "env-token".to_string(),
+                ),
+            ]),
+        );
+
+        let provider = EKSPodIdentityCredentialProvider::new();
+        let cred = provider
+            .provide_credential(&ctx)
+            .await?
+            .expect("credential must be loaded");
+        assert_eq!(cred.access_key_id, "ASIAEKSPODIDENTITY");
+        assert_eq!(cred.secret_access_key, "eks/secret/key");
+        assert_eq!(cred.session_token.as_deref(), Some("eks-session-token"));
+        assert!(cred.expires_in.is_some());
+
+        std::fs::write(&token_file, "rotated-token")?;
+        provider.provide_credential(&ctx).await?;
+
+        assert_eq!(
+            http.requests(),
+            vec![
+                (
+                    "http://[fd00:ec2::23]/v1/credentials".to_string(),
+                    Some("first-token".to_string())
+                ),
+                (
+                    "http://[fd00:ec2::23]/v1/credentials".to_string(),
+                    Some("rotated-token".to_string())
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_configured_values_override_env() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let token_file = tmp_dir.path().join("token");
+        std::fs::write(&token_file, "configured-token")?;
+
+        let http = CaptureHttpSend::new(StatusCode::OK, RESPONSE);
+        let ctx = context(
+            http.clone(),
+            HashMap::from([
+                (
+                    AWS_CONTAINER_CREDENTIALS_FULL_URI.to_string(),
+                    "http://169.254.170.23/v1/credentials".to_string(),
+                ),
+                (
+                    AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE.to_string(),
+                    "/non/existent/token".to_string(),
+                ),
+            ]),
+        );
+
+        let provider = EKSPodIdentityCredentialProvider::new()
+            .with_endpoint("http://127.0.0.1:2703/v1/credentials")
+            .with_auth_token_file(token_file.to_string_lossy());
+        provider.provide_credential(&ctx).await?;
+
+        assert_eq!(
+            http.requests(),
+            vec![(
+                "http://127.0.0.1:2703/v1/credentials".to_string(),
+                Some("configured-token".to_string())
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_config_error() {
+        let http = CaptureHttpSend::new(StatusCode::OK, RESPONSE);
+        let ctx = context(
+            http.clone(),
+            HashMap::from([(
+                AWS_CONTAINER_CREDENTIALS_FULL_URI.to_string(),
+                "http://169.254.170.23/v1/credentials".to_string(),
+            )]),
+        );
+
+        let err = EKSPodIdentityCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .expect_err("token is required");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(http.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_agent_errors_are_mapped() {
+        let http = CaptureHttpSend::new(StatusCode::INTERNAL_SERVER_ERROR, "agent unavailable");
+        let ctx = context(
+            http,
+            HashMap::from([
+                (
+                    AWS_CONTAINER_CREDENTIALS_FULL_URI.to_string(),
+                    "http://169.254.170.23/v1/credentials".to_string(),
+                ),
+                (
+                    AWS_CONTAINER_AUTHORIZATION_TOKEN.to_string(),
+                    "env-token".to_string(),
+                ),
+            ]),
+        );
+
+        let err = EKSPodIdentityCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .expect_err("agent error must surface");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_retryable());
+    }
+}
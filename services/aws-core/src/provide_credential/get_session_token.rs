@@ -0,0 +1,117 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::{Debug, Formatter};
+
+use reqsign_core::{Context, ProvideCredential, Result, Signer};
+
+use crate::Credential;
+use crate::endpoint::EndpointConfig;
+use crate::session_token::{GetSessionTokenGrant, SessionTokenOperation, resolve_sts_endpoint};
+
+/// Loads credentials through one fixed AWS STS `GetSessionToken` flow.
+///
+/// `sts_signer` must load long-term IAM user credentials; STS rejects
+/// `GetSessionToken` calls signed with temporary credentials. Use
+/// [`reqsign_core::Granter`] with the SigV4 crate's explicit
+/// `GetSessionTokenGranter` when the source credential is supplied per
+/// vending operation.
+pub struct GetSessionTokenCredentialProvider {
+    grant: GetSessionTokenGrant,
+    duration_seconds: Option<u32>,
+    region: Option<String>,
+    use_regional_sts_endpoint: bool,
+    endpoint_config: EndpointConfig,
+    sts_signer: Signer<Credential>,
+}
+
+impl Debug for GetSessionTokenCredentialProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GetSessionTokenCredentialProvider")
+            .finish_non_exhaustive()
+    }
+}
+
+impl GetSessionTokenCredentialProvider {
+    /// Create a new fixed-flow GetSessionToken credential provider.
+    pub fn new(sts_signer: Signer<Credential>) -> Self {
+        Self {
+            grant: GetSessionTokenGrant::new(),
+            duration_seconds: Some(3_600),
+            region: None,
+            use_regional_sts_endpoint: false,
+            endpoint_config: EndpointConfig::default(),
+            sts_signer,
+        }
+    }
+
+    /// Set the duration in seconds.
+    pub fn with_duration_seconds(mut self, seconds: u32) -> Self {
+        self.duration_seconds = Some(seconds);
+        self
+    }
+
+    /// Set the MFA serial number.
+    pub fn with_mfa_serial(mut self, serial_number: String) -> Self {
+        self.grant.serial_number = Some(serial_number);
+        self
+    }
+
+    /// Set the MFA token code.
+    pub fn with_mfa_code(mut self, token_code: String) -> Self {
+        self.grant.token_code = Some(token_code);
+        self
+    }
+
+    /// Set the region used to select a regional STS endpoint.
+    pub fn with_region(mut self, region: String) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Use a regional STS endpoint.
+    pub fn with_regional_sts_endpoint(mut self) -> Self {
+        self.use_regional_sts_endpoint = true;
+        self
+    }
+
+    /// Set the FIPS, dual-stack, and endpoint URL settings for STS.
+    pub fn with_endpoint_config(mut self, config: EndpointConfig) -> Self {
+        self.endpoint_config = config;
+        self
+    }
+}
+
+impl ProvideCredential for GetSessionTokenCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let endpoint = resolve_sts_endpoint(
+            ctx,
+            &self.endpoint_config,
+            self.region.as_deref(),
+            self.use_regional_sts_endpoint,
+        )
+        .await?;
+        let operation = SessionTokenOperation::get_session_token(
+            &endpoint,
+            &self.grant,
+            self.duration_seconds,
+        )?;
+        operation.execute(ctx, &self.sts_signer).await.map(Some)
+    }
+}
@@ -16,27 +16,117 @@
 // under the License.
 
 use crate::Credential;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::constants::AWS_PROFILE;
+use crate::provide_credential::static_stability::StaticStability;
 use crate::provide_credential::utils::parse_imds_error;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::shared_config::ProfileFiles;
 use bytes::Bytes;
 use http::Method;
 use http::header::CONTENT_LENGTH;
+use log::debug;
 use reqsign_core::time::Timestamp;
 use reqsign_core::{Context, Error, ProvideCredential, Result};
 use serde::Deserialize;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+const AWS_EC2_METADATA_DISABLED: &str = "AWS_EC2_METADATA_DISABLED";
+const AWS_EC2_METADATA_SERVICE_ENDPOINT: &str = "AWS_EC2_METADATA_SERVICE_ENDPOINT";
+const AWS_EC2_METADATA_SERVICE_ENDPOINT_MODE: &str = "AWS_EC2_METADATA_SERVICE_ENDPOINT_MODE";
+const AWS_EC2_METADATA_V1_DISABLED: &str = "AWS_EC2_METADATA_V1_DISABLED";
+const AWS_EC2_INSTANCE_PROFILE_NAME: &str = "AWS_EC2_INSTANCE_PROFILE_NAME";
+
+const IMDS_IPV4_ENDPOINT: &str = "http://169.254.169.254";
+const IMDS_IPV6_ENDPOINT: &str = "http://[fd00:ec2::254]";
+/// 21600s (6h) is recommended by AWS.
+const IMDS_TOKEN_TTL: Duration = Duration::from_secs(21600);
+
+/// Addressing mode used to reach the EC2 instance metadata service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IMDSEndpointMode {
+    /// Use `http://169.254.169.254`.
+    #[default]
+    IPv4,
+    /// Use `http://[fd00:ec2::254]`.
+    IPv6,
+}
+
+impl IMDSEndpointMode {
+    fn endpoint(self) -> &'static str {
+        match self {
+            IMDSEndpointMode::IPv4 => IMDS_IPV4_ENDPOINT,
+            IMDSEndpointMode::IPv6 => IMDS_IPV6_ENDPOINT,
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self> {
+        if value.eq_ignore_ascii_case("ipv4") {
+            Ok(IMDSEndpointMode::IPv4)
+        } else if value.eq_ignore_ascii_case("ipv6") {
+            Ok(IMDSEndpointMode::IPv6)
+        } else {
+            Err(
+                Error::config_invalid(format!("invalid IMDS endpoint mode: {value}"))
+                    .with_context("hint: expected 'IPv4' or 'IPv6'"),
+            )
+        }
+    }
+}
+
+/// IMDSv2CredentialProvider loads credentials from the EC2 instance metadata service.
+///
+/// Configuration values can be provided directly via builder methods, through
+/// environment variables, or through the selected shared config profile:
+///
+/// | Setting | Environment variable | Profile key |
+/// |---------|----------------------|-------------|
+/// | Endpoint | `AWS_EC2_METADATA_SERVICE_ENDPOINT` | `ec2_metadata_service_endpoint` |
+/// | Endpoint mode | `AWS_EC2_METADATA_SERVICE_ENDPOINT_MODE` | `ec2_metadata_service_endpoint_mode` |
+/// | Disable IMDSv1 fallback | `AWS_EC2_METADATA_V1_DISABLED` | `ec2_metadata_v1_disabled` |
+/// | Instance profile name | `AWS_EC2_INSTANCE_PROFILE_NAME` | `ec2_instance_profile_name` |
+///
+/// The provider is disabled when `AWS_EC2_METADATA_DISABLED` is `true`.
+///
+/// A session token is requested first and cached for its TTL. If the token
+/// request fails, the provider falls back to IMDSv1 requests unless IMDSv1 is
+/// disabled.
+///
+/// If IMDS returns expired credentials or is unavailable after credentials were
+/// loaded once, the last known credentials are reused with their expiration
+/// extended by 15 to 20 minutes, so that signing keeps working during short
+/// metadata service outages. Errors that are not retryable are returned as
+/// is.
 #[derive(Debug, Clone)]
 pub struct IMDSv2CredentialProvider {
     endpoint: Option<String>,
+    endpoint_mode: Option<IMDSEndpointMode>,
+    disable_imdsv1: Option<bool>,
+    instance_profile_name: Option<String>,
+    token_ttl: Duration,
+    #[cfg(not(target_arch = "wasm32"))]
+    profile: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    files: ProfileFiles,
     token: Arc<Mutex<(String, Timestamp)>>,
+    static_stability: StaticStability,
 }
 
 impl Default for IMDSv2CredentialProvider {
     fn default() -> Self {
         Self {
             endpoint: None,
+            endpoint_mode: None,
+            disable_imdsv1: None,
+            instance_profile_name: None,
+            token_ttl: IMDS_TOKEN_TTL,
+            #[cfg(not(target_arch = "wasm32"))]
+            profile: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            files: ProfileFiles::default(),
             token: Arc::new(Mutex::new((String::new(), Timestamp::default()))),
+            static_stability: StaticStability::default(),
         }
     }
 }
@@ -48,39 +138,171 @@ impl IMDSv2CredentialProvider {
     }
 
     /// Set the endpoint for the metadata service.
+    ///
+    /// This setting takes precedence over the endpoint mode.
     pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
         self.endpoint = Some(endpoint.into());
         self
     }
+
+    /// Set the endpoint mode used when no endpoint is configured.
+    pub fn with_endpoint_mode(mut self, mode: IMDSEndpointMode) -> Self {
+        self.endpoint_mode = Some(mode);
+        self
+    }
+
+    /// Configure whether IMDSv1 fallback is disabled.
+    ///
+    /// This setting takes precedence over `AWS_EC2_METADATA_V1_DISABLED`.
+    pub fn with_disable_imdsv1(mut self, disable: bool) -> Self {
+        self.disable_imdsv1 = Some(disable);
+        self
+    }
+
+    /// Set the instance profile name instead of discovering it from IMDS.
+    pub fn with_instance_profile_name(mut self, name: impl Into<String>) -> Self {
+        self.instance_profile_name = Some(name.into());
+        self
+    }
+
+    /// Set the TTL requested for IMDS session tokens.
+    ///
+    /// Tokens are reused until shortly before the TTL elapses. Defaults to 6 hours.
+    pub fn with_token_ttl(mut self, ttl: Duration) -> Self {
+        self.token_ttl = ttl.clamp(Duration::from_secs(1), IMDS_TOKEN_TTL);
+        self
+    }
+
+    /// Set the profile used to read IMDS settings from the shared config.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Set the path to the config file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_config_file(mut self, path: impl Into<String>) -> Self {
+        self.files = self.files.with_config_file(path);
+        self
+    }
+}
+
+/// IMDS settings resolved from the builder, environment, and profile.
+struct IMDSConfig {
+    endpoint: String,
+    disable_imdsv1: bool,
+    instance_profile_name: Option<String>,
 }
 
 impl IMDSv2CredentialProvider {
-    fn get_endpoint(&self, ctx: &Context) -> String {
-        // First check configured endpoint, then environment, then default
-        self.endpoint.clone().unwrap_or_else(|| {
-            ctx.env_vars()
-                .get("AWS_EC2_METADATA_SERVICE_ENDPOINT")
-                .cloned()
-                .unwrap_or_else(|| "http://169.254.169.254".into())
+    async fn resolve_config(&self, ctx: &Context) -> Result<IMDSConfig> {
+        let profile = self.load_profile_settings(ctx).await;
+        let setting = |env: &str, key: &str| {
+            ctx.env_var(env)
+                .or_else(|| {
+                    profile
+                        .iter()
+                        .find(|(k, _)| *k == key)
+                        .map(|(_, v)| v.clone())
+                })
+                .filter(|v| !v.is_empty())
+        };
+
+        let endpoint = match self.endpoint.clone().or_else(|| {
+            setting(
+                AWS_EC2_METADATA_SERVICE_ENDPOINT,
+                "ec2_metadata_service_endpoint",
+            )
+        }) {
+            Some(endpoint) => endpoint,
+            None => {
+                let mode = match self.endpoint_mode {
+                    Some(mode) => mode,
+                    None => setting(
+                        AWS_EC2_METADATA_SERVICE_ENDPOINT_MODE,
+                        "ec2_metadata_service_endpoint_mode",
+                    )
+                    .map(|v| IMDSEndpointMode::parse(&v))
+                    .transpose()?
+                    .unwrap_or_default(),
+                };
+                mode.endpoint().to_string()
+            }
+        };
+
+        let disable_imdsv1 = self.disable_imdsv1.unwrap_or_else(|| {
+            setting(AWS_EC2_METADATA_V1_DISABLED, "ec2_metadata_v1_disabled")
+                .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+        });
+
+        let instance_profile_name = self
+            .instance_profile_name
+            .clone()
+            .or_else(|| setting(AWS_EC2_INSTANCE_PROFILE_NAME, "ec2_instance_profile_name"));
+
+        Ok(IMDSConfig {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            disable_imdsv1,
+            instance_profile_name,
         })
     }
 
-    async fn load_ec2_metadata_token(&self, ctx: &Context) -> Result<String> {
+    /// Read the IMDS keys of the selected profile.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn load_profile_settings(&self, ctx: &Context) -> Vec<(&'static str, String)> {
+        let profile_name = self
+            .profile
+            .clone()
+            .or_else(|| ctx.env_var(AWS_PROFILE))
+            .unwrap_or_else(|| "default".to_string());
+        let Ok(profiles) = self.files.load(ctx).await else {
+            return Vec::new();
+        };
+        let Some(profile) = profiles.profile(&profile_name) else {
+            return Vec::new();
+        };
+        [
+            "ec2_metadata_service_endpoint",
+            "ec2_metadata_service_endpoint_mode",
+            "ec2_metadata_v1_disabled",
+            "ec2_instance_profile_name",
+        ]
+        .into_iter()
+        .filter_map(|key| profile.get(key).map(|v| (key, v.to_string())))
+        .collect()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn load_profile_settings(&self, _: &Context) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Fetch an IMDSv2 session token.
+    ///
+    /// Returns `None` when the token request fails and IMDSv1 fallback is allowed.
+    async fn load_ec2_metadata_token(
+        &self,
+        ctx: &Context,
+        config: &IMDSConfig,
+    ) -> Result<Option<String>> {
         {
             let (token, expires_in) = self.token.lock().expect("lock poisoned").clone();
             if expires_in > Timestamp::now() {
-                return Ok(token);
+                return Ok(Some(token));
             }
         }
 
-        let endpoint = self.get_endpoint(ctx);
+        let endpoint = &config.endpoint;
         let url = format!("{endpoint}/latest/api/token");
         let req = http::Request::builder()
             .uri(&url)
             .method(Method::PUT)
             .header(CONTENT_LENGTH, "0")
-            // 21600s (6h) is recommended by AWS.
-            .header("x-aws-ec2-metadata-token-ttl-seconds", "21600")
+            .header(
+                "x-aws-ec2-metadata-token-ttl-seconds",
+                self.token_ttl.as_secs().to_string(),
+            )
             .body(Bytes::new())
             .map_err(|e| {
                 Error::request_invalid("failed to build IMDS token request")
@@ -88,15 +310,29 @@ impl IMDSv2CredentialProvider {
                     .with_context(format!("url: {url}"))
             })?;
 
-        let resp = ctx.http_send_as_string(req).await.map_err(|e| {
-            Error::unexpected("failed to connect to IMDS")
-                .with_source(e)
-                .with_context("endpoint: {endpoint}")
-                .with_context("hint: check if running on EC2 instance")
-                .set_retryable(true)
-        })?;
+        let resp = match ctx.http_send_as_string(req).await {
+            Ok(resp) => resp,
+            Err(e) if config.disable_imdsv1 => {
+                return Err(Error::unexpected("failed to connect to IMDS")
+                    .with_source(e)
+                    .with_context(format!("endpoint: {endpoint}"))
+                    .with_context("hint: check if running on EC2 instance")
+                    .set_retryable(true));
+            }
+            Err(e) => {
+                debug!("failed to fetch IMDS token, falling back to IMDSv1: {e}");
+                return Ok(None);
+            }
+        };
 
         if resp.status() != http::StatusCode::OK {
+            if !config.disable_imdsv1 {
+                debug!(
+                    "IMDS token request returned {}, falling back to IMDSv1",
+                    resp.status()
+                );
+                return Ok(None);
+            }
             return Err(parse_imds_error(
                 "fetch_imds_token",
                 resp.status(),
@@ -104,64 +340,64 @@ impl IMDSv2CredentialProvider {
             ));
         }
         let ec2_token = resp.into_body();
-        // Set expires_in to 10 minutes to enforce re-read.
-        let expires_in = Timestamp::now() + Duration::from_secs(21600) - Duration::from_secs(600);
+        // Refresh the token before it expires: 10 minutes early for the default
+        // TTL, or halfway through shorter TTLs.
+        let refresh_buffer = Duration::from_secs(600).min(self.token_ttl / 2);
+        let expires_in = Timestamp::now() + self.token_ttl - refresh_buffer;
 
         {
             *self.token.lock().expect("lock poisoned") = (ec2_token.clone(), expires_in);
         }
 
-        Ok(ec2_token)
+        Ok(Some(ec2_token))
     }
-}
-impl ProvideCredential for IMDSv2CredentialProvider {
-    type Credential = Credential;
 
-    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
-        // Check if disabled via environment
-        let disabled_env = ctx
-            .env_vars()
-            .get("AWS_EC2_METADATA_DISABLED")
-            .map(|v| v == "true")
-            .unwrap_or(false);
-
-        if disabled_env {
-            return Ok(None);
+    fn build_get_request(&self, url: &str, token: Option<&str>) -> Result<http::Request<Bytes>> {
+        let mut req = http::Request::builder().uri(url).method(Method::GET);
+        if let Some(token) = token {
+            req = req.header("x-aws-ec2-metadata-token", token);
         }
+        req.body(Bytes::new()).map_err(|e| {
+            Error::request_invalid("failed to build IMDS request")
+                .with_source(e)
+                .with_context(format!("url: {url}"))
+        })
+    }
 
-        let token = self.load_ec2_metadata_token(ctx).await?;
+    async fn load_credential(&self, ctx: &Context, config: &IMDSConfig) -> Result<Credential> {
+        let token = self.load_ec2_metadata_token(ctx, config).await?;
+        let endpoint = &config.endpoint;
 
-        // List all credentials that node has.
-        let endpoint = self.get_endpoint(ctx);
-        let url = format!("{endpoint}/latest/meta-data/iam/security-credentials/");
-        let req = http::Request::builder()
-            .uri(&url)
-            .method(Method::GET)
-            // 21600s (6h) is recommended by AWS.
-            .header("x-aws-ec2-metadata-token", &token)
-            .body(Bytes::new())
-            .map_err(|e| {
-                Error::request_invalid("failed to build IMDS credentials list request")
-                    .with_source(e)
-                    .with_context("url: {url}")
-            })?;
+        let profile_name = match &config.instance_profile_name {
+            Some(name) => name.clone(),
+            None => {
+                // List all credentials that node has.
+                let url = format!("{endpoint}/latest/meta-data/iam/security-credentials/");
+                let req = self.build_get_request(&url, token.as_deref())?;
 
-        let resp = ctx.http_send_as_string(req).await.map_err(|e| {
-            Error::unexpected("failed to list IMDS credentials")
-                .with_source(e)
-                .with_context("operation: list_instance_profiles")
-                .set_retryable(true)
-        })?;
+                let resp = ctx.http_send_as_string(req).await.map_err(|e| {
+                    Error::unexpected("failed to list IMDS credentials")
+                        .with_source(e)
+                        .with_context("operation: list_instance_profiles")
+                        .set_retryable(true)
+                })?;
 
-        if resp.status() != http::StatusCode::OK {
-            return Err(parse_imds_error(
-                "list_instance_profiles",
-                resp.status(),
-                resp.body(),
-            ));
-        }
+                if resp.status() != http::StatusCode::OK {
+                    return Err(parse_imds_error(
+                        "list_instance_profiles",
+                        resp.status(),
+                        resp.body(),
+                    ));
+                }
 
-        let profile_name = resp.into_body();
+                resp.into_body()
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            }
+        };
 
         if profile_name.is_empty() {
             return Err(
@@ -171,19 +407,10 @@ impl ProvideCredential for IMDSv2CredentialProvider {
         }
 
         // Get the credentials via role_name.
-        let endpoint = self.get_endpoint(ctx);
         let url = format!("{endpoint}/latest/meta-data/iam/security-credentials/{profile_name}");
-        let req = http::Request::builder()
-            .uri(url)
-            .method(Method::GET)
-            // 21600s (6h) is recommended by AWS.
-            .header("x-aws-ec2-metadata-token", &token)
-            .body(Bytes::new())
-            .map_err(|e| {
-                Error::request_invalid("failed to build IMDS credentials fetch request")
-                    .with_source(e)
-                    .with_context(format!("profile: {profile_name}"))
-            })?;
+        let req = self
+            .build_get_request(&url, token.as_deref())
+            .map_err(|e| e.with_context(format!("profile: {profile_name}")))?;
 
         let resp = ctx.http_send_as_string(req).await.map_err(|e| {
             Error::unexpected("failed to fetch IMDS credentials")
@@ -248,6 +475,28 @@ impl ProvideCredential for IMDSv2CredentialProvider {
             })?),
         };
 
+        Ok(cred)
+    }
+}
+
+impl ProvideCredential for IMDSv2CredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        // Check if disabled via environment
+        let disabled_env = ctx
+            .env_var(AWS_EC2_METADATA_DISABLED)
+            .is_some_and(|v| v.eq_ignore_ascii_case("true"));
+
+        if disabled_env {
+            return Ok(None);
+        }
+
+        let config = self.resolve_config(ctx).await?;
+        let cred = match self.load_credential(ctx, &config).await {
+            Ok(cred) => self.static_stability.record("IMDS", cred),
+            Err(err) => self.static_stability.fallback("IMDS", err)?,
+        };
         Ok(Some(cred))
     }
 }
@@ -263,3 +512,201 @@ struct Ec2MetadataIamSecurityCredentials {
     code: String,
     message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqsign_core::{ErrorKind, HttpSend, StaticEnv};
+    use reqsign_file_read_tokio::TokioFileRead;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Request method, URI, and IMDS session token seen by the metadata service.
+    type RecordedRequest = (Method, String, Option<String>);
+
+    #[derive(Clone, Debug, Default)]
+    struct MockIMDS {
+        requests: Arc<Mutex<Vec<RecordedRequest>>>,
+        token_status: Option<http::StatusCode>,
+        outage: Arc<AtomicBool>,
+    }
+
+    impl HttpSend for MockIMDS {
+        async fn http_send(&self, req: http::Request<Bytes>) -> Result<http::Response<Bytes>> {
+            self.requests.lock().unwrap().push((
+                req.method().clone(),
+                req.uri().to_string(),
+                req.headers()
+                    .get("x-aws-ec2-metadata-token")
+                    .map(|v| v.to_str().unwrap().to_string()),
+            ));
+            if self.outage.load(Ordering::SeqCst) {
+                return Err(Error::unexpected("connection refused"));
+            }
+
+            let (status, body): (_, &'static [u8]) = match req.uri().path() {
+                "/latest/api/token" => (
+                    self.token_status.unwrap_or(http::StatusCode::OK),
+                    b"imds-session-token",
+                ),
+                "/latest/meta-data/iam/security-credentials/" => {
+                    (http::StatusCode::OK, b"discovered-role\n")
+                }
+                _ => (
+                    http::StatusCode::OK,
+                    br#"{"Code":"Success","AccessKeyId":"ASIAIMDSEXAMPLE","SecretAccessKey":"secret","Token":"token","Expiration":"2099-01-01T00:00:00Z"}"#,
+                ),
+            };
+            Ok(http::Response::builder()
+                .status(status)
+                .body(Bytes::from_static(body))
+                .unwrap())
+        }
+    }
+
+    fn test_context(http: MockIMDS, envs: &[(&str, &str)]) -> Context {
+        Context::new()
+            .with_file_read(TokioFileRead)
+            .with_http_send(http)
+            .with_env(StaticEnv {
+                home_dir: None,
+                envs: envs
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<HashMap<_, _>>(),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_imds_provider_ipv6_endpoint_mode() -> anyhow::Result<()> {
+        let http = MockIMDS::default();
+        let ctx = test_context(
+            http.clone(),
+            &[(AWS_EC2_METADATA_SERVICE_ENDPOINT_MODE, "IPv6")],
+        );
+
+        let cred = IMDSv2CredentialProvider::new()
+            .provide_credential(&ctx)
+            .await?
+            .expect("credential must be loaded");
+        assert_eq!(cred.access_key_id, "ASIAIMDSEXAMPLE");
+
+        let requests = http.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].1, "http://[fd00:ec2::254]/latest/api/token");
+        assert_eq!(
+            requests[2].1,
+            "http://[fd00:ec2::254]/latest/meta-data/iam/security-credentials/discovered-role"
+        );
+        assert_eq!(requests[2].2.as_deref(), Some("imds-session-token"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_imds_provider_reads_profile_settings() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let config = dir.path().join("config");
+        std::fs::write(
+            &config,
+            "[profile ec2]\nec2_metadata_service_endpoint_mode = IPv6\nec2_instance_profile_name = configured-role\n",
+        )?;
+
+        let http = MockIMDS::default();
+        let ctx = test_context(http.clone(), &[]);
+        IMDSv2CredentialProvider::new()
+            .with_profile("ec2")
+            .with_config_file(config.to_string_lossy())
+            .provide_credential(&ctx)
+            .await?
+            .expect("credential must be loaded");
+
+        let requests = http.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].1,
+            "http://[fd00:ec2::254]/latest/meta-data/iam/security-credentials/configured-role"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_imds_provider_caches_session_token() -> anyhow::Result<()> {
+        let http = MockIMDS::default();
+        let ctx = test_context(http.clone(), &[]);
+        let provider = IMDSv2CredentialProvider::new()
+            .with_endpoint("http://imds.test")
+            .with_token_ttl(Duration::from_secs(60));
+
+        provider.provide_credential(&ctx).await?;
+        provider.provide_credential(&ctx).await?;
+
+        let requests = http.requests.lock().unwrap().clone();
+        let token_requests = requests.iter().filter(|r| r.0 == Method::PUT).count();
+        assert_eq!(token_requests, 1);
+        assert_eq!(requests.len(), 5);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_imds_provider_falls_back_to_imdsv1() -> anyhow::Result<()> {
+        let http = MockIMDS {
+            token_status: Some(http::StatusCode::FORBIDDEN),
+            ..Default::default()
+        };
+        let ctx = test_context(http.clone(), &[]);
+        let cred = IMDSv2CredentialProvider::new()
+            .with_endpoint("http://imds.test")
+            .provide_credential(&ctx)
+            .await?;
+        assert!(cred.is_some());
+
+        let requests = http.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1..].iter().all(|r| r.2.is_none()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_imds_provider_respects_imdsv1_disable() {
+        let http = MockIMDS {
+            token_status: Some(http::StatusCode::FORBIDDEN),
+            ..Default::default()
+        };
+        let ctx = test_context(http.clone(), &[(AWS_EC2_METADATA_V1_DISABLED, "true")]);
+        let err = IMDSv2CredentialProvider::new()
+            .with_endpoint("http://imds.test")
+            .provide_credential(&ctx)
+            .await
+            .expect_err("provider must fail when IMDSv1 is disabled");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert_eq!(http.requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_imds_provider_reuses_credential_during_outage() -> anyhow::Result<()> {
+        let http = MockIMDS::default();
+        let ctx = test_context(http.clone(), &[]);
+        let provider = IMDSv2CredentialProvider::new().with_endpoint("http://imds.test");
+
+        let cred = provider
+            .provide_credential(&ctx)
+            .await?
+            .expect("credential must be loaded");
+        http.outage.store(true, Ordering::SeqCst);
+        let reused = provider
+            .provide_credential(&ctx)
+            .await?
+            .expect("last credential must be reused");
+        assert_eq!(reused.access_key_id, cred.access_key_id);
+        assert_eq!(reused.expires_in, cred.expires_in);
+
+        let err = IMDSv2CredentialProvider::new()
+            .with_endpoint("http://imds.test")
+            .with_disable_imdsv1(true)
+            .provide_credential(&ctx)
+            .await
+            .expect_err("provider without previous credential must fail");
+        assert!(err.is_retryable());
+        Ok(())
+    }
+}
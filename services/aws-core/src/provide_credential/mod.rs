@@ -18,6 +18,9 @@
 mod assume_role;
 pub use assume_role::AssumeRoleCredentialProvider;
 
+mod assume_role_with_saml;
+pub use assume_role_with_saml::AssumeRoleWithSAMLCredentialProvider;
+
 mod assume_role_with_web_identity;
 pub use assume_role_with_web_identity::AssumeRoleWithWebIdentityCredentialProvider;
 
@@ -30,11 +33,25 @@ pub use default::{DefaultCredentialProvider, DefaultCredentialProviderBuilder};
 mod ecs;
 pub use ecs::ECSCredentialProvider;
 
+mod eks_pod_identity;
+pub use eks_pod_identity::EKSPodIdentityCredentialProvider;
+
 mod env;
 pub use env::EnvCredentialProvider;
 
+mod get_federation_token;
+pub use get_federation_token::GetFederationTokenCredentialProvider;
+
+mod get_session_token;
+pub use get_session_token::GetSessionTokenCredentialProvider;
+
 mod imds;
-pub use imds::IMDSv2CredentialProvider;
+pub use imds::{IMDSEndpointMode, IMDSv2CredentialProvider};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod login;
+#[cfg(not(target_arch = "wasm32"))]
+pub use login::LoginCredentialProvider;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod process;
@@ -49,7 +66,13 @@ mod sso;
 #[cfg(not(target_arch = "wasm32"))]
 pub use sso::SSOCredentialProvider;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod roles_anywhere;
+#[cfg(not(target_arch = "wasm32"))]
+pub use roles_anywhere::RolesAnywhereCredentialProvider;
+
 mod r#static;
 pub use r#static::StaticCredentialProvider;
 
+mod static_stability;
 pub(crate) mod utils;
@@ -16,7 +16,7 @@
 // under the License.
 
 use crate::Credential;
-use ini::Ini;
+use crate::shared_config::ProfileFiles;
 use log::debug;
 use reqsign_core::{Context, Error, ProvideCredential, Result};
 use serde::Deserialize;
@@ -48,6 +48,7 @@ use serde::Deserialize;
 pub struct ProcessCredentialProvider {
     profile: Option<String>,
     command: Option<String>,
+    files: ProfileFiles,
 }
 
 impl Default for ProcessCredentialProvider {
@@ -62,6 +63,7 @@ impl ProcessCredentialProvider {
         Self {
             profile: None,
             command: None,
+            files: ProfileFiles::new(),
         }
     }
 
@@ -71,6 +73,18 @@ impl ProcessCredentialProvider {
         self
     }
 
+    /// Set the path to the config file
+    pub fn with_config_file(mut self, path: impl Into<String>) -> Self {
+        self.files = self.files.with_config_file(path);
+        self
+    }
+
+    /// Set the path to the credentials file
+    pub fn with_credentials_file(mut self, path: impl Into<String>) -> Self {
+        self.files = self.files.with_credentials_file(path);
+        self
+    }
+
     /// Set the command directly
     pub fn with_command(mut self, command: impl Into<String>) -> Self {
         self.command = Some(command.into());
@@ -83,7 +97,7 @@ impl ProcessCredentialProvider {
             return Ok(cmd.clone());
         }
 
-        // Otherwise, load from config file
+        // Otherwise, load from the shared config files
         // Priority: 1. self.profile, 2. AWS_PROFILE env var, 3. "default"
         let profile_name = self
             .profile
@@ -94,34 +108,8 @@ impl ProcessCredentialProvider {
     }
 
     async fn load_command_from_config(&self, ctx: &Context, profile: &str) -> Result<String> {
-        // Load AWS config file
-        let config_path = ctx
-            .env_var("AWS_CONFIG_FILE")
-            .unwrap_or_else(|| "~/.aws/config".to_string());
-
-        let expanded_path = if config_path.starts_with("~/") {
-            match ctx.expand_home_dir(&config_path) {
-                Some(expanded) => expanded,
-                None => return Err(Error::config_invalid("failed to expand home directory")),
-            }
-        } else {
-            config_path
-        };
-
-        let content = ctx.file_read(&expanded_path).await.map_err(|_| {
-            Error::config_invalid(format!("failed to read config file: {expanded_path}"))
-        })?;
-
-        let conf = Ini::load_from_str(&String::from_utf8_lossy(&content))
-            .map_err(|e| Error::config_invalid(format!("failed to parse config file: {e}")))?;
-
-        let profile_section = if profile == "default" {
-            profile.to_string()
-        } else {
-            format!("profile {profile}")
-        };
-
-        let section = conf.section(Some(profile_section)).ok_or_else(|| {
+        let profiles = self.files.load(ctx).await?;
+        let section = profiles.profile(profile).ok_or_else(|| {
             Error::config_invalid(format!("profile '{profile}' not found in config"))
         })?;
 
@@ -15,16 +15,45 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
 use crate::Credential;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::constants::*;
+use crate::endpoint::EndpointConfig;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::endpoint::STS;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::provide_credential::{
+    AssumeRoleCredentialProvider, AssumeRoleWithWebIdentityCredentialProvider,
+    ECSCredentialProvider, EnvCredentialProvider, IMDSv2CredentialProvider,
+    ProcessCredentialProvider, SSOCredentialProvider, StaticCredentialProvider,
+};
 #[cfg(not(target_arch = "wasm32"))]
-use ini::Ini;
+use crate::shared_config::{ProfileFiles, ProfileSet, Section};
 #[cfg(not(target_arch = "wasm32"))]
 use log::debug;
+use reqsign_core::{Context, ProvideCredential, Result, SignRequest, SignRequestDyn};
 #[cfg(not(target_arch = "wasm32"))]
-use reqsign_core::Error;
-use reqsign_core::{Context, ProvideCredential, Result};
+use reqsign_core::{Error, ProvideCredentialDyn, Signer};
+
+/// Builds the request signer for STS calls made while resolving role profiles.
+pub(crate) type StsRequestSignerFactory =
+    Arc<dyn Fn(&str) -> Arc<dyn SignRequestDyn<Credential = Credential>> + Send + Sync>;
+
+pub(crate) fn sts_request_signer_factory<F, S>(factory: F) -> StsRequestSignerFactory
+where
+    F: Fn(&str) -> S + Send + Sync + 'static,
+    S: SignRequest<Credential = Credential>,
+{
+    Arc::new(move |region| {
+        Arc::new(factory(region)) as Arc<dyn SignRequestDyn<Credential = Credential>>
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type BoxedProvider = Arc<dyn ProvideCredentialDyn<Credential = Credential>>;
 
 /// ProfileCredentialProvider loads AWS credentials from configuration files.
 ///
@@ -36,11 +65,42 @@ use reqsign_core::{Context, ProvideCredential, Result};
 /// 1. The profile specified via `with_profile()`
 /// 2. The `AWS_PROFILE` environment variable
 /// 3. Default to "default"
-#[derive(Debug, Clone)]
+///
+/// Profiles that set `role_arn` are resolved like the AWS SDKs do. The role
+/// chain follows `source_profile` until it reaches a profile with static
+/// keys, SSO settings, `credential_process`, or `web_identity_token_file`, or
+/// a `credential_source` of `Environment`, `Ec2InstanceMetadata`, or
+/// `EcsContainer`. A source profile with static keys is used as the base
+/// credential even if it also sets `role_arn`. Each role is then assumed from the base credential
+/// outward, honouring `external_id`, `role_session_name`, `duration_seconds`,
+/// and `mfa_serial`. Cycles in `source_profile` are rejected.
+///
+/// AssumeRole calls must be signed with SigV4, so role profiles require a
+/// signer configured via `with_sts_request_signer()`. The
+/// `DefaultCredentialProvider` of `reqsign-aws-v4` configures one already:
+///
+/// ```ignore
+/// let provider = ProfileCredentialProvider::new()
+///     .with_sts_request_signer(|region| RequestSigner::new("sts", region));
+/// ```
+#[derive(Clone)]
 pub struct ProfileCredentialProvider {
     profile: Option<String>,
     config_file: Option<String>,
     credentials_file: Option<String>,
+    mfa_code: Option<String>,
+    endpoint_config: EndpointConfig,
+    sts_request_signer: Option<StsRequestSignerFactory>,
+}
+
+impl Debug for ProfileCredentialProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProfileCredentialProvider")
+            .field("profile", &self.profile)
+            .field("config_file", &self.config_file)
+            .field("credentials_file", &self.credentials_file)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for ProfileCredentialProvider {
@@ -56,6 +116,9 @@ impl ProfileCredentialProvider {
             profile: None,
             config_file: None,
             credentials_file: None,
+            mfa_code: None,
+            endpoint_config: EndpointConfig::new(),
+            sts_request_signer: None,
         }
     }
 
@@ -77,130 +140,325 @@ impl ProfileCredentialProvider {
         self
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
-    async fn load_from_credentials_file(
-        &self,
-        ctx: &Context,
-        profile: &str,
-    ) -> Result<Option<Credential>> {
-        let path = if let Some(path) = &self.credentials_file {
-            path.clone()
-        } else if let Some(path) = ctx.env_var(AWS_SHARED_CREDENTIALS_FILE) {
-            path
-        } else {
-            "~/.aws/credentials".to_string()
-        };
+    /// Set the MFA token code sent for role profiles that declare `mfa_serial`.
+    pub fn with_mfa_code(mut self, token_code: impl Into<String>) -> Self {
+        self.mfa_code = Some(token_code.into());
+        self
+    }
 
-        let expanded_path = if path.starts_with("~/") {
-            match ctx.expand_home_dir(&path) {
-                Some(expanded) => expanded,
-                None => {
-                    debug!("failed to expand homedir for path: {path}");
-                    return Ok(None);
-                }
-            }
-        } else {
-            path.clone()
-        };
+    /// Set the FIPS, dual-stack, and endpoint URL settings for STS.
+    ///
+    /// Settings that are not configured explicitly are read from the
+    /// environment and the selected profile.
+    pub fn with_endpoint_config(mut self, config: EndpointConfig) -> Self {
+        self.endpoint_config = config;
+        self
+    }
 
-        let content = match ctx.file_read(&expanded_path).await {
-            Ok(content) => content,
-            Err(err) => {
-                debug!("failed to read credentials file {expanded_path}: {err:?}");
-                return Ok(None);
-            }
-        };
+    /// Set the SigV4 request signer used for STS AssumeRole calls.
+    ///
+    /// The factory receives the signing region of the selected STS endpoint.
+    pub fn with_sts_request_signer<F, S>(mut self, factory: F) -> Self
+    where
+        F: Fn(&str) -> S + Send + Sync + 'static,
+        S: SignRequest<Credential = Credential>,
+    {
+        self.sts_request_signer = Some(sts_request_signer_factory(factory));
+        self
+    }
 
-        let conf = Ini::load_from_str(&String::from_utf8_lossy(&content)).map_err(|e| {
-            Error::config_invalid("failed to parse credentials file").with_source(e)
-        })?;
+    /// Use `factory` unless an STS request signer is already configured.
+    pub(crate) fn or_sts_request_signer(mut self, factory: StsRequestSignerFactory) -> Self {
+        self.sts_request_signer.get_or_insert(factory);
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn load(&self, ctx: &Context) -> Result<Option<Credential>> {
+        let profile = self
+            .profile
+            .clone()
+            .or_else(|| ctx.env_var(AWS_PROFILE))
+            .unwrap_or_else(|| "default".to_string());
+
+        let mut files = ProfileFiles::new();
+        if let Some(path) = &self.config_file {
+            files = files.with_config_file(path);
+        }
+        if let Some(path) = &self.credentials_file {
+            files = files.with_credentials_file(path);
+        }
+        let profiles = files.load(ctx).await?;
+        let Some(props) = profiles.profile(&profile) else {
+            debug!("profile {profile} not found in shared config files");
+            return Ok(None);
+        };
 
-        let props = match conf.section(Some(profile)) {
-            Some(props) => props,
+        let chain = match resolve_chain(&profiles, &profile)? {
+            Some(chain) => chain,
             None => {
-                debug!("profile {profile} not found in credentials file");
+                debug!("profile {profile} has no static or role credentials");
                 return Ok(None);
             }
         };
 
-        let access_key_id = props.get("aws_access_key_id");
-        let secret_access_key = props.get("aws_secret_access_key");
-
-        match (access_key_id, secret_access_key) {
-            (Some(ak), Some(sk)) => Ok(Some(Credential {
-                access_key_id: ak.to_string(),
-                secret_access_key: sk.to_string(),
-                session_token: props.get("aws_session_token").map(|s| s.to_string()),
-                expires_in: None,
-            })),
-            _ => Ok(None),
-        }
+        let sts = self
+            .endpoint_config
+            .load_from_profiles(ctx, &profiles, &profile, &STS)?;
+        let provider = self.build_provider(ctx, props, chain, &sts)?;
+        provider.provide_credential_dyn(ctx).await
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    async fn load_from_config_file(
+    fn build_provider(
         &self,
         ctx: &Context,
-        profile: &str,
-    ) -> Result<Option<Credential>> {
-        let path = if let Some(path) = &self.config_file {
-            path.clone()
-        } else if let Some(path) = ctx.env_var(AWS_CONFIG_FILE) {
-            path
-        } else {
-            "~/.aws/config".to_string()
-        };
+        start: &Section,
+        chain: ProfileChain,
+        sts: &EndpointConfig,
+    ) -> Result<BoxedProvider> {
+        let region = ctx
+            .env_var("AWS_REGION")
+            .or_else(|| start.get("region").map(str::to_string));
+        let use_regional = ctx
+            .env_var("AWS_STS_REGIONAL_ENDPOINTS")
+            .or_else(|| start.get("sts_regional_endpoints").map(str::to_string))
+            .is_some_and(|v| v == "regional");
 
-        let expanded_path = if path.starts_with("~/") {
-            match ctx.expand_home_dir(&path) {
-                Some(expanded) => expanded,
-                None => {
-                    debug!("failed to expand homedir for path: {path}");
-                    return Ok(None);
+        let mut provider: BoxedProvider = match chain.base {
+            BaseCredential::Static(props) => {
+                let mut provider = StaticCredentialProvider::new(
+                    props.get("aws_access_key_id").unwrap_or_default(),
+                    props.get("aws_secret_access_key").unwrap_or_default(),
+                );
+                if let Some(token) = props.get("aws_session_token") {
+                    provider = provider.with_session_token(token);
+                }
+                Arc::new(provider)
+            }
+            BaseCredential::WebIdentity(props) => {
+                let mut provider = AssumeRoleWithWebIdentityCredentialProvider::with_config(
+                    props.get("role_arn").unwrap_or_default().to_string(),
+                    props
+                        .get("web_identity_token_file")
+                        .unwrap_or_default()
+                        .into(),
+                );
+                if let Some(name) = props.get("role_session_name") {
+                    provider = provider.with_role_session_name(name.to_string());
+                }
+                if let Some(region) = &region {
+                    provider = provider.with_region(region.clone());
+                }
+                if use_regional {
+                    provider = provider.with_regional_sts_endpoint();
+                }
+                Arc::new(provider.with_endpoint_config(sts.clone()))
+            }
+            BaseCredential::Sso(name) => {
+                let mut provider = SSOCredentialProvider::new().with_profile(name);
+                if let Some(path) = &self.config_file {
+                    provider = provider.with_config_file(path);
+                }
+                if let Some(path) = &self.credentials_file {
+                    provider = provider.with_credentials_file(path);
                 }
+                Arc::new(provider)
             }
-        } else {
-            path.clone()
+            BaseCredential::Process(name) => {
+                let mut provider = ProcessCredentialProvider::new().with_profile(name);
+                if let Some(path) = &self.config_file {
+                    provider = provider.with_config_file(path);
+                }
+                if let Some(path) = &self.credentials_file {
+                    provider = provider.with_credentials_file(path);
+                }
+                Arc::new(provider)
+            }
+            BaseCredential::Environment => Arc::new(EnvCredentialProvider::new()),
+            BaseCredential::Ec2InstanceMetadata => Arc::new(IMDSv2CredentialProvider::new()),
+            BaseCredential::EcsContainer => Arc::new(ECSCredentialProvider::new()),
         };
 
-        let content = match ctx.file_read(&expanded_path).await {
-            Ok(content) => content,
-            Err(err) => {
-                debug!("failed to read config file {expanded_path}: {err:?}");
-                return Ok(None);
+        if chain.roles.is_empty() {
+            return Ok(provider);
+        }
+
+        let factory = self.sts_request_signer.as_ref().ok_or_else(|| {
+            Error::config_invalid(
+                "AWS profile assumes a role but no STS request signer is configured",
+            )
+        })?;
+        let endpoint = sts.resolve_sts(region.as_deref(), use_regional)?;
+        let signing_region = endpoint.signing_region();
+
+        for role in chain.roles.into_iter().rev() {
+            let signer = Signer::new(ctx.clone(), provider, factory(signing_region));
+            let mut assume_role = AssumeRoleCredentialProvider::new(
+                role.get("role_arn").unwrap_or_default().to_string(),
+                signer,
+            );
+            if let Some(name) = role.get("role_session_name") {
+                assume_role = assume_role.with_role_session_name(name.to_string());
             }
-        };
+            if let Some(id) = role.get("external_id") {
+                assume_role = assume_role.with_external_id(id.to_string());
+            }
+            if let Some(seconds) = role.get("duration_seconds") {
+                let seconds = seconds.parse().map_err(|e| {
+                    Error::config_invalid("AWS profile duration_seconds is invalid").with_source(e)
+                })?;
+                assume_role = assume_role.with_duration_seconds(seconds);
+            }
+            if let Some(serial) = role.get("mfa_serial") {
+                assume_role = assume_role.with_mfa_serial(serial.to_string());
+                if let Some(code) = &self.mfa_code {
+                    assume_role = assume_role.with_mfa_code(code.clone());
+                }
+            }
+            if let Some(region) = &region {
+                assume_role = assume_role.with_region(region.clone());
+            }
+            if use_regional {
+                assume_role = assume_role.with_regional_sts_endpoint();
+            }
+            provider = Arc::new(assume_role.with_endpoint_config(sts.clone()));
+        }
 
-        let conf = Ini::load_from_str(&String::from_utf8_lossy(&content))
-            .map_err(|e| Error::config_invalid("failed to parse config file").with_source(e))?;
+        Ok(provider)
+    }
+}
 
-        let section = match profile {
-            "default" => "default".to_string(),
-            x => format!("profile {x}"),
-        };
+/// Credential source at the end of a role chain.
+#[cfg(not(target_arch = "wasm32"))]
+enum BaseCredential<'a> {
+    Static(&'a Section),
+    WebIdentity(&'a Section),
+    Sso(&'a str),
+    Process(&'a str),
+    Environment,
+    Ec2InstanceMetadata,
+    EcsContainer,
+}
 
-        let props = match conf.section(Some(&section)) {
-            Some(props) => props,
-            None => {
-                debug!("section {profile} not found in config file");
+/// Resolved profile chain: the base credential and the roles assumed from
+/// it, ordered from the selected profile towards the base.
+#[cfg(not(target_arch = "wasm32"))]
+struct ProfileChain<'a> {
+    base: BaseCredential<'a>,
+    roles: Vec<&'a Section>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn has_static_keys(props: &Section) -> bool {
+    props.contains_key("aws_access_key_id") && props.contains_key("aws_secret_access_key")
+}
+
+/// Follow `source_profile` links from the selected profile.
+///
+/// Returns `None` when the selected profile is not a role profile and holds no
+/// static keys, leaving SSO and process profiles to their own providers.
+#[cfg(not(target_arch = "wasm32"))]
+fn resolve_chain<'a>(profiles: &'a ProfileSet, start: &'a str) -> Result<Option<ProfileChain<'a>>> {
+    let mut visited: Vec<&str> = Vec::new();
+    let mut roles = Vec::new();
+    let mut name = start;
+
+    loop {
+        let props = profiles.profile(name).ok_or_else(|| {
+            Error::config_invalid(format!(
+                "AWS source profile {name} referenced by {} was not found",
+                visited.last().copied().unwrap_or(start)
+            ))
+        })?;
+        if visited.contains(&name) {
+            visited.push(name);
+            return Err(Error::config_invalid(format!(
+                "AWS profile source chain contains a cycle: {}",
+                visited.join(" -> ")
+            )));
+        }
+        visited.push(name);
+
+        // A source profile with static keys ends the chain even if it also
+        // sets `role_arn`, as in the AWS SDKs.
+        if !roles.is_empty() && has_static_keys(props) {
+            return Ok(Some(ProfileChain {
+                base: BaseCredential::Static(props),
+                roles,
+            }));
+        }
+
+        if !props.contains_key("role_arn") {
+            if has_static_keys(props) {
+                return Ok(Some(ProfileChain {
+                    base: BaseCredential::Static(props),
+                    roles,
+                }));
+            }
+            if roles.is_empty() {
                 return Ok(None);
             }
-        };
+            let base = if props.contains_key("sso_start_url") || props.contains_key("sso_session") {
+                BaseCredential::Sso(name)
+            } else if props.contains_key("credential_process") {
+                BaseCredential::Process(name)
+            } else {
+                return Err(Error::config_invalid(format!(
+                    "AWS source profile {name} does not contain credentials"
+                )));
+            };
+            return Ok(Some(ProfileChain { base, roles }));
+        }
 
-        let access_key_id = props.get("aws_access_key_id");
-        let secret_access_key = props.get("aws_secret_access_key");
-
-        match (access_key_id, secret_access_key) {
-            (Some(ak), Some(sk)) => Ok(Some(Credential {
-                access_key_id: ak.to_string(),
-                secret_access_key: sk.to_string(),
-                session_token: props.get("aws_session_token").map(|s| s.to_string()),
-                expires_in: None,
-            })),
-            _ => Ok(None),
+        if props.contains_key("web_identity_token_file") {
+            return Ok(Some(ProfileChain {
+                base: BaseCredential::WebIdentity(props),
+                roles,
+            }));
+        }
+        roles.push(props);
+
+        match (props.get("source_profile"), props.get("credential_source")) {
+            (Some(_), Some(_)) => {
+                return Err(Error::config_invalid(format!(
+                    "AWS profile {name} sets both source_profile and credential_source"
+                )));
+            }
+            (None, None) => {
+                return Err(Error::config_invalid(format!(
+                    "AWS profile {name} sets role_arn without source_profile or credential_source"
+                )));
+            }
+            (Some(source), None) if source == name => {
+                if !has_static_keys(props) {
+                    return Err(Error::config_invalid(format!(
+                        "AWS profile {name} sources itself but does not contain static keys"
+                    )));
+                }
+                return Ok(Some(ProfileChain {
+                    base: BaseCredential::Static(props),
+                    roles,
+                }));
+            }
+            (Some(source), None) => name = source,
+            (None, Some(source)) => {
+                let base = match source {
+                    "Environment" => BaseCredential::Environment,
+                    "Ec2InstanceMetadata" => BaseCredential::Ec2InstanceMetadata,
+                    "EcsContainer" => BaseCredential::EcsContainer,
+                    _ => {
+                        return Err(Error::config_invalid(format!(
+                            "AWS profile {name} has unsupported credential_source {source}"
+                        )));
+                    }
+                };
+                return Ok(Some(ProfileChain { base, roles }));
+            }
         }
     }
 }
+
 impl ProvideCredential for ProfileCredentialProvider {
     type Credential = Credential;
 
@@ -213,34 +471,26 @@ impl ProvideCredential for ProfileCredentialProvider {
 
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let profile = self
-                .profile
-                .clone()
-                .or_else(|| ctx.env_var(AWS_PROFILE))
-                .unwrap_or_else(|| "default".to_string());
-
-            // Try credentials file first
-            if let Some(cred) = self.load_from_credentials_file(ctx, &profile).await? {
-                return Ok(Some(cred));
-            }
-
-            // Then try config file
-            self.load_from_config_file(ctx, &profile).await
+            self.load(ctx).await
         }
     }
 }
-
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
     use super::*;
+    use bytes::Bytes;
+    use http::request::Parts;
     use pretty_assertions::assert_eq;
     use reqsign_core::StaticEnv;
+    use reqsign_core::{ErrorKind, HttpSend};
     use reqsign_file_read_tokio::TokioFileRead;
     use reqsign_http_send_reqwest::ReqwestHttpSend;
     use std::collections::HashMap;
     use std::fs::File;
     use std::io::Write;
+    use std::sync::Mutex;
+    use std::time::Duration;
     use tempfile::tempdir;
 
     #[tokio::test]
@@ -422,4 +672,364 @@ mod tests {
 
         Ok(())
     }
+
+    const TEST_ACCESS_KEY_HEADER: &str = "x-reqsign-test-access-key";
+
+    /// Stand-in STS signer recording the source access key and signing region.
+    #[derive(Debug)]
+    struct TestStsSigner {
+        region: String,
+    }
+
+    impl SignRequest for TestStsSigner {
+        type Credential = Credential;
+
+        async fn sign_request(
+            &self,
+            _: &Context,
+            req: &mut Parts,
+            credential: Option<&Self::Credential>,
+            _: Option<Duration>,
+        ) -> Result<()> {
+            let credential = credential.expect("STS request must have a source credential");
+            req.headers.insert(
+                TEST_ACCESS_KEY_HEADER,
+                format!("{}@{}", credential.access_key_id, self.region)
+                    .parse()
+                    .unwrap(),
+            );
+            Ok(())
+        }
+    }
+
+    /// Query `RoleArn` and test signer header of each STS request.
+    type RecordedRequest = (String, Option<String>);
+
+    #[derive(Clone, Debug, Default)]
+    struct MockSts {
+        requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    }
+
+    impl MockSts {
+        fn requests(&self) -> Vec<RecordedRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl HttpSend for MockSts {
+        async fn http_send(&self, req: http::Request<Bytes>) -> Result<http::Response<Bytes>> {
+            let query = req.uri().query().unwrap_or_default();
+            let params: HashMap<String, String> = form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect();
+            let role_arn = params.get("RoleArn").cloned().unwrap_or_default();
+            let signed_by = req
+                .headers()
+                .get(TEST_ACCESS_KEY_HEADER)
+                .map(|v| v.to_str().unwrap().to_string());
+            let mut requests = self.requests.lock().unwrap();
+            requests.push((role_arn.clone(), signed_by));
+            let n = requests.len();
+
+            let role = role_arn.rsplit('/').next().unwrap_or_default();
+            let body =
+                if params.get("Action").map(String::as_str) == Some("AssumeRoleWithWebIdentity") {
+                    format!(
+                        "<AssumeRoleWithWebIdentityResponse><AssumeRoleWithWebIdentityResult>\
+                     <Credentials><AccessKeyId>ASIAWEBIDENTITY{n}</AccessKeyId>\
+                     <SecretAccessKey>secret</SecretAccessKey><SessionToken>token</SessionToken>\
+                     <Expiration>2124-05-25T11:45:17Z</Expiration></Credentials>\
+                     </AssumeRoleWithWebIdentityResult></AssumeRoleWithWebIdentityResponse>"
+                    )
+                } else {
+                    format!(
+                        "<AssumeRoleResponse><AssumeRoleResult><Credentials>\
+                     <AccessKeyId>ASIA{role:0>12}</AccessKeyId>\
+                     <SecretAccessKey>secret</SecretAccessKey><SessionToken>token</SessionToken>\
+                     <Expiration>2124-05-25T11:45:17Z</Expiration>\
+                     </Credentials></AssumeRoleResult></AssumeRoleResponse>"
+                    )
+                };
+            Ok(http::Response::builder()
+                .status(http::StatusCode::OK)
+                .body(Bytes::from(body))
+                .unwrap())
+        }
+    }
+
+    fn role_context(sts: &MockSts, envs: HashMap<String, String>) -> Context {
+        Context::new()
+            .with_file_read(TokioFileRead)
+            .with_http_send(sts.clone())
+            .with_env(StaticEnv {
+                home_dir: None,
+                envs,
+            })
+    }
+
+    fn role_provider(config: &std::path::Path, profile: &str) -> ProfileCredentialProvider {
+        ProfileCredentialProvider::new()
+            .with_profile(profile)
+            .with_config_file(config.to_str().unwrap())
+            .with_credentials_file("/non/existent/path")
+            .with_sts_request_signer(|region| TestStsSigner {
+                region: region.to_string(),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_profile_role_chain_from_source_profiles() -> anyhow::Result<()> {
+        let tmp_dir = tempdir()?;
+        let config = tmp_dir.path().join("config");
+        std::fs::write(
+            &config,
+            "[profile base]\n\
+             aws_access_key_id = AKIABASEEXAMPLE1\n\
+             aws_secret_access_key = base-secret\n\
+             [profile first]\n\
+             role_arn = arn:aws:iam::123456789012:role/FIRST\n\
+             source_profile = base\n\
+             [profile second]\n\
+             role_arn = arn:aws:iam::123456789012:role/SECOND\n\
+             source_profile = first\n\
+             external_id = external\n\
+             role_session_name = chained\n\
+             duration_seconds = 900\n",
+        )?;
+        let sts = MockSts::default();
+        let ctx = role_context(&sts, HashMap::new());
+
+        let cred = role_provider(&config, "second")
+            .provide_credential(&ctx)
+            .await?
+            .expect("role chain must provide credentials");
+        assert_eq!(cred.access_key_id, "ASIA000000SECOND");
+        assert_eq!(cred.session_token.as_deref(), Some("token"));
+        assert_eq!(
+            sts.requests(),
+            vec![
+                (
+                    "arn:aws:iam::123456789012:role/FIRST".to_string(),
+                    Some("AKIABASEEXAMPLE1@us-east-1".to_string())
+                ),
+                (
+                    "arn:aws:iam::123456789012:role/SECOND".to_string(),
+                    Some("ASIA0000000FIRST@us-east-1".to_string())
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_role_chain_stops_at_source_profile_static_keys() -> anyhow::Result<()> {
+        let tmp_dir = tempdir()?;
+        let config = tmp_dir.path().join("config");
+        std::fs::write(
+            &config,
+            "[profile base]\n\
+             role_arn = arn:aws:iam::123456789012:role/BASE\n\
+             source_profile = missing\n\
+             [profile middle]\n\
+             aws_access_key_id = AKIAMIDDLEEXAMPLE\n\
+             aws_secret_access_key = middle-secret\n\
+             role_arn = arn:aws:iam::123456789012:role/MIDDLE\n\
+             source_profile = base\n\
+             [profile target]\n\
+             role_arn = arn:aws:iam::123456789012:role/TARGET\n\
+             source_profile = middle\n",
+        )?;
+        let sts = MockSts::default();
+        let ctx = role_context(&sts, HashMap::new());
+
+        let cred = role_provider(&config, "target")
+            .provide_credential(&ctx)
+            .await?
+            .expect("role chain must provide credentials");
+        assert_eq!(cred.access_key_id, "ASIA000000TARGET");
+        assert_eq!(
+            sts.requests(),
+            vec![(
+                "arn:aws:iam::123456789012:role/TARGET".to_string(),
+                Some("AKIAMIDDLEEXAMPLE@us-east-1".to_string())
+            )]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_role_self_source_and_regional_endpoint() -> anyhow::Result<()> {
+        let tmp_dir = tempdir()?;
+        let config = tmp_dir.path().join("config");
+        std::fs::write(
+            &config,
+            "[profile self]\n\
+             aws_access_key_id = AKIASELFEXAMPLE1\n\
+             aws_secret_access_key = self-secret\n\
+             role_arn = arn:aws:iam::123456789012:role/SELF\n\
+             source_profile = self\n\
+             region = eu-west-1\n\
+             sts_regional_endpoints = regional\n",
+        )?;
+        let sts = MockSts::default();
+        let ctx = role_context(&sts, HashMap::new());
+
+        let cred = role_provider(&config, "self")
+            .provide_credential(&ctx)
+            .await?
+            .expect("self-sourced role must provide credentials");
+        assert_eq!(cred.access_key_id, "ASIA00000000SELF");
+        assert_eq!(
+            sts.requests(),
+            vec![(
+                "arn:aws:iam::123456789012:role/SELF".to_string(),
+                Some("AKIASELFEXAMPLE1@eu-west-1".to_string())
+            )]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_role_credential_source_environment() -> anyhow::Result<()> {
+        let tmp_dir = tempdir()?;
+        let config = tmp_dir.path().join("config");
+        std::fs::write(
+            &config,
+            "[default]\n\
+             role_arn = arn:aws:iam::123456789012:role/ENV\n\
+             credential_source = Environment\n",
+        )?;
+        let sts = MockSts::default();
+        let ctx = role_context(
+            &sts,
+            HashMap::from([
+                (AWS_ACCESS_KEY_ID.to_string(), "AKIAENVIRONMENT".to_string()),
+                (AWS_SECRET_ACCESS_KEY.to_string(), "env-secret".to_string()),
+            ]),
+        );
+
+        let cred = role_provider(&config, "default")
+            .provide_credential(&ctx)
+            .await?
+            .expect("credential_source role must provide credentials");
+        assert_eq!(cred.access_key_id, "ASIA000000000ENV");
+        assert_eq!(
+            sts.requests()[0].1.as_deref(),
+            Some("AKIAENVIRONMENT@us-east-1")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_role_chain_from_web_identity() -> anyhow::Result<()> {
+        let tmp_dir = tempdir()?;
+        let token = tmp_dir.path().join("token");
+        std::fs::write(&token, "web-identity-token")?;
+        let config = tmp_dir.path().join("config");
+        std::fs::write(
+            &config,
+            format!(
+                "[profile web]\n\
+                 role_arn = arn:aws:iam::123456789012:role/WEB\n\
+                 web_identity_token_file = {}\n\
+                 [profile target]\n\
+                 role_arn = arn:aws:iam::123456789012:role/TARGET\n\
+                 source_profile = web\n",
+                token.display()
+            ),
+        )?;
+        let sts = MockSts::default();
+        let ctx = role_context(&sts, HashMap::new());
+
+        let cred = role_provider(&config, "target")
+            .provide_credential(&ctx)
+            .await?
+            .expect("web identity chain must provide credentials");
+        assert_eq!(cred.access_key_id, "ASIA000000TARGET");
+        assert_eq!(
+            sts.requests(),
+            vec![
+                ("arn:aws:iam::123456789012:role/WEB".to_string(), None),
+                (
+                    "arn:aws:iam::123456789012:role/TARGET".to_string(),
+                    Some("ASIAWEBIDENTITY1@us-east-1".to_string())
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_role_chain_rejects_invalid_configs() -> anyhow::Result<()> {
+        let tmp_dir = tempdir()?;
+        let config = tmp_dir.path().join("config");
+        std::fs::write(
+            &config,
+            "[profile a]\n\
+             role_arn = arn:aws:iam::123456789012:role/A\n\
+             source_profile = b\n\
+             [profile b]\n\
+             role_arn = arn:aws:iam::123456789012:role/B\n\
+             source_profile = a\n\
+             [profile both]\n\
+             role_arn = arn:aws:iam::123456789012:role/BOTH\n\
+             source_profile = a\n\
+             credential_source = Environment\n\
+             [profile neither]\n\
+             role_arn = arn:aws:iam::123456789012:role/NEITHER\n\
+             [profile missing]\n\
+             role_arn = arn:aws:iam::123456789012:role/MISSING\n\
+             source_profile = nowhere\n\
+             [profile unknown]\n\
+             role_arn = arn:aws:iam::123456789012:role/UNKNOWN\n\
+             credential_source = Somewhere\n",
+        )?;
+        let sts = MockSts::default();
+        let ctx = role_context(&sts, HashMap::new());
+
+        for (profile, message) in [
+            ("a", "cycle: a -> b -> a"),
+            ("both", "both source_profile and credential_source"),
+            ("neither", "without source_profile or credential_source"),
+            ("missing", "source profile nowhere referenced by missing"),
+            ("unknown", "unsupported credential_source Somewhere"),
+        ] {
+            let err = role_provider(&config, profile)
+                .provide_credential(&ctx)
+                .await
+                .expect_err("invalid role profile must fail");
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+            assert!(
+                err.to_string().contains(message),
+                "{profile}: unexpected error {err}"
+            );
+        }
+        assert!(sts.requests().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_role_requires_sts_request_signer() -> anyhow::Result<()> {
+        let tmp_dir = tempdir()?;
+        let config = tmp_dir.path().join("config");
+        std::fs::write(
+            &config,
+            "[default]\n\
+             aws_access_key_id = AKIASTATICEXAMPLE\n\
+             aws_secret_access_key = static-secret\n\
+             role_arn = arn:aws:iam::123456789012:role/ROLE\n\
+             source_profile = default\n",
+        )?;
+        let sts = MockSts::default();
+        let ctx = role_context(&sts, HashMap::new());
+
+        let err = ProfileCredentialProvider::new()
+            .with_config_file(config.to_str().unwrap())
+            .with_credentials_file("/non/existent/path")
+            .provide_credential(&ctx)
+            .await
+            .expect_err("role profile without STS signer must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        Ok(())
+    }
 }
@@ -16,20 +16,26 @@
 // under the License.
 
 use crate::Credential;
+use crate::endpoint::{AwsService, Endpoint, EndpointConfig, SSO, SSO_OIDC};
+use crate::shared_config::ProfileFiles;
 use http::{Method, Request, StatusCode};
-use ini::Ini;
 use log::{debug, warn};
 use reqsign_core::time::Timestamp;
+use reqsign_core::utils::Redact;
 use reqsign_core::{Context, Error, ProvideCredential, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 
 const AWS_SSO_ACCOUNT_ID: &str = "sso_account_id";
 const AWS_SSO_REGION: &str = "sso_region";
 const AWS_SSO_ROLE_NAME: &str = "sso_role_name";
 const AWS_SSO_START_URL: &str = "sso_start_url";
-#[allow(dead_code)]
 const AWS_SSO_SESSION_NAME: &str = "sso_session";
 
+/// Refresh cached tokens that expire within this window, like the AWS CLI.
+const SSO_TOKEN_REFRESH_WINDOW: Duration = Duration::from_secs(15 * 60);
+
 /// SSO Credentials Provider
 ///
 /// This provider fetches credentials from AWS SSO (IAM Identity Center).
@@ -44,6 +50,24 @@ const AWS_SSO_SESSION_NAME: &str = "sso_session";
 /// sso_account_id = 123456789012
 /// sso_role_name = MyRole
 /// ```
+///
+/// Profiles may also reference an `[sso-session]` section, in which case the
+/// cached token is looked up by session name:
+/// ```ini
+/// [profile my-sso-profile]
+/// sso_session = my-session
+/// sso_account_id = 123456789012
+/// sso_role_name = MyRole
+///
+/// [sso-session my-session]
+/// sso_start_url = https://my-sso-portal.awsapps.com/start
+/// sso_region = us-east-1
+/// ```
+///
+/// For `sso-session` profiles, a cached token that is expired or about to
+/// expire is refreshed through the SSO OIDC `CreateToken` API with the cached
+/// `refreshToken`, `clientId` and `clientSecret`. The refreshed token is
+/// written back to the cache file via `Context::file_write`.
 #[derive(Debug, Clone)]
 pub struct SSOCredentialProvider {
     profile: Option<String>,
@@ -52,6 +76,9 @@ pub struct SSOCredentialProvider {
     sso_role_name: Option<String>,
     sso_start_url: Option<String>,
     sso_endpoint: Option<String>, // Allow custom endpoint for testing
+    oidc_endpoint: Option<String>,
+    endpoint_config: EndpointConfig,
+    files: ProfileFiles,
 }
 
 impl Default for SSOCredentialProvider {
@@ -70,6 +97,9 @@ impl SSOCredentialProvider {
             sso_role_name: None,
             sso_start_url: None,
             sso_endpoint: None,
+            oidc_endpoint: None,
+            endpoint_config: EndpointConfig::new(),
+            files: ProfileFiles::new(),
         }
     }
 
@@ -79,6 +109,18 @@ impl SSOCredentialProvider {
         self
     }
 
+    /// Set the path to the config file
+    pub fn with_config_file(mut self, path: impl Into<String>) -> Self {
+        self.files = self.files.with_config_file(path);
+        self
+    }
+
+    /// Set the path to the credentials file
+    pub fn with_credentials_file(mut self, path: impl Into<String>) -> Self {
+        self.files = self.files.with_credentials_file(path);
+        self
+    }
+
     /// Set SSO account ID
     pub fn with_account_id(mut self, account_id: impl Into<String>) -> Self {
         self.sso_account_id = Some(account_id.into());
@@ -109,6 +151,35 @@ impl SSOCredentialProvider {
         self
     }
 
+    /// Set custom SSO OIDC token endpoint (for testing)
+    pub fn with_oidc_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.oidc_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Set the FIPS and dual-stack settings for the SSO portal and OIDC endpoints.
+    ///
+    /// Endpoint URLs are configured per service with `AWS_ENDPOINT_URL_SSO` and
+    /// `AWS_ENDPOINT_URL_SSO_OIDC`, or the `sso` and `sso_oidc` entries of the
+    /// profile's `services` section.
+    pub fn with_endpoint_config(mut self, config: EndpointConfig) -> Self {
+        self.endpoint_config = config;
+        self
+    }
+
+    async fn resolve_endpoint(
+        &self,
+        ctx: &Context,
+        service: &AwsService,
+        region: &str,
+    ) -> Result<Endpoint> {
+        self.endpoint_config
+            .or_profile_files(self.profile.as_ref(), &self.files)
+            .load(ctx, service)
+            .await?
+            .resolve(service, region)
+    }
+
     async fn load_sso_config(&self, ctx: &Context) -> Result<SSOConfig> {
         // If all fields are provided directly, use them
         if let (Some(account_id), Some(region), Some(role_name), Some(start_url)) = (
@@ -122,10 +193,11 @@ impl SSOCredentialProvider {
                 sso_region: region.clone(),
                 sso_role_name: role_name.clone(),
                 sso_start_url: start_url.clone(),
+                sso_session: None,
             });
         }
 
-        // Otherwise, load from config file
+        // Otherwise, load from the shared config files
         // Priority: 1. self.profile, 2. AWS_PROFILE env var, 3. "default"
         let profile_name = self
             .profile
@@ -136,66 +208,66 @@ impl SSOCredentialProvider {
     }
 
     async fn load_from_config_file(&self, ctx: &Context, profile: &str) -> Result<SSOConfig> {
-        // Load AWS config file
-        let config_path = ctx
-            .env_var("AWS_CONFIG_FILE")
-            .unwrap_or_else(|| "~/.aws/config".to_string());
-
-        let expanded_path = if config_path.starts_with("~/") {
-            match ctx.expand_home_dir(&config_path) {
-                Some(expanded) => expanded,
-                None => return Err(Error::config_invalid("failed to expand home directory")),
-            }
-        } else {
-            config_path
-        };
-
-        let content = ctx.file_read(&expanded_path).await.map_err(|_| {
-            Error::config_invalid(format!("failed to read config file: {expanded_path}"))
-        })?;
-
-        let conf = Ini::load_from_str(&String::from_utf8_lossy(&content))
-            .map_err(|e| Error::config_invalid(format!("failed to parse config file: {e}")))?;
-
-        let profile_section = if profile == "default" {
-            profile.to_string()
-        } else {
-            format!("profile {profile}")
-        };
-
-        let section = conf.section(Some(profile_section)).ok_or_else(|| {
+        let profiles = self.files.load(ctx).await?;
+        let section = profiles.profile(profile).ok_or_else(|| {
             Error::config_invalid(format!("profile '{profile}' not found in config"))
         })?;
 
-        // Check if this profile has SSO configuration
         let sso_account_id = section.get(AWS_SSO_ACCOUNT_ID).ok_or_else(|| {
             Error::config_invalid(format!("missing {AWS_SSO_ACCOUNT_ID} in profile"))
         })?;
 
-        let sso_region = section
-            .get(AWS_SSO_REGION)
-            .ok_or_else(|| Error::config_invalid(format!("missing {AWS_SSO_REGION} in profile")))?;
-
         let sso_role_name = section.get(AWS_SSO_ROLE_NAME).ok_or_else(|| {
             Error::config_invalid(format!("missing {AWS_SSO_ROLE_NAME} in profile"))
         })?;
 
-        let sso_start_url = section.get(AWS_SSO_START_URL).ok_or_else(|| {
-            Error::config_invalid(format!("missing {AWS_SSO_START_URL} in profile"))
-        })?;
+        // Profiles using `sso_session` take the start URL and region from the
+        // referenced `[sso-session]` section. Values repeated on the profile
+        // must match the session.
+        let (sso_start_url, sso_region, sso_session) = match section.get(AWS_SSO_SESSION_NAME) {
+            Some(session_name) => {
+                let session = profiles.sso_session(session_name).ok_or_else(|| {
+                    Error::config_invalid(format!("sso-session '{session_name}' not found"))
+                })?;
+                let session_value = |key: &str| -> Result<String> {
+                    let value = session.get(key).ok_or_else(|| {
+                        Error::config_invalid(format!("missing {key} in sso-session"))
+                    })?;
+                    if section.get(key).is_some_and(|v| v != value) {
+                        return Err(Error::config_invalid(format!(
+                            "{key} in profile '{profile}' does not match sso-session '{session_name}'"
+                        )));
+                    }
+                    Ok(value.to_string())
+                };
+                let sso_start_url = session_value(AWS_SSO_START_URL)?;
+                let sso_region = session_value(AWS_SSO_REGION)?;
+                (sso_start_url, sso_region, Some(session_name.to_string()))
+            }
+            None => {
+                let sso_start_url = section.get(AWS_SSO_START_URL).ok_or_else(|| {
+                    Error::config_invalid(format!("missing {AWS_SSO_START_URL} in profile"))
+                })?;
+                let sso_region = section.get(AWS_SSO_REGION).ok_or_else(|| {
+                    Error::config_invalid(format!("missing {AWS_SSO_REGION} in profile"))
+                })?;
+                (sso_start_url.to_string(), sso_region.to_string(), None)
+            }
+        };
 
         Ok(SSOConfig {
             sso_account_id: sso_account_id.to_string(),
-            sso_region: sso_region.to_string(),
+            sso_region,
             sso_role_name: sso_role_name.to_string(),
-            sso_start_url: sso_start_url.to_string(),
+            sso_start_url,
+            sso_session,
         })
     }
 
     async fn find_cached_token(
         &self,
         ctx: &Context,
-        start_url: &str,
+        config: &SSOConfig,
     ) -> Result<Option<CachedToken>> {
         // Get home directory and build cache path
         let home_dir = ctx
@@ -204,34 +276,149 @@ impl SSOCredentialProvider {
 
         let cache_dir = home_dir.join(".aws").join("sso").join("cache");
 
-        // Generate cache file name (SHA1 hash of start URL)
-        let cache_key = hex_sha1(start_url.as_bytes());
+        // Generate cache file name (SHA1 hash of the session name, or of the
+        // start URL for legacy profiles)
+        let cache_key = hex_sha1(
+            config
+                .sso_session
+                .as_deref()
+                .unwrap_or(&config.sso_start_url)
+                .as_bytes(),
+        );
         let cache_file = cache_dir.join(format!("{cache_key}.json"));
 
         debug!("looking for SSO token cache at: {cache_file:?}");
 
-        match ctx.file_read(&cache_file.to_string_lossy()).await {
-            Ok(content) => {
-                let token: CachedToken = serde_json::from_slice(&content).map_err(|e| {
-                    Error::unexpected(format!("failed to parse SSO token cache: {e}"))
-                })?;
+        let cache_path = cache_file.to_string_lossy();
+        let content = match ctx.file_read(&cache_path).await {
+            Ok(content) => content,
+            Err(_) => {
+                debug!("SSO token cache not found");
+                return Ok(None);
+            }
+        };
+        let token: CachedToken = serde_json::from_slice(&content)
+            .map_err(|e| Error::unexpected(format!("failed to parse SSO token cache: {e}")))?;
 
-                // Check if token is expired
-                let expires_at = token.expires_at.parse::<Timestamp>()?;
-                if expires_at <= Timestamp::now() {
-                    warn!("SSO token is expired");
-                    return Ok(None);
-                }
+        let expires_at = token.expires_at.parse::<Timestamp>()?;
+        let now = Timestamp::now();
+        if expires_at > now + SSO_TOKEN_REFRESH_WINDOW {
+            return Ok(Some(token));
+        }
 
-                Ok(Some(token))
+        match self.refresh_token(ctx, config, &token, now).await {
+            Ok(Some(refreshed)) => {
+                match serde_json::to_vec(&refreshed) {
+                    Ok(content) => {
+                        if let Err(err) = ctx.file_write(&cache_path, &content).await {
+                            warn!("failed to write refreshed SSO token cache: {err}");
+                        }
+                    }
+                    Err(err) => warn!("failed to serialize refreshed SSO token: {err}"),
+                }
+                Ok(Some(refreshed))
             }
-            Err(_) => {
-                debug!("SSO token cache not found");
+            Ok(None) if expires_at > now => Ok(Some(token)),
+            Ok(None) => {
+                warn!("SSO token is expired");
                 Ok(None)
             }
+            Err(err) if expires_at > now => {
+                warn!("failed to refresh SSO token, using cached token: {err}");
+                Ok(Some(token))
+            }
+            Err(err) => Err(err),
         }
     }
 
+    /// Exchange the cached refresh token for a new access token.
+    ///
+    /// Returns `None` when the token cannot be refreshed: legacy profiles
+    /// without `sso_session`, caches without client registration, or expired
+    /// registrations.
+    async fn refresh_token(
+        &self,
+        ctx: &Context,
+        config: &SSOConfig,
+        token: &CachedToken,
+        now: Timestamp,
+    ) -> Result<Option<CachedToken>> {
+        if config.sso_session.is_none() {
+            return Ok(None);
+        }
+        let (Some(refresh_token), Some(client_id), Some(client_secret)) =
+            (&token.refresh_token, &token.client_id, &token.client_secret)
+        else {
+            debug!("SSO token cache has no refresh token or client registration");
+            return Ok(None);
+        };
+        if let Some(registration_expires_at) = &token.registration_expires_at {
+            if registration_expires_at.parse::<Timestamp>()? <= now {
+                debug!("SSO OIDC client registration is expired");
+                return Ok(None);
+            }
+        }
+
+        let region = token.region.as_deref().unwrap_or(&config.sso_region);
+        let endpoint = match &self.oidc_endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => {
+                let endpoint = self.resolve_endpoint(ctx, &SSO_OIDC, region).await?;
+                format!("{}/token", endpoint.url())
+            }
+        };
+        let body = serde_json::to_vec(&CreateTokenRequest {
+            client_id,
+            client_secret,
+            grant_type: "refresh_token",
+            refresh_token,
+        })
+        .map_err(|e| Error::unexpected(format!("failed to encode CreateToken request: {e}")))?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(&endpoint)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(bytes::Bytes::from(body))
+            .map_err(|e| Error::unexpected(format!("failed to build request: {e}")))?;
+
+        let resp = ctx.http_send(req).await.map_err(|e| {
+            Error::unexpected("failed to send SSO OIDC CreateToken request")
+                .with_source(e)
+                .set_retryable(true)
+        })?;
+
+        let status = resp.status();
+        if status != StatusCode::OK {
+            let err = match status.as_u16() {
+                400 | 401 | 403 => Error::credential_invalid(format!(
+                    "SSO OIDC CreateToken rejected the refresh token with status {status}. Please run 'aws sso login'"
+                )),
+                429 => {
+                    Error::rate_limited(format!("SSO OIDC CreateToken returned status {status}"))
+                }
+                500..=599 => {
+                    Error::unexpected(format!("SSO OIDC CreateToken returned status {status}"))
+                        .set_retryable(true)
+                }
+                _ => Error::unexpected(format!("SSO OIDC CreateToken returned status {status}")),
+            };
+            return Err(err);
+        }
+
+        let created: CreateTokenResponse = serde_json::from_slice(resp.body())
+            .map_err(|e| Error::unexpected(format!("failed to parse CreateToken response: {e}")))?;
+
+        let mut refreshed = token.clone();
+        refreshed.access_token = created.access_token;
+        refreshed.expires_at =
+            (now + Duration::from_secs(created.expires_in)).format_rfc3339_zulu();
+        if let Some(refresh_token) = created.refresh_token {
+            refreshed.refresh_token = Some(refresh_token);
+        }
+        Ok(Some(refreshed))
+    }
+
     async fn get_role_credentials(
         &self,
         ctx: &Context,
@@ -239,16 +426,17 @@ impl SSOCredentialProvider {
         access_token: &str,
     ) -> Result<Credential> {
         // Allow endpoint override for testing
-        let endpoint = self
+        let endpoint = match self
             .sso_endpoint
             .clone()
             .or_else(|| ctx.env_var("AWS_SSO_ENDPOINT"))
-            .unwrap_or_else(|| {
-                format!(
-                    "https://portal.sso.{}.amazonaws.com/federation/credentials",
-                    config.sso_region
-                )
-            });
+        {
+            Some(endpoint) => endpoint,
+            None => {
+                let endpoint = self.resolve_endpoint(ctx, &SSO, &config.sso_region).await?;
+                format!("{}/federation/credentials", endpoint.url())
+            }
+        };
 
         let params = serde_urlencoded::to_string([
             ("role_name", &config.sso_role_name),
@@ -300,14 +488,59 @@ struct SSOConfig {
     sso_region: String,
     sso_role_name: String,
     sso_start_url: String,
+    sso_session: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+/// SSO token cache entry. Unknown fields are preserved when written back.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 struct CachedToken {
-    #[serde(rename = "accessToken")]
     access_token: String,
-    #[serde(rename = "expiresAt")]
     expires_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    registration_expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    region: Option<String>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Debug for CachedToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedToken")
+            .field("access_token", &Redact::from(&self.access_token))
+            .field("expires_at", &self.expires_at)
+            .field("refresh_token", &Redact::from(&self.refresh_token))
+            .field("client_id", &self.client_id)
+            .field("client_secret", &Redact::from(&self.client_secret))
+            .field("registration_expires_at", &self.registration_expires_at)
+            .field("region", &self.region)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateTokenRequest<'a> {
+    client_id: &'a str,
+    client_secret: &'a str,
+    grant_type: &'a str,
+    refresh_token: &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateTokenResponse {
+    access_token: String,
+    expires_in: u64,
+    #[serde(default)]
+    refresh_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -342,14 +575,11 @@ impl ProvideCredential for SSOCredentialProvider {
         );
 
         // Find cached SSO token
-        let token = self
-            .find_cached_token(ctx, &config.sso_start_url)
-            .await?
-            .ok_or_else(|| {
-                Error::config_invalid(
-                    "No valid SSO token found. Please run 'aws sso login' first".to_string(),
-                )
-            })?;
+        let token = self.find_cached_token(ctx, &config).await?.ok_or_else(|| {
+            Error::config_invalid(
+                "No valid SSO token found. Please run 'aws sso login' first".to_string(),
+            )
+        })?;
 
         // Exchange token for role credentials
         let creds = self
@@ -371,10 +601,56 @@ fn hex_sha1(data: &[u8]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reqsign_core::HttpSend;
     use reqsign_core::StaticEnv;
-    use reqsign_file_read_tokio::TokioFileRead;
+    use reqsign_file_read_tokio::{TokioFileRead, TokioFileWrite};
     use reqsign_http_send_reqwest::ReqwestHttpSend;
     use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    const OIDC_ENDPOINT: &str = "https://oidc.test/token";
+
+    /// Request URI with the SSO bearer token seen by the portal, or the
+    /// request body seen by the OIDC token endpoint.
+    type RecordedRequest = (String, Option<String>);
+
+    #[derive(Clone, Debug, Default)]
+    struct CaptureHttpSend {
+        requests: Arc<Mutex<Vec<RecordedRequest>>>,
+        oidc_status: Option<StatusCode>,
+    }
+
+    impl HttpSend for CaptureHttpSend {
+        async fn http_send(
+            &self,
+            req: http::Request<bytes::Bytes>,
+        ) -> Result<http::Response<bytes::Bytes>> {
+            if req.uri() == OIDC_ENDPOINT {
+                self.requests.lock().unwrap().push((
+                    req.uri().to_string(),
+                    Some(String::from_utf8_lossy(req.body()).to_string()),
+                ));
+                return Ok(http::Response::builder()
+                    .status(self.oidc_status.unwrap_or(StatusCode::OK))
+                    .body(bytes::Bytes::from_static(
+                        br#"{"accessToken":"refreshed-token","expiresIn":3600,"refreshToken":"rotated-refresh-token","tokenType":"Bearer"}"#,
+                    ))
+                    .unwrap());
+            }
+            self.requests.lock().unwrap().push((
+                req.uri().to_string(),
+                req.headers()
+                    .get("x-amz-sso_bearer_token")
+                    .map(|v| v.to_str().unwrap().to_string()),
+            ));
+            Ok(http::Response::builder()
+                .status(StatusCode::OK)
+                .body(bytes::Bytes::from_static(
+                    br#"{"roleCredentials":{"accessKeyId":"ASIASSOSESSION","secretAccessKey":"secret","sessionToken":"token","expiration":4102444800000}}"#,
+                ))
+                .unwrap())
+        }
+    }
 
     #[tokio::test]
     async fn test_sso_provider_no_config() {
@@ -397,4 +673,199 @@ mod tests {
         let hash = hex_sha1(url.as_bytes());
         assert_eq!(hash.len(), 40); // SHA1 produces 40 hex characters
     }
+
+    #[tokio::test]
+    async fn test_sso_provider_with_sso_session() -> anyhow::Result<()> {
+        let home = tempfile::tempdir()?;
+        let cache_dir = home.path().join(".aws").join("sso").join("cache");
+        std::fs::create_dir_all(&cache_dir)?;
+        std::fs::write(
+            cache_dir.join(format!("{}.json", hex_sha1(b"corp"))),
+            r#"{"accessToken":"session-token","expiresAt":"2099-01-01T00:00:00Z"}"#,
+        )?;
+        let config = home.path().join("config");
+        std::fs::write(
+            &config,
+            "[profile dev]\n\
+             sso_session = corp\n\
+             sso_account_id = 123456789012\n\
+             sso_role_name = Reader\n\
+             [profile mismatch]\n\
+             sso_session = corp\n\
+             sso_region = eu-west-1\n\
+             sso_account_id = 123456789012\n\
+             sso_role_name = Reader\n\
+             [sso-session corp]\n\
+             sso_start_url = https://corp.awsapps.com/start\n\
+             sso_region = us-east-1\n",
+        )?;
+
+        let http = CaptureHttpSend::default();
+        let ctx = Context::new()
+            .with_file_read(TokioFileRead)
+            .with_http_send(http.clone())
+            .with_env(StaticEnv {
+                home_dir: Some(home.path().to_path_buf()),
+                envs: HashMap::new(),
+            });
+
+        let cred = SSOCredentialProvider::new()
+            .with_profile("dev")
+            .with_config_file(config.to_string_lossy())
+            .provide_credential(&ctx)
+            .await?
+            .expect("sso-session profile must provide credentials");
+        assert_eq!(cred.access_key_id, "ASIASSOSESSION");
+        assert_eq!(
+            http.requests.lock().unwrap().clone(),
+            vec![(
+                "https://portal.sso.us-east-1.amazonaws.com/federation/credentials?role_name=Reader&account_id=123456789012".to_string(),
+                Some("session-token".to_string())
+            )]
+        );
+
+        let err = SSOCredentialProvider::new()
+            .with_profile("mismatch")
+            .with_config_file(config.to_string_lossy())
+            .load_sso_config(&ctx)
+            .await
+            .expect_err("mismatched sso_region must fail");
+        assert!(err.to_string().contains("does not match sso-session"));
+        Ok(())
+    }
+
+    const PORTAL_URI: &str = "https://portal.sso.us-east-1.amazonaws.com/federation/credentials?role_name=Reader&account_id=123456789012";
+
+    /// Write an sso-session profile and a cached token expiring at `expires_at`.
+    fn write_refreshable_cache(
+        home: &std::path::Path,
+        expires_at: Timestamp,
+    ) -> anyhow::Result<(String, std::path::PathBuf)> {
+        let cache_dir = home.join(".aws").join("sso").join("cache");
+        std::fs::create_dir_all(&cache_dir)?;
+        let cache_file = cache_dir.join(format!("{}.json", hex_sha1(b"corp")));
+        std::fs::write(
+            &cache_file,
+            serde_json::json!({
+                "startUrl": "https://corp.awsapps.com/start",
+                "region": "us-east-1",
+                "accessToken": "cached-token",
+                "expiresAt": expires_at.format_rfc3339_zulu(),
+                "clientId": "client-id",
+                "clientSecret": "client-secret",
+                "registrationExpiresAt": "2099-01-01T00:00:00Z",
+                "refreshToken": "refresh-token",
+            })
+            .to_string(),
+        )?;
+        let config = home.join("config");
+        std::fs::write(
+            &config,
+            "[profile dev]\n\
+             sso_session = corp\n\
+             sso_account_id = 123456789012\n\
+             sso_role_name = Reader\n\
+             [sso-session corp]\n\
+             sso_start_url = https://corp.awsapps.com/start\n\
+             sso_region = us-east-1\n",
+        )?;
+        Ok((config.to_string_lossy().to_string(), cache_file))
+    }
+
+    fn refresh_context(home: &std::path::Path, http: &CaptureHttpSend) -> Context {
+        Context::new()
+            .with_file_read(TokioFileRead)
+            .with_file_write(TokioFileWrite)
+            .with_http_send(http.clone())
+            .with_env(StaticEnv {
+                home_dir: Some(home.to_path_buf()),
+                envs: HashMap::new(),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_sso_provider_refreshes_expired_token() -> anyhow::Result<()> {
+        let home = tempfile::tempdir()?;
+        let (config, cache_file) =
+            write_refreshable_cache(home.path(), Timestamp::now() - Duration::from_secs(60))?;
+        let http = CaptureHttpSend::default();
+        let ctx = refresh_context(home.path(), &http);
+
+        let cred = SSOCredentialProvider::new()
+            .with_profile("dev")
+            .with_config_file(config)
+            .with_oidc_endpoint(OIDC_ENDPOINT)
+            .provide_credential(&ctx)
+            .await?
+            .expect("refreshed SSO token must provide credentials");
+        assert_eq!(cred.access_key_id, "ASIASSOSESSION");
+
+        let requests = http.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        let body: serde_json::Value = serde_json::from_str(requests[0].1.as_deref().unwrap())?;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "clientId": "client-id",
+                "clientSecret": "client-secret",
+                "grantType": "refresh_token",
+                "refreshToken": "refresh-token",
+            })
+        );
+        assert_eq!(
+            requests[1],
+            (PORTAL_URI.to_string(), Some("refreshed-token".to_string()))
+        );
+
+        let cache: serde_json::Value = serde_json::from_slice(&std::fs::read(&cache_file)?)?;
+        assert_eq!(cache["accessToken"], "refreshed-token");
+        assert_eq!(cache["refreshToken"], "rotated-refresh-token");
+        assert_eq!(cache["startUrl"], "https://corp.awsapps.com/start");
+        assert_eq!(cache["clientSecret"], "client-secret");
+        let expires_at: Timestamp = cache["expiresAt"].as_str().unwrap().parse()?;
+        assert!(expires_at > Timestamp::now() + Duration::from_secs(3000));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sso_provider_keeps_valid_token_when_refresh_fails() -> anyhow::Result<()> {
+        let home = tempfile::tempdir()?;
+        let (config, cache_file) =
+            write_refreshable_cache(home.path(), Timestamp::now() + Duration::from_secs(300))?;
+        let http = CaptureHttpSend {
+            oidc_status: Some(StatusCode::BAD_REQUEST),
+            ..Default::default()
+        };
+        let ctx = refresh_context(home.path(), &http);
+
+        let cred = SSOCredentialProvider::new()
+            .with_profile("dev")
+            .with_config_file(config.clone())
+            .with_oidc_endpoint(OIDC_ENDPOINT)
+            .provide_credential(&ctx)
+            .await?
+            .expect("valid cached SSO token must still be used");
+        assert_eq!(cred.access_key_id, "ASIASSOSESSION");
+        assert_eq!(
+            http.requests.lock().unwrap()[1],
+            (PORTAL_URI.to_string(), Some("cached-token".to_string()))
+        );
+        let cache: serde_json::Value = serde_json::from_slice(&std::fs::read(&cache_file)?)?;
+        assert_eq!(cache["accessToken"], "cached-token");
+
+        std::fs::write(
+            &cache_file,
+            std::fs::read_to_string(&cache_file)?
+                .replace(cache["expiresAt"].as_str().unwrap(), "2000-01-01T00:00:00Z"),
+        )?;
+        let err = SSOCredentialProvider::new()
+            .with_profile("dev")
+            .with_config_file(config)
+            .with_oidc_endpoint(OIDC_ENDPOINT)
+            .provide_credential(&ctx)
+            .await
+            .expect_err("expired token with rejected refresh must fail");
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::CredentialInvalid);
+        Ok(())
+    }
 }
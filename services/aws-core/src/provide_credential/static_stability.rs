@@ -0,0 +1,193 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use log::warn;
+use reqsign_core::time::Timestamp;
+use reqsign_core::{Error, ErrorKind, Result};
+
+use crate::Credential;
+use crate::provide_credential::utils::random_bytes;
+
+/// Minimum time an expired credential is extended by.
+const EXTENSION_BASE: Duration = Duration::from_secs(15 * 60);
+/// Upper bound of the random jitter added on top of [`EXTENSION_BASE`].
+const EXTENSION_JITTER_SECS: u64 = 5 * 60;
+
+/// Static stability for instance and container metadata credentials.
+///
+/// Metadata services can briefly return expired credentials or become
+/// unreachable. Instead of failing hard, the last known credential is kept and
+/// its expiry is extended by 15 to 20 minutes so requests keep being signed
+/// while the service recovers. The random window spreads the retries of many
+/// hosts hitting the same outage.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StaticStability {
+    last: Arc<Mutex<Option<Credential>>>,
+}
+
+impl StaticStability {
+    /// Remember a freshly loaded credential, extending it if it's already expired.
+    pub(crate) fn record(&self, source: &str, mut cred: Credential) -> Credential {
+        let now = Timestamp::now();
+        if cred.expires_in.is_some_and(|expires_in| expires_in <= now) {
+            let expires_in = extended_expiry(now);
+            warn!(
+                "{source} returned expired credentials, extending expiration to {expires_in} for static stability"
+            );
+            cred.expires_in = Some(expires_in);
+        }
+
+        *self.last.lock().expect("lock poisoned") = Some(cred.clone());
+        cred
+    }
+
+    /// Fall back to the last known credential when loading a new one failed.
+    ///
+    /// Only retryable failures, such as transport errors and 5xx responses,
+    /// and throttling fall back. Other errors, and any error before a credential
+    /// has been loaded, are returned as is so a revoked role or a broken
+    /// configuration is not masked.
+    pub(crate) fn fallback(&self, source: &str, err: Error) -> Result<Credential> {
+        if !is_transient(&err) {
+            return Err(err);
+        }
+
+        let mut last = self.last.lock().expect("lock poisoned");
+        let Some(cred) = last.as_mut() else {
+            return Err(err);
+        };
+
+        let now = Timestamp::now();
+        if cred.expires_in.is_some_and(|expires_in| expires_in <= now) {
+            cred.expires_in = Some(extended_expiry(now));
+        }
+        warn!(
+            "failed to load credentials from {source}, reusing the last known credentials for static stability: {err}"
+        );
+        Ok(cred.clone())
+    }
+}
+
+fn is_transient(err: &Error) -> bool {
+    err.is_retryable() || err.kind() == ErrorKind::RateLimited
+}
+
+fn extended_expiry(now: Timestamp) -> Timestamp {
+    // Without randomness the extension is still valid, only not spread out.
+    let jitter = random_bytes()
+        .map(|bytes| u64::from_le_bytes(bytes) % EXTENSION_JITTER_SECS)
+        .unwrap_or_default();
+    now + EXTENSION_BASE + Duration::from_secs(jitter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqsign_core::SigningCredential;
+
+    fn credential(expires_in: Timestamp) -> Credential {
+        Credential {
+            access_key_id: "access-key".to_string(),
+            secret_access_key: "secret-key".to_string(),
+            session_token: Some("token".to_string()),
+            expires_in: Some(expires_in),
+        }
+    }
+
+    #[test]
+    fn test_record_extends_expired_credential() {
+        let stability = StaticStability::default();
+        let now = Timestamp::now();
+
+        let cred = stability.record("IMDS", credential(now - Duration::from_secs(60)));
+        let expires_in = cred.expires_in.unwrap();
+        assert!(expires_in >= now + EXTENSION_BASE);
+        assert!(expires_in <= Timestamp::now() + EXTENSION_BASE + Duration::from_secs(300));
+        assert!(cred.is_valid());
+    }
+
+    #[test]
+    fn test_record_keeps_valid_expiration() {
+        let stability = StaticStability::default();
+        let expires_in = Timestamp::now() + Duration::from_secs(3600);
+
+        let cred = stability.record("IMDS", credential(expires_in));
+        assert_eq!(cred.expires_in, Some(expires_in));
+    }
+
+    #[test]
+    fn test_fallback_reuses_last_credential() {
+        let stability = StaticStability::default();
+        let err = stability
+            .fallback("IMDS", Error::unexpected("outage").set_retryable(true))
+            .expect_err("nothing to fall back to");
+        assert_eq!(
+            err.to_string(),
+            Error::unexpected("outage").set_retryable(true).to_string()
+        );
+
+        stability.record(
+            "IMDS",
+            credential(Timestamp::now() + Duration::from_secs(3600)),
+        );
+        let cred = stability
+            .fallback("IMDS", Error::unexpected("outage").set_retryable(true))
+            .expect("last credential must be reused");
+        assert_eq!(cred.access_key_id, "access-key");
+    }
+
+    #[test]
+    fn test_fallback_returns_non_transient_errors() {
+        let stability = StaticStability::default();
+        stability.record(
+            "IMDS",
+            credential(Timestamp::now() + Duration::from_secs(3600)),
+        );
+
+        for err in [
+            Error::config_invalid("no IAM role attached"),
+            Error::permission_denied("role revoked"),
+            Error::credential_invalid("bad credentials"),
+            Error::unexpected("failed to parse IMDS credentials response"),
+        ] {
+            let kind = err.kind();
+            let err = stability
+                .fallback("IMDS", err)
+                .expect_err("non-transient errors must not fall back");
+            assert_eq!(err.kind(), kind);
+        }
+
+        stability
+            .fallback("IMDS", Error::rate_limited("throttled"))
+            .expect("throttling must fall back");
+    }
+
+    #[test]
+    fn test_fallback_extends_expired_credential() {
+        let stability = StaticStability::default();
+        *stability.last.lock().unwrap() =
+            Some(credential(Timestamp::now() - Duration::from_secs(1)));
+
+        let cred = stability
+            .fallback("ECS", Error::unexpected("outage").set_retryable(true))
+            .expect("last credential must be reused");
+        assert!(cred.is_valid());
+    }
+}
@@ -22,6 +22,15 @@ use serde::Deserialize;
 pub(crate) struct AwsPartition {
     pub(crate) id: &'static str,
     pub(crate) dns_suffix: &'static str,
+    pub(crate) dual_stack_dns_suffix: Option<&'static str>,
+}
+
+/// Fill an array with bytes from the operating system's secure random source.
+pub(crate) fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| Error::unexpected("failed to generate random bytes").with_source(e))?;
+    Ok(bytes)
 }
 
 pub(crate) fn partition_for_region(region: &str) -> Result<AwsPartition> {
@@ -45,41 +54,49 @@ pub(crate) fn partition_for_region(region: &str) -> Result<AwsPartition> {
         AwsPartition {
             id: "aws-cn",
             dns_suffix: "amazonaws.com.cn",
+            dual_stack_dns_suffix: Some("api.amazonwebservices.com.cn"),
         }
     } else if region.starts_with("eusc-") {
         AwsPartition {
             id: "aws-eusc",
             dns_suffix: "amazonaws.eu",
+            dual_stack_dns_suffix: None,
         }
     } else if region.starts_with("us-isob-") {
         AwsPartition {
             id: "aws-iso-b",
             dns_suffix: "sc2s.sgov.gov",
+            dual_stack_dns_suffix: None,
         }
     } else if region.starts_with("us-iso-") {
         AwsPartition {
             id: "aws-iso",
             dns_suffix: "c2s.ic.gov",
+            dual_stack_dns_suffix: None,
         }
     } else if region.starts_with("eu-isoe-") {
         AwsPartition {
             id: "aws-iso-e",
             dns_suffix: "cloud.adc-e.uk",
+            dual_stack_dns_suffix: None,
         }
     } else if region.starts_with("us-isof-") {
         AwsPartition {
             id: "aws-iso-f",
             dns_suffix: "csp.hci.ic.gov",
+            dual_stack_dns_suffix: None,
         }
     } else if region.starts_with("us-gov-") {
         AwsPartition {
             id: "aws-us-gov",
             dns_suffix: "amazonaws.com",
+            dual_stack_dns_suffix: Some("api.aws"),
         }
     } else {
         AwsPartition {
             id: "aws",
             dns_suffix: "amazonaws.com",
+            dual_stack_dns_suffix: Some("api.aws"),
         }
     };
     Ok(partition)
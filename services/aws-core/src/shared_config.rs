@@ -0,0 +1,605 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! AWS shared config and credentials file model.
+//!
+//! The parser follows the AWS SDK shared file format:
+//!
+//! - The config file declares `[default]`, `[profile name]`, `[sso-session name]`
+//!   and `[services name]` sections. `[profile default]` takes precedence over
+//!   `[default]`.
+//! - The credentials file declares profiles with bare `[name]` headers.
+//! - A property with an empty value followed by indented `key = value` lines
+//!   holds sub-properties. Other indented lines continue the previous value.
+//! - Lines starting with `#` or `;` are comments. In property lines a comment
+//!   marker must be preceded by whitespace.
+//! - Profiles defined in both files are merged, and properties from the
+//!   credentials file take precedence.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+use log::{debug, warn};
+use reqsign_core::{Context, Result};
+
+use crate::constants::{AWS_CONFIG_FILE, AWS_SHARED_CREDENTIALS_FILE};
+
+/// Loader for the AWS shared config and credentials files.
+///
+/// Paths are resolved from:
+/// 1. The path set via `with_config_file()` or `with_credentials_file()`
+/// 2. The `AWS_CONFIG_FILE` or `AWS_SHARED_CREDENTIALS_FILE` environment variable
+/// 3. `~/.aws/config` or `~/.aws/credentials`
+///
+/// Missing files are treated as empty.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileFiles {
+    config_file: Option<String>,
+    credentials_file: Option<String>,
+}
+
+impl ProfileFiles {
+    /// Create a loader using the default file locations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the path to the config file.
+    pub fn with_config_file(mut self, path: impl Into<String>) -> Self {
+        self.config_file = Some(path.into());
+        self
+    }
+
+    /// Set the path to the credentials file.
+    pub fn with_credentials_file(mut self, path: impl Into<String>) -> Self {
+        self.credentials_file = Some(path.into());
+        self
+    }
+
+    /// Read and parse both files.
+    pub async fn load(&self, ctx: &Context) -> Result<ProfileSet> {
+        let config = self
+            .read(
+                ctx,
+                self.config_file.as_ref(),
+                AWS_CONFIG_FILE,
+                "~/.aws/config",
+            )
+            .await;
+        let credentials = self
+            .read(
+                ctx,
+                self.credentials_file.as_ref(),
+                AWS_SHARED_CREDENTIALS_FILE,
+                "~/.aws/credentials",
+            )
+            .await;
+        Ok(ProfileSet::parse(
+            config.as_deref().unwrap_or_default(),
+            credentials.as_deref().unwrap_or_default(),
+        ))
+    }
+
+    async fn read(
+        &self,
+        ctx: &Context,
+        path: Option<&String>,
+        env: &str,
+        default_path: &str,
+    ) -> Option<String> {
+        let path = path
+            .cloned()
+            .or_else(|| ctx.env_var(env))
+            .unwrap_or_else(|| default_path.to_string());
+
+        let expanded_path = if path.starts_with("~/") {
+            match ctx.expand_home_dir(&path) {
+                Some(expanded) => expanded,
+                None => {
+                    debug!("failed to expand homedir for path: {path}");
+                    return None;
+                }
+            }
+        } else {
+            path
+        };
+
+        match ctx.file_read(&expanded_path).await {
+            Ok(content) => Some(String::from_utf8_lossy(&content).into_owned()),
+            Err(err) => {
+                debug!("failed to read {expanded_path}: {err:?}");
+                None
+            }
+        }
+    }
+}
+
+/// Profiles, SSO sessions and services sections from the shared files.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileSet {
+    profiles: HashMap<String, Section>,
+    sso_sessions: HashMap<String, Section>,
+    services: HashMap<String, Section>,
+}
+
+impl ProfileSet {
+    /// Parse the content of a config file and a credentials file.
+    pub fn parse(config: &str, credentials: &str) -> Self {
+        let mut set = ProfileSet::default();
+        let mut bare_default: Option<Section> = None;
+
+        for section in parse_sections(config) {
+            let header = section.name.clone();
+            let (kind, name) = match header.split_once(char::is_whitespace) {
+                Some((kind, name)) => (kind, name.trim()),
+                None => (header.as_str(), ""),
+            };
+            let target = match (kind, name) {
+                ("default", "") => {
+                    merge(&mut bare_default, section, "default");
+                    continue;
+                }
+                ("profile", name) if !name.is_empty() => &mut set.profiles,
+                ("sso-session", name) if !name.is_empty() => &mut set.sso_sessions,
+                ("services", name) if !name.is_empty() => &mut set.services,
+                _ => {
+                    warn!("ignoring section [{header}] in AWS config file");
+                    continue;
+                }
+            };
+            if !is_valid_name(name) {
+                warn!("ignoring section [{header}] with invalid name in AWS config file");
+                continue;
+            }
+            let mut slot = target.remove(name);
+            merge(&mut slot, section, name);
+            target.extend(slot.map(|s| (name.to_string(), s)));
+        }
+
+        if let Some(section) = bare_default {
+            if set.profiles.contains_key("default") {
+                warn!("ignoring [default] in AWS config file because [profile default] exists");
+            } else {
+                set.profiles.insert("default".to_string(), section);
+            }
+        }
+
+        for section in parse_sections(credentials) {
+            let name = section.name.clone();
+            if name.starts_with("profile ") || !is_valid_name(&name) {
+                warn!("ignoring section [{name}] in AWS credentials file");
+                continue;
+            }
+            let mut slot = set.profiles.remove(&name);
+            merge(&mut slot, section, &name);
+            set.profiles.extend(slot.map(|s| (name, s)));
+        }
+
+        set
+    }
+
+    /// Return the profile with the given name.
+    pub fn profile(&self, name: &str) -> Option<&Section> {
+        self.profiles.get(name)
+    }
+
+    /// Return the names of all profiles.
+    pub fn profile_names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Return the `[sso-session name]` section.
+    pub fn sso_session(&self, name: &str) -> Option<&Section> {
+        self.sso_sessions.get(name)
+    }
+
+    /// Return the `[services name]` section.
+    pub fn services(&self, name: &str) -> Option<&Section> {
+        self.services.get(name)
+    }
+
+    /// Return the endpoint URL configured for a service in a profile.
+    ///
+    /// The `endpoint_url` sub-property of the service in the profile's
+    /// `services` section takes precedence over the profile's global
+    /// `endpoint_url`. `service_id` is matched after lowercasing it and
+    /// replacing spaces with underscores, so both `S3` and `s3` match `s3`.
+    pub fn endpoint_url(&self, profile: &str, service_id: &str) -> Option<&str> {
+        self.service_endpoint_url(profile, service_id)
+            .or_else(|| self.profile(profile)?.get("endpoint_url"))
+    }
+
+    /// Return the endpoint URL from the profile's `services` section only.
+    pub(crate) fn service_endpoint_url(&self, profile: &str, service_id: &str) -> Option<&str> {
+        let service_key = service_id.to_ascii_lowercase().replace(' ', "_");
+        self.profile(profile)?
+            .get("services")
+            .and_then(|name| self.services(name))
+            .and_then(|services| services.property(&service_key))
+            .and_then(|property| property.sub_property("endpoint_url"))
+    }
+}
+
+/// A named section of the shared files.
+#[derive(Clone, Default)]
+pub struct Section {
+    name: String,
+    properties: HashMap<String, Property>,
+}
+
+impl Debug for Section {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut keys: Vec<_> = self.properties.keys().collect();
+        keys.sort();
+        f.debug_struct("Section")
+            .field("name", &self.name)
+            .field("properties", &keys)
+            .finish()
+    }
+}
+
+impl Section {
+    /// Return the section name without its `profile`, `sso-session` or
+    /// `services` prefix.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the value of a property.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).map(|p| p.value.as_str())
+    }
+
+    /// Return a property together with its sub-properties.
+    pub fn property(&self, key: &str) -> Option<&Property> {
+        self.properties.get(key)
+    }
+
+    /// Return whether the property is set.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.properties.contains_key(key)
+    }
+}
+
+/// A property value and its sub-properties.
+#[derive(Clone, Default)]
+pub struct Property {
+    value: String,
+    sub_properties: HashMap<String, String>,
+}
+
+impl Debug for Property {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut keys: Vec<_> = self.sub_properties.keys().collect();
+        keys.sort();
+        f.debug_struct("Property")
+            .field("sub_properties", &keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Property {
+    /// Return the property value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Return the value of a sub-property.
+    pub fn sub_property(&self, key: &str) -> Option<&str> {
+        self.sub_properties.get(key).map(String::as_str)
+    }
+}
+
+/// Merge `section` into `slot`, with later properties overriding earlier ones.
+fn merge(slot: &mut Option<Section>, section: Section, name: &str) {
+    let target = slot.get_or_insert_with(|| Section {
+        name: name.to_string(),
+        properties: HashMap::new(),
+    });
+    target.properties.extend(section.properties);
+}
+
+/// Profile, section and property names may only contain these characters.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-/.%@:+".contains(c))
+}
+
+/// Strip a trailing comment from a property line.
+fn strip_comment(line: &str) -> &str {
+    let mut prev_whitespace = false;
+    for (idx, c) in line.char_indices() {
+        if prev_whitespace && (c == '#' || c == ';') {
+            return &line[..idx];
+        }
+        prev_whitespace = c.is_whitespace();
+    }
+    line
+}
+
+/// Parse a file into sections named after their full header.
+fn parse_sections(content: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut in_section = false;
+    let mut last_key: Option<String> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+
+        if let Some(header) = trimmed.strip_prefix('[') {
+            last_key = None;
+            match header.split_once(']') {
+                Some((name, _)) => {
+                    sections.push(Section {
+                        name: name.split_whitespace().collect::<Vec<_>>().join(" "),
+                        properties: HashMap::new(),
+                    });
+                    in_section = true;
+                }
+                None => {
+                    warn!("ignoring malformed section header in AWS shared file");
+                    in_section = false;
+                }
+            }
+            continue;
+        }
+        if !in_section {
+            warn!("ignoring property defined outside of a section in AWS shared file");
+            continue;
+        }
+        let section = sections.last_mut().expect("section must exist");
+
+        let continuation = line.starts_with([' ', '\t']);
+        if let (true, Some(key)) = (continuation, &last_key) {
+            let property = section
+                .properties
+                .get_mut(key)
+                .expect("continued property must exist");
+            if property.value.is_empty() || !property.sub_properties.is_empty() {
+                match strip_comment(trimmed).split_once('=') {
+                    Some((k, v)) if is_valid_name(k.trim()) => {
+                        property
+                            .sub_properties
+                            .insert(k.trim().to_string(), v.trim().to_string());
+                    }
+                    _ => warn!("ignoring malformed sub-property of {key} in AWS shared file"),
+                }
+            } else {
+                property.value.push('\n');
+                property.value.push_str(trimmed);
+            }
+            continue;
+        }
+
+        match strip_comment(trimmed).split_once('=') {
+            Some((key, value)) if is_valid_name(key.trim()) => {
+                let key = key.trim().to_string();
+                section.properties.insert(
+                    key.clone(),
+                    Property {
+                        value: value.trim().to_string(),
+                        sub_properties: HashMap::new(),
+                    },
+                );
+                last_key = Some(key);
+            }
+            _ => {
+                warn!("ignoring malformed property in AWS shared file");
+                last_key = None;
+            }
+        }
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use reqsign_core::StaticEnv;
+    use reqsign_file_read_tokio::TokioFileRead;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_config_profile_section_rules() {
+        let set = ProfileSet::parse(
+            "[default]\n\
+             region = us-west-1\n\
+             [profile default]\n\
+             region = us-east-1\n\
+             [profile  dev ]\n\
+             region = eu-west-1\n\
+             [bare]\n\
+             region = ap-south-1\n\
+             [profile bad name!]\n\
+             region = ap-south-1\n",
+            "[profile ignored]\n\
+             aws_access_key_id = AKID\n",
+        );
+
+        assert_eq!(
+            set.profile("default").unwrap().get("region"),
+            Some("us-east-1")
+        );
+        assert_eq!(set.profile("dev").unwrap().get("region"), Some("eu-west-1"));
+        assert_eq!(set.profile("dev").unwrap().name(), "dev");
+        assert!(set.profile("bare").is_none());
+        assert!(set.profile("bad name!").is_none());
+        assert!(set.profile("ignored").is_none());
+        assert!(set.profile("profile ignored").is_none());
+
+        let set = ProfileSet::parse("[default]\nregion = us-west-1\n", "");
+        assert_eq!(
+            set.profile("default").unwrap().get("region"),
+            Some("us-west-1")
+        );
+    }
+
+    #[test]
+    fn test_credentials_file_takes_precedence() {
+        let set = ProfileSet::parse(
+            "[profile dev]\n\
+             aws_access_key_id = CONFIGKEY\n\
+             region = eu-west-1\n",
+            "[dev]\n\
+             aws_access_key_id = CREDENTIALSKEY\n\
+             aws_secret_access_key = secret\n\
+             [dev]\n\
+             aws_session_token = token\n\
+             [other]\n\
+             aws_access_key_id = OTHERKEY\n",
+        );
+
+        let dev = set.profile("dev").unwrap();
+        assert_eq!(dev.get("aws_access_key_id"), Some("CREDENTIALSKEY"));
+        assert_eq!(dev.get("aws_secret_access_key"), Some("secret"));
+        assert_eq!(dev.get("aws_session_token"), Some("token"));
+        assert_eq!(dev.get("region"), Some("eu-west-1"));
+        assert_eq!(
+            set.profile("other").unwrap().get("aws_access_key_id"),
+            Some("OTHERKEY")
+        );
+
+        let mut names: Vec<_> = set.profile_names().collect();
+        names.sort();
+        assert_eq!(names, vec!["dev", "other"]);
+    }
+
+    #[test]
+    fn test_comments_continuations_and_sub_properties() {
+        let set = ProfileSet::parse(
+            "# leading comment\n\
+             ; another comment\n\
+             [profile dev] # header comment\n\
+             region = us-east-1 # trailing comment\n\
+             role_arn = arn:aws:iam::123456789012:role/a#b\n\
+             description = first line\n\
+             \x20 second line\n\
+             s3 =\n\
+             \x20 max_concurrent_requests = 20 ; comment\n\
+             \x20 addressing_style = path\n",
+            "",
+        );
+
+        let dev = set.profile("dev").unwrap();
+        assert_eq!(dev.get("region"), Some("us-east-1"));
+        assert_eq!(
+            dev.get("role_arn"),
+            Some("arn:aws:iam::123456789012:role/a#b")
+        );
+        assert_eq!(dev.get("description"), Some("first line\nsecond line"));
+        let s3 = dev.property("s3").unwrap();
+        assert_eq!(s3.value(), "");
+        assert_eq!(s3.sub_property("max_concurrent_requests"), Some("20"));
+        assert_eq!(s3.sub_property("addressing_style"), Some("path"));
+        assert!(dev.contains_key("s3"));
+    }
+
+    #[test]
+    fn test_sso_session_and_services_sections() {
+        let set = ProfileSet::parse(
+            "[profile dev]\n\
+             sso_session = corp\n\
+             services = dev-services\n\
+             endpoint_url = https://global.example.com\n\
+             [profile plain]\n\
+             region = us-east-1\n\
+             [sso-session corp]\n\
+             sso_start_url = https://corp.awsapps.com/start\n\
+             sso_region = us-east-1\n\
+             [services dev-services]\n\
+             s3 =\n\
+             \x20 endpoint_url = https://s3.example.com\n\
+             elastic_beanstalk =\n\
+             \x20 endpoint_url = https://eb.example.com\n",
+            "",
+        );
+
+        let session = set.sso_session("corp").unwrap();
+        assert_eq!(session.name(), "corp");
+        assert_eq!(
+            session.get("sso_start_url"),
+            Some("https://corp.awsapps.com/start")
+        );
+        assert!(set.profile("corp").is_none());
+        assert!(set.services("dev-services").is_some());
+
+        assert_eq!(
+            set.endpoint_url("dev", "S3"),
+            Some("https://s3.example.com")
+        );
+        assert_eq!(
+            set.endpoint_url("dev", "Elastic Beanstalk"),
+            Some("https://eb.example.com")
+        );
+        assert_eq!(
+            set.endpoint_url("dev", "sts"),
+            Some("https://global.example.com")
+        );
+        assert_eq!(set.endpoint_url("plain", "s3"), None);
+        assert_eq!(set.endpoint_url("missing", "s3"), None);
+    }
+
+    #[test]
+    fn test_section_debug_omits_values() {
+        let set = ProfileSet::parse("", "[dev]\naws_secret_access_key = SECRET\n");
+        let debug = format!("{:?}", set.profile("dev").unwrap());
+        assert!(debug.contains("aws_secret_access_key"));
+        assert!(!debug.contains("SECRET"));
+    }
+
+    #[tokio::test]
+    async fn test_profile_files_load() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let config = tmp_dir.path().join("config");
+        std::fs::write(&config, "[profile dev]\nregion = eu-west-1\n")?;
+        let credentials = tmp_dir.path().join("credentials");
+        std::fs::write(&credentials, "[dev]\naws_access_key_id = AKID\n")?;
+
+        let ctx = Context::new()
+            .with_file_read(TokioFileRead)
+            .with_env(StaticEnv {
+                home_dir: None,
+                envs: HashMap::from([(
+                    AWS_CONFIG_FILE.to_string(),
+                    config.to_string_lossy().to_string(),
+                )]),
+            });
+
+        let set = ProfileFiles::new()
+            .with_credentials_file(credentials.to_string_lossy())
+            .load(&ctx)
+            .await?;
+        let dev = set.profile("dev").unwrap();
+        assert_eq!(dev.get("region"), Some("eu-west-1"));
+        assert_eq!(dev.get("aws_access_key_id"), Some("AKID"));
+
+        let set = ProfileFiles::new()
+            .with_config_file("/non/existent/config")
+            .with_credentials_file("/non/existent/credentials")
+            .load(&ctx)
+            .await?;
+        assert_eq!(set.profile_names().count(), 0);
+        Ok(())
+    }
+}
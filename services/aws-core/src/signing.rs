@@ -21,7 +21,7 @@ use std::time::Duration;
 use http::{HeaderValue, Uri, header};
 use percent_encoding::{percent_decode_str, utf8_percent_encode};
 use reqsign_core::time::Timestamp;
-use reqsign_core::{Result, SigningRequest};
+use reqsign_core::{Result, SigningOverrides, SigningRequest};
 
 use crate::Credential;
 use crate::constants::{
@@ -33,6 +33,38 @@ use crate::constants::{
 pub fn canonical_request_string(
     request: &SigningRequest,
     canonical_query: &[(String, String)],
+) -> Result<String> {
+    canonical_request_string_with_signed_headers(
+        request,
+        canonical_query,
+        &request.header_name_to_vec_sorted(),
+    )
+}
+
+/// Select the sorted header names to sign.
+///
+/// A [`SigningOverrides`] signed header list restricts the result, but `host`
+/// and every `x-amz-*` header are always signed.
+pub fn signed_header_names<'a>(
+    request: &'a SigningRequest,
+    overrides: Option<&SigningOverrides>,
+) -> Vec<&'a str> {
+    let mut names = request.header_name_to_vec_sorted();
+    if let Some(overrides) = overrides {
+        names.retain(|name| {
+            *name == header::HOST.as_str()
+                || name.starts_with("x-amz-")
+                || overrides.signs_header(name)
+        });
+    }
+    names
+}
+
+/// Build the canonical request over an explicit sorted list of signed headers.
+pub fn canonical_request_string_with_signed_headers(
+    request: &SigningRequest,
+    canonical_query: &[(String, String)],
+    signed_headers: &[&str],
 ) -> Result<String> {
     let mut output = String::with_capacity(256);
 
@@ -52,8 +84,7 @@ pub fn canonical_request_string(
     )
     .map_err(|e| reqsign_core::Error::unexpected(format!("failed to write query: {e}")))?;
 
-    let signed_headers = request.header_name_to_vec_sorted();
-    for name in &signed_headers {
+    for name in signed_headers {
         let mut value = request.headers[*name].clone();
         SigningRequest::header_value_normalize(&mut value);
         writeln!(
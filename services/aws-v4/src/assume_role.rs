@@ -18,13 +18,14 @@
 use std::fmt::{Debug, Formatter};
 use std::time::Duration;
 
-use reqsign_aws_core::assume_role::{AssumeRoleOperation, regional_sts_endpoint};
+use reqsign_aws_core::EndpointConfig;
+use reqsign_aws_core::assume_role::{AssumeRoleOperation, resolve_regional_sts_endpoint};
 use reqsign_core::time::Timestamp;
 use reqsign_core::{Context, Error, GrantCredential, Result, SignRequest, SigningCredential};
 
 use crate::{AssumeRoleGrant, Credential, RequestSigner};
 
-const STS_REQUEST_HEADROOM: Duration = Duration::from_secs(20);
+pub(crate) const STS_REQUEST_HEADROOM: Duration = Duration::from_secs(20);
 const MIN_ASSUME_ROLE_DURATION: Duration = Duration::from_secs(900);
 const MAX_ASSUME_ROLE_DURATION: Duration = Duration::from_secs(43_200);
 
@@ -45,9 +46,10 @@ const MAX_ASSUME_ROLE_DURATION: Duration = Duration::from_secs(43_200);
 /// Callers performing role chaining must request at most one hour; STS remains
 /// authoritative for this and for the target role's configured maximum.
 ///
-/// This granter uses the standard regional STS endpoint for the bound signing
-/// region. Every call performs a new STS exchange; granted credentials are
-/// never cached.
+/// This granter uses the regional STS endpoint for the bound signing region,
+/// honouring the FIPS, dual-stack, and endpoint URL settings of
+/// [`EndpointConfig`]. Every call performs a new STS exchange; granted
+/// credentials are never cached.
 ///
 /// # Example
 ///
@@ -82,6 +84,7 @@ const MAX_ASSUME_ROLE_DURATION: Duration = Duration::from_secs(43_200);
 pub struct AssumeRoleGranter {
     region: String,
     grant: AssumeRoleGrant,
+    endpoint_config: EndpointConfig,
     #[cfg(test)]
     time: Option<Timestamp>,
 }
@@ -98,6 +101,7 @@ impl AssumeRoleGranter {
         Self {
             region: region.into(),
             grant,
+            endpoint_config: EndpointConfig::new(),
             #[cfg(test)]
             time: None,
         }
@@ -109,6 +113,12 @@ impl AssumeRoleGranter {
         self
     }
 
+    /// Set the FIPS, dual-stack, and endpoint URL settings for STS.
+    pub fn with_endpoint_config(mut self, config: EndpointConfig) -> Self {
+        self.endpoint_config = config;
+        self
+    }
+
     fn now(&self) -> Timestamp {
         #[cfg(test)]
         if let Some(time) = self.time {
@@ -126,50 +136,6 @@ impl AssumeRoleGranter {
         signer
     }
 
-    fn duration_seconds(expires_in: Option<Duration>) -> Result<Option<u32>> {
-        let Some(expires_in) = expires_in else {
-            return Ok(None);
-        };
-        if !(MIN_ASSUME_ROLE_DURATION..=MAX_ASSUME_ROLE_DURATION).contains(&expires_in) {
-            return Err(Error::request_invalid(
-                "AWS STS AssumeRole duration must be between 900 and 43200 seconds",
-            ));
-        }
-        let seconds = u32::try_from(expires_in.as_secs()).map_err(|_| {
-            Error::request_invalid(
-                "AWS STS AssumeRole duration must be between 900 and 43200 seconds",
-            )
-        })?;
-        Ok(Some(seconds))
-    }
-
-    fn validate_source(&self, credential: &Credential, required_until: Timestamp) -> Result<()> {
-        if !(16..=128).contains(&credential.access_key_id.chars().count())
-            || !credential
-                .access_key_id
-                .bytes()
-                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
-            || credential.secret_access_key.is_empty()
-        {
-            return Err(Error::credential_invalid(
-                "AWS STS AssumeRole requires a source access key and secret access key",
-            ));
-        }
-        if credential.session_token.as_ref().is_some_and(|token| {
-            token.trim().is_empty() || http::HeaderValue::try_from(token.as_str()).is_err()
-        }) {
-            return Err(Error::credential_invalid(
-                "AWS STS AssumeRole source session token is invalid",
-            ));
-        }
-        if !credential.is_valid_at(required_until) {
-            return Err(Error::credential_invalid(
-                "AWS source credential expires before the STS AssumeRole request can complete",
-            ));
-        }
-        Ok(())
-    }
-
     #[cfg(test)]
     fn with_time(mut self, time: Timestamp) -> Self {
         self.time = Some(time);
@@ -194,11 +160,19 @@ impl GrantCredential for AssumeRoleGranter {
         credential: &Self::Credential,
         expires_in: Option<Duration>,
     ) -> Result<Self::Credential> {
-        let duration_seconds = Self::duration_seconds(expires_in)?;
-        let endpoint = regional_sts_endpoint(&self.region, &self.grant)?;
-        let operation = AssumeRoleOperation::new(endpoint, &self.grant, duration_seconds)?;
+        let duration_seconds = sts_duration_seconds(
+            "AssumeRole",
+            expires_in,
+            MIN_ASSUME_ROLE_DURATION,
+            MAX_ASSUME_ROLE_DURATION,
+        )?;
+        let endpoint =
+            resolve_regional_sts_endpoint(ctx, &self.endpoint_config, &self.region, &self.grant)
+                .await?;
+        let operation =
+            AssumeRoleOperation::from_endpoint(&endpoint, &self.grant, duration_seconds)?;
         let required_until = self.required_valid_until(credential, expires_in);
-        self.validate_source(credential, required_until)?;
+        validate_sts_source("AssumeRole", credential, required_until, true)?;
 
         let request = operation.build_request()?;
         let (mut parts, body) = request.into_parts();
@@ -215,6 +189,71 @@ impl GrantCredential for AssumeRoleGranter {
     }
 }
 
+/// Convert a requested grant lifetime into STS `DurationSeconds`.
+pub(crate) fn sts_duration_seconds(
+    operation: &str,
+    expires_in: Option<Duration>,
+    minimum: Duration,
+    maximum: Duration,
+) -> Result<Option<u32>> {
+    let Some(expires_in) = expires_in else {
+        return Ok(None);
+    };
+    let invalid = || {
+        Error::request_invalid(format!(
+            "AWS STS {operation} duration must be between {} and {} seconds",
+            minimum.as_secs(),
+            maximum.as_secs()
+        ))
+    };
+    if !(minimum..=maximum).contains(&expires_in) {
+        return Err(invalid());
+    }
+    let seconds = u32::try_from(expires_in.as_secs()).map_err(|_| invalid())?;
+    Ok(Some(seconds))
+}
+
+/// Validate the explicit source credential that will sign an STS request.
+///
+/// `GetSessionToken` and `GetFederationToken` only accept long-term IAM user
+/// credentials, so they pass `allow_session_token = false`.
+pub(crate) fn validate_sts_source(
+    operation: &str,
+    credential: &Credential,
+    required_until: Timestamp,
+    allow_session_token: bool,
+) -> Result<()> {
+    if !(16..=128).contains(&credential.access_key_id.chars().count())
+        || !credential
+            .access_key_id
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+        || credential.secret_access_key.is_empty()
+    {
+        return Err(Error::credential_invalid(format!(
+            "AWS STS {operation} requires a source access key and secret access key"
+        )));
+    }
+    if credential.session_token.is_some() && !allow_session_token {
+        return Err(Error::credential_invalid(format!(
+            "AWS STS {operation} requires long-term IAM user source credentials"
+        )));
+    }
+    if credential.session_token.as_ref().is_some_and(|token| {
+        token.trim().is_empty() || http::HeaderValue::try_from(token.as_str()).is_err()
+    }) {
+        return Err(Error::credential_invalid(format!(
+            "AWS STS {operation} source session token is invalid"
+        )));
+    }
+    if !credential.is_valid_at(required_until) {
+        return Err(Error::credential_invalid(format!(
+            "AWS source credential expires before the STS {operation} request can complete"
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::VecDeque;
@@ -762,6 +801,28 @@ mod tests {
         assert_eq!(http.calls(), 0);
     }
 
+    #[tokio::test]
+    async fn rejects_invalid_resolved_endpoints_before_sts_io() {
+        let http = MockHttpSend::new(Vec::<http::Response<Bytes>>::new());
+        let context = Context::new().with_http_send(http.clone());
+        let source = source_credential(None);
+
+        for endpoint_url in [
+            "http://sts.us-east-1.amazonaws.com",
+            "https://sts.us-east-1.amazonaws.com:8443",
+            "https://sts.us-east-1.amazonaws.com/path",
+        ] {
+            let error = AssumeRoleGranter::new(REGION, valid_grant())
+                .with_endpoint_config(EndpointConfig::new().with_endpoint_url(endpoint_url))
+                .with_time(signing_time())
+                .grant_credential(&context, &source, None)
+                .await
+                .expect_err("invalid STS endpoint must be rejected");
+            assert_eq!(error.kind(), ErrorKind::ConfigInvalid, "{endpoint_url}");
+        }
+        assert_eq!(http.calls(), 0);
+    }
+
     #[tokio::test]
     async fn rejects_invalid_source_variants_before_sts_io() {
         let http = MockHttpSend::new(Vec::<http::Response<Bytes>>::new());
@@ -70,9 +70,13 @@
 //! 1. **Environment Variables**: `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`
 //! 2. **Credential File**: `~/.aws/credentials`
 //! 3. **IAM Roles**: For EC2 instances and ECS tasks
-//! 4. **AssumeRole**: Via STS AssumeRole operations
+//! 4. **AssumeRole**: Via STS AssumeRole and AssumeRoleWithSAML operations
 //! 5. **WebIdentity**: For Kubernetes service accounts
 //! 6. **SSO**: AWS SSO credentials
+//! 7. **Console Login**: Sessions created by `aws login`
+//! 8. **Session and Federation Tokens**: Via STS GetSessionToken and
+//!    GetFederationToken with long-term IAM user credentials
+//! 9. **IAM Roles Anywhere**: X.509 certificates exchanged via `CreateSession`
 //!
 //! ## Supported Services
 //!
@@ -114,8 +118,8 @@
 //!
 //! ### Selecting a Profile
 //!
-//! Use one explicit profile for the shared profile, SSO, and process credential
-//! sources:
+//! Use one explicit profile for the shared profile, SSO, login, and process
+//! credential sources:
 //!
 //! ```no_run
 //! use reqsign_aws_v4::DefaultCredentialProvider;
@@ -153,26 +157,37 @@
 
 mod assume_role;
 pub use assume_role::AssumeRoleGranter;
+mod session_token;
+pub use session_token::{GetFederationTokenGranter, GetSessionTokenGranter};
 mod sign_request;
 pub use sign_request::RequestSigner;
 mod s3_access_grants;
 pub use s3_access_grants::{
-    S3AccessGrantsConfig, S3AccessGrantsGrant, S3AccessGrantsGranter, S3AccessGrantsPermission,
-    S3AccessGrantsPrivilege, S3AccessGrantsTarget,
+    S3AccessGrantsCache, S3AccessGrantsConfig, S3AccessGrantsGrant, S3AccessGrantsGranter,
+    S3AccessGrantsPermission, S3AccessGrantsPrivilege, S3AccessGrantsTarget,
 };
 mod provide_credential;
 pub use provide_credential::{
-    S3ExpressSessionConfig, S3ExpressSessionGrant, S3ExpressSessionGranter, S3ExpressSessionMode,
+    DefaultCredentialProvider, DefaultCredentialProviderBuilder, S3ExpressSessionConfig,
+    S3ExpressSessionGrant, S3ExpressSessionGranter, S3ExpressSessionManager, S3ExpressSessionMode,
     S3ExpressSessionPartition, S3ExpressSessionProvider,
 };
 pub use reqsign_aws_core::constants;
+pub use reqsign_aws_core::endpoint;
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqsign_aws_core::shared_config;
 pub use reqsign_aws_core::{
-    AssumeRoleCredentialProvider, AssumeRoleGrant, AssumeRoleWithWebIdentityCredentialProvider,
-    CognitoIdentityCredentialProvider, Credential, DefaultCredentialProvider,
-    DefaultCredentialProviderBuilder, ECSCredentialProvider, EnvCredentialProvider,
+    AssumeRoleCredentialProvider, AssumeRoleGrant, AssumeRoleWithSAMLCredentialProvider,
+    AssumeRoleWithWebIdentityCredentialProvider, CognitoIdentityCredentialProvider, Credential,
+    ECSCredentialProvider, EKSPodIdentityCredentialProvider, Endpoint, EndpointConfig,
+    EnvCredentialProvider, GetFederationTokenCredentialProvider, GetFederationTokenGrant,
+    GetSessionTokenCredentialProvider, GetSessionTokenGrant, IMDSEndpointMode,
     IMDSv2CredentialProvider, ProfileCredentialProvider, StaticCredentialProvider,
 };
 #[cfg(not(target_arch = "wasm32"))]
-pub use reqsign_aws_core::{ProcessCredentialProvider, SSOCredentialProvider};
+pub use reqsign_aws_core::{
+    LoginCredentialProvider, ProcessCredentialProvider, ProfileFiles, ProfileSet,
+    RolesAnywhereCredentialProvider, SSOCredentialProvider,
+};
 
 pub const EMPTY_STRING_SHA256: &str = reqsign_aws_core::EMPTY_STRING_SHA256;
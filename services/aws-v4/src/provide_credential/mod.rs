@@ -15,8 +15,12 @@
 // specific language governing permissions and limitations
 // under the License.
 
+mod default;
+pub use default::{DefaultCredentialProvider, DefaultCredentialProviderBuilder};
+
 mod s3_express_session;
 pub use s3_express_session::{
-    S3ExpressSessionConfig, S3ExpressSessionGrant, S3ExpressSessionGranter, S3ExpressSessionMode,
-    S3ExpressSessionPartition, S3ExpressSessionProvider,
+    S3ExpressSessionConfig, S3ExpressSessionGrant, S3ExpressSessionGranter,
+    S3ExpressSessionManager, S3ExpressSessionMode, S3ExpressSessionPartition,
+    S3ExpressSessionProvider,
 };
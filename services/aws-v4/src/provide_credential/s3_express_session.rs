@@ -17,15 +17,19 @@
 
 use crate::Credential;
 use bytes::Bytes;
+use futures::lock::Mutex as AsyncMutex;
+use http::request::Parts;
 use http::{HeaderValue, Method, Request, StatusCode, header};
 use reqsign_core::time::Timestamp;
 use reqsign_core::{
     Context, Error, GrantCredential, ProvideCredential, ProvideCredentialDyn, Result, SignRequest,
-    SigningCredential,
+    SigningCredential, SigningOverrideField, SigningOverrides,
 };
 use serde::Deserialize;
+use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
+use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 
 const CREATE_SESSION_OPERATION_HEADROOM: Duration = Duration::from_secs(10);
@@ -550,6 +554,319 @@ impl ProvideCredential for S3ExpressSessionProvider {
     }
 }
 
+const DEFAULT_SESSION_CACHE_CAPACITY: usize = 256;
+// Refresh cached sessions slightly before S3 stops accepting them.
+const SESSION_REFRESH_WINDOW: Duration = Duration::from_secs(30);
+
+/// Signs requests to many S3 Express directory buckets with one [`reqsign_core::Signer`].
+///
+/// The manager is a [`SignRequest`] for the source AWS credential. At signing
+/// time it infers the directory bucket, Zone ID, Region, and partition from the
+/// request authority, which must be a virtual-hosted Zonal endpoint such as
+/// `example--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com`. The
+/// inferred values pass the same validation as [`S3ExpressSessionConfig::new`],
+/// and the authority must equal the endpoint that configuration derives.
+///
+/// Session credentials from `CreateSession` are cached per bucket, session
+/// mode, and source access key. They are reused until they come within thirty
+/// seconds of expiring. The cache holds at most 256 sessions by default and
+/// evicts the least recently used one. Concurrent requests that miss the cache
+/// for the same session wait for a single `CreateSession` call and share its
+/// result.
+///
+/// Supports the signing time, signed headers, and `expires_in`
+/// [`SigningOverrides`]. The session scope is fixed by the endpoint, so the
+/// region and service cannot be overridden.
+///
+/// # Example
+///
+/// ```no_run
+/// use reqsign_aws_v4::{
+///     DefaultCredentialProvider, S3ExpressSessionGrant, S3ExpressSessionManager,
+///     S3ExpressSessionMode,
+/// };
+/// use reqsign_core::{Context, Signer};
+///
+/// # async fn example() -> reqsign_core::Result<()> {
+/// let signer = Signer::new(
+///     Context::new(),
+///     DefaultCredentialProvider::new(),
+///     S3ExpressSessionManager::new(S3ExpressSessionGrant::new(S3ExpressSessionMode::ReadOnly)),
+/// );
+/// let mut parts = http::Request::get(
+///     "https://example--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/object",
+/// )
+/// .body(())
+/// .expect("request must build")
+/// .into_parts()
+/// .0;
+/// signer.sign(&mut parts, None).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct S3ExpressSessionManager {
+    grant: S3ExpressSessionGrant,
+    capacity: usize,
+    sessions: Arc<Mutex<VecDeque<CachedSession>>>,
+    create_locks: CreateLockRegistry,
+    #[cfg(test)]
+    time: Option<Timestamp>,
+}
+
+impl Debug for S3ExpressSessionManager {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3ExpressSessionManager")
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct SessionKey {
+    endpoint: String,
+    mode: S3ExpressSessionMode,
+    source_access_key_id: String,
+}
+
+struct CachedSession {
+    key: SessionKey,
+    credential: Credential,
+}
+
+type CreateLockRegistry = Arc<Mutex<Vec<(SessionKey, Weak<AsyncMutex<()>>)>>>;
+
+/// A shared `CreateSession` lock for one session key, unregistered when the last holder drops it.
+struct CreateLockLease {
+    key: SessionKey,
+    lock: Arc<AsyncMutex<()>>,
+    registry: CreateLockRegistry,
+}
+
+impl Drop for CreateLockLease {
+    fn drop(&mut self) {
+        let mut registry = self
+            .registry
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if Arc::strong_count(&self.lock) != 1 {
+            return;
+        }
+        registry.retain(|(candidate, weak)| {
+            candidate != &self.key
+                || weak
+                    .upgrade()
+                    .is_none_or(|lock| !Arc::ptr_eq(&lock, &self.lock))
+        });
+    }
+}
+
+impl S3ExpressSessionManager {
+    /// Create a manager that requests sessions with the given grant.
+    pub fn new(grant: S3ExpressSessionGrant) -> Self {
+        Self {
+            grant,
+            capacity: DEFAULT_SESSION_CACHE_CAPACITY,
+            sessions: Arc::new(Mutex::new(VecDeque::new())),
+            create_locks: Arc::default(),
+            #[cfg(test)]
+            time: None,
+        }
+    }
+
+    /// Set the maximum number of cached bucket sessions.
+    ///
+    /// A capacity of zero is treated as one.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    #[cfg(test)]
+    fn with_time(mut self, time: Timestamp) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    fn now(&self) -> Timestamp {
+        #[cfg(test)]
+        if let Some(time) = self.time {
+            return time;
+        }
+        Timestamp::now()
+    }
+
+    /// Derive and validate the directory bucket configuration from a request authority.
+    fn config_from_request(&self, req: &Parts) -> Result<S3ExpressSessionConfig> {
+        let not_zonal =
+            || Error::request_invalid("request authority is not an S3 Express Zonal endpoint");
+        if req.uri.scheme_str() != Some("https") {
+            return Err(Error::request_invalid(
+                "S3 Express Zonal endpoint requests must use HTTPS",
+            ));
+        }
+        let authority = req.uri.authority().ok_or_else(not_zonal)?.as_str();
+        let (partition, labels) = [
+            S3ExpressSessionPartition::Aws,
+            S3ExpressSessionPartition::AwsCn,
+        ]
+        .into_iter()
+        .find_map(|partition| {
+            let labels = authority.strip_suffix(partition.dns_suffix())?;
+            Some((partition, labels.strip_suffix('.')?))
+        })
+        .ok_or_else(not_zonal)?;
+        let [bucket, zonal, region] = labels.split('.').collect::<Vec<_>>()[..] else {
+            return Err(not_zonal());
+        };
+        let zone_id = zonal.strip_prefix("s3express-").ok_or_else(not_zonal)?;
+
+        let config = S3ExpressSessionConfig::new(bucket, zone_id, region, partition)?;
+        if config.endpoint.strip_prefix("https://") != Some(authority) {
+            return Err(not_zonal());
+        }
+        Ok(config)
+    }
+
+    fn session_key(&self, config: &S3ExpressSessionConfig, source: &Credential) -> SessionKey {
+        SessionKey {
+            endpoint: config.endpoint.clone(),
+            mode: self.grant.mode,
+            source_access_key_id: source.access_key_id.clone(),
+        }
+    }
+
+    fn cached_session(&self, key: &SessionKey, required_until: Timestamp) -> Option<Credential> {
+        let mut sessions = self.sessions.lock().expect("lock poisoned");
+        let index = sessions.iter().position(|session| session.key == *key)?;
+        let session = sessions.remove(index)?;
+        if !session.credential.is_valid_at(required_until) {
+            return None;
+        }
+        let credential = session.credential.clone();
+        sessions.push_back(session);
+        Some(credential)
+    }
+
+    fn cache_session(&self, key: SessionKey, credential: Credential) {
+        let mut sessions = self.sessions.lock().expect("lock poisoned");
+        sessions.retain(|session| session.key != key);
+        sessions.push_back(CachedSession { key, credential });
+        while sessions.len() > self.capacity {
+            sessions.pop_front();
+        }
+    }
+
+    fn create_lock(&self, key: &SessionKey) -> CreateLockLease {
+        let mut create_locks = self
+            .create_locks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let lock = create_locks
+            .iter()
+            .find_map(|(candidate, lock)| (candidate == key).then(|| lock.upgrade()).flatten())
+            .unwrap_or_else(|| {
+                let lock = Arc::new(AsyncMutex::new(()));
+                create_locks.push((key.clone(), Arc::downgrade(&lock)));
+                lock
+            });
+        CreateLockLease {
+            key: key.clone(),
+            lock,
+            registry: self.create_locks.clone(),
+        }
+    }
+
+    async fn create_session(
+        &self,
+        ctx: &Context,
+        config: &S3ExpressSessionConfig,
+        source: &Credential,
+        key: SessionKey,
+    ) -> Result<Credential> {
+        let granter = S3ExpressSessionGranter::new(config.clone(), self.grant.clone());
+        #[cfg(test)]
+        let granter = if let Some(time) = self.time {
+            granter.with_time(time)
+        } else {
+            granter
+        };
+        granter.validate_request(source, None)?;
+        let session = granter.create_session(ctx, source).await?;
+        self.cache_session(key, session.clone());
+        Ok(session)
+    }
+
+    fn session_signer(&self, region: &str) -> crate::RequestSigner {
+        let signer = crate::RequestSigner::new("s3express", region);
+        #[cfg(test)]
+        let signer = if let Some(time) = self.time {
+            signer.with_time(time)
+        } else {
+            signer
+        };
+        signer
+    }
+}
+
+impl SignRequest for S3ExpressSessionManager {
+    type Credential = Credential;
+
+    fn required_valid_until(
+        &self,
+        _credential: &Self::Credential,
+        _expires_in: Option<Duration>,
+    ) -> Timestamp {
+        self.now() + CREATE_SESSION_OPERATION_HEADROOM
+    }
+
+    async fn sign_request(
+        &self,
+        ctx: &Context,
+        req: &mut Parts,
+        credential: Option<&Self::Credential>,
+        expires_in: Option<Duration>,
+    ) -> Result<()> {
+        let Some(source) = credential else {
+            return Ok(());
+        };
+        // Sessions are scoped to the bucket's zone, so the signing scope is fixed.
+        let (expires_in, _) = SigningOverrides::resolve(
+            req,
+            &[
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::SignedHeaders,
+                SigningOverrideField::ExpiresIn,
+            ],
+            expires_in,
+            || self.now(),
+        )?;
+        // Validate the endpoint before any session is created or reused.
+        let config = self.config_from_request(req)?;
+        let signer = self.session_signer(&config.region);
+        let required_until =
+            signer.required_valid_until_for(req, source, expires_in) + SESSION_REFRESH_WINDOW;
+
+        let key = self.session_key(&config, source);
+        let session = match self.cached_session(&key, required_until) {
+            Some(session) => session,
+            None => {
+                // Let one caller create the session; the others reuse its result.
+                let create_lock = self.create_lock(&key);
+                let _create_guard = create_lock.lock.lock().await;
+                match self.cached_session(&key, required_until) {
+                    Some(session) => session,
+                    None => self.create_session(ctx, &config, source, key).await?,
+                }
+            }
+        };
+
+        signer
+            .sign_request(ctx, req, Some(&session), expires_in)
+            .await
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename = "CreateSessionResult", rename_all = "PascalCase")]
 struct CreateSessionResponse {
@@ -858,6 +1175,8 @@ mod tests {
     impl HttpSend for MockHttpSend {
         async fn http_send(&self, request: Request<Bytes>) -> Result<Response<Bytes>> {
             self.calls.fetch_add(1, Ordering::SeqCst);
+            // Suspend like a real network call so concurrent callers interleave.
+            tokio::task::yield_now().await;
             let (parts, body) = request.into_parts();
             self.requests
                 .lock()
@@ -1633,4 +1952,189 @@ mod tests {
         assert!(!parts.headers.contains_key("x-amz-security-token"));
         assert!(parts.headers.contains_key(header::AUTHORIZATION));
     }
+
+    fn zonal_parts(uri: &str) -> Parts {
+        Request::get(uri).body(()).unwrap().into_parts().0
+    }
+
+    fn session_manager(mode: S3ExpressSessionMode) -> S3ExpressSessionManager {
+        S3ExpressSessionManager::new(S3ExpressSessionGrant::new(mode))
+            .with_time(timestamp("2030-01-01T00:00:00Z"))
+    }
+
+    #[tokio::test]
+    async fn session_manager_caches_sessions_per_bucket() {
+        let http = MockHttpSend::new([
+            success_response(
+                "first-key",
+                "first-secret",
+                "first-token",
+                "2099-01-01T00:05:00Z",
+            ),
+            success_response(
+                "second-key",
+                "second-secret",
+                "second-token",
+                "2099-01-01T00:05:00Z",
+            ),
+        ]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let (source_provider, _) = FixedCredentialProvider::new(source_credential());
+        let signer = Signer::new(
+            ctx,
+            source_provider,
+            session_manager(S3ExpressSessionMode::ReadOnly),
+        );
+
+        let first_uri =
+            "https://first--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/a";
+        let second_uri =
+            "https://second--cnn1-az1--x-s3.s3express-cnn1-az1.cn-north-1.amazonaws.com.cn/b";
+        let mut first = zonal_parts(first_uri);
+        signer.sign(&mut first, None).await.expect("first sign");
+        let mut again = zonal_parts(first_uri);
+        signer.sign(&mut again, None).await.expect("cached sign");
+        let mut second = zonal_parts(second_uri);
+        signer.sign(&mut second, None).await.expect("second sign");
+
+        assert_eq!(http.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            http.request(0).uri,
+            "https://first--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/?session"
+        );
+        assert_eq!(
+            http.request(0).headers["x-amz-create-session-mode"],
+            "ReadOnly"
+        );
+        assert_eq!(
+            http.request(1).uri,
+            "https://second--cnn1-az1--x-s3.s3express-cnn1-az1.cn-north-1.amazonaws.com.cn/?session"
+        );
+        for (parts, token, scope) in [
+            (
+                &first,
+                "first-token",
+                "first-key/20300101/us-west-2/s3express",
+            ),
+            (
+                &again,
+                "first-token",
+                "first-key/20300101/us-west-2/s3express",
+            ),
+            (
+                &second,
+                "second-token",
+                "second-key/20300101/cn-north-1/s3express",
+            ),
+        ] {
+            assert_eq!(parts.headers["x-amz-s3session-token"], token);
+            assert!(!parts.headers.contains_key("x-amz-security-token"));
+            assert!(
+                parts.headers[header::AUTHORIZATION]
+                    .to_str()
+                    .unwrap()
+                    .contains(scope)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn session_manager_creates_one_session_for_concurrent_misses() {
+        let http = MockHttpSend::new([success_response(
+            "shared-key",
+            "shared-secret",
+            "shared-token",
+            "2099-01-01T00:05:00Z",
+        )]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let manager = session_manager(S3ExpressSessionMode::ReadOnly);
+        let uri = "https://a--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/o";
+        let source = source_credential();
+        let mut first = zonal_parts(uri);
+        let mut second = zonal_parts(uri);
+
+        let (first_result, second_result) = futures::join!(
+            manager.sign_request(&ctx, &mut first, Some(&source), None),
+            manager.sign_request(&ctx, &mut second, Some(&source), None),
+        );
+        first_result.expect("first sign must succeed");
+        second_result.expect("second sign must succeed");
+
+        assert_eq!(http.calls.load(Ordering::SeqCst), 1);
+        for parts in [&first, &second] {
+            assert_eq!(parts.headers["x-amz-s3session-token"], "shared-token");
+        }
+        assert!(manager.create_locks.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn session_manager_evicts_least_recently_used_and_refreshes_near_expiry() {
+        let response =
+            |key: &str, expiration: &str| success_response(key, "secret", "token", expiration);
+        let http = MockHttpSend::new([
+            response("a-1", "2099-01-01T00:05:00Z"),
+            response("b-1", "2099-01-01T00:05:00Z"),
+            response("a-2", "2030-01-01T00:00:20Z"),
+            response("a-3", "2099-01-01T00:05:00Z"),
+        ]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let manager = session_manager(S3ExpressSessionMode::ReadWrite).with_capacity(1);
+        let bucket_a = "https://a--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/o";
+        let bucket_b = "https://b--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/o";
+
+        let mut signed_keys = Vec::new();
+        for uri in [bucket_a, bucket_b, bucket_a, bucket_a] {
+            let mut parts = zonal_parts(uri);
+            manager
+                .sign_request(&ctx, &mut parts, Some(&source_credential()), None)
+                .await
+                .expect("signing must succeed");
+            let authorization = parts.headers[header::AUTHORIZATION].to_str().unwrap();
+            signed_keys.push(
+                authorization
+                    .split("Credential=")
+                    .nth(1)
+                    .and_then(|value| value.split('/').next())
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+
+        assert_eq!(signed_keys, ["a-1", "b-1", "a-2", "a-3"]);
+        assert_eq!(http.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn session_manager_rejects_non_zonal_authorities_before_io() {
+        let http = MockHttpSend::new([]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let manager = session_manager(S3ExpressSessionMode::ReadOnly);
+
+        for uri in [
+            "http://a--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/o",
+            "https://a--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com:8443/o",
+            "https://s3express-usw2-az1.us-west-2.amazonaws.com/a--usw2-az1--x-s3/o",
+            "https://s3express-control.us-west-2.amazonaws.com/",
+            "https://a--usw2-az1--x-s3.s3express-usw2-az2.us-west-2.amazonaws.com/o",
+            "https://a--usw2-az1--x-s3.s3express-usw2-az1.us-east-1.amazonaws.com/o",
+            "https://a--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com.cn/o",
+            "https://a--usgw1-az1--x-s3.s3express-usgw1-az1.us-gov-west-1.amazonaws.com/o",
+            "https://a--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.example.com/o",
+            "https://user@a--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com/o",
+        ] {
+            let mut parts = zonal_parts(uri);
+            let before = parts.headers.clone();
+            let error = manager
+                .sign_request(&ctx, &mut parts, Some(&source_credential()), None)
+                .await
+                .expect_err("non-Zonal authority must be rejected");
+            assert!(matches!(
+                error.kind(),
+                ErrorKind::RequestInvalid | ErrorKind::ConfigInvalid
+            ));
+            assert_eq!(parts.uri, uri);
+            assert_eq!(parts.headers, before);
+        }
+        assert_eq!(http.calls.load(Ordering::SeqCst), 0);
+    }
 }
@@ -15,8 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::VecDeque;
 use std::fmt::{self, Debug, Formatter};
 use std::net::Ipv4Addr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use bytes::Bytes;
@@ -31,12 +33,17 @@ use serde::Deserialize;
 use crate::{Credential, EMPTY_STRING_SHA256, RequestSigner};
 
 const GET_DATA_ACCESS_PATH: &str = "/v20180820/accessgrantsinstance/dataaccess";
+const GET_INSTANCE_FOR_PREFIX_PATH: &str = "/v20180820/accessgrantsinstance/prefix";
 const GET_DATA_ACCESS_HEADROOM: Duration = Duration::from_secs(10);
 const MIN_DURATION_SECONDS: u64 = 900;
 const MAX_DURATION_SECONDS: u64 = 43_200;
 const MAX_TARGET_CHARACTERS: usize = 2_000;
 const MAX_OBJECT_KEY_BYTES: usize = 1_024;
 const MAX_AUDIT_CONTEXT_CHARACTERS: usize = 2_048;
+const MAX_CACHED_GRANTS: usize = 1_000;
+const MAX_CACHED_BUCKET_OWNERS: usize = 1_000;
+const DEFAULT_CACHE_MIN_REMAINING: Duration = Duration::from_secs(300);
+const DEFAULT_BUCKET_OWNER_TTL: Duration = Duration::from_secs(3_600);
 
 const X_AMZ_ACCOUNT_ID: &str = "x-amz-account-id";
 const X_AMZ_CHECKSUM_CRC64NVME: &str = "x-amz-checksum-crc64nvme";
@@ -128,6 +135,7 @@ impl S3AccessGrantsConfig {
         }
 
         Ok(ValidatedConfig {
+            account_id: self.account_id.clone(),
             authority: authority.as_str().to_string(),
         })
     }
@@ -179,6 +187,7 @@ fn aws_dns_suffix(region: &str) -> &'static str {
 }
 
 struct ValidatedConfig {
+    account_id: String,
     authority: String,
 }
 
@@ -238,15 +247,24 @@ impl S3AccessGrantsTarget {
     fn validate(&self, account_id: &str, region: &str) -> Result<ValidatedTarget> {
         validate_bucket_name(&self.bucket, account_id, region)?;
 
-        let (value, target_type) = match &self.kind {
-            S3AccessGrantsTargetKind::Bucket => (format!("s3://{}", self.bucket), None),
+        let (value, target_type, scope) = match &self.kind {
+            S3AccessGrantsTargetKind::Bucket => (
+                format!("s3://{}", self.bucket),
+                None,
+                GrantScope::Prefix(format!("s3://{}/", self.bucket)),
+            ),
             S3AccessGrantsTargetKind::Prefix(prefix) => {
                 validate_object_key_component(prefix, "prefix", false)?;
-                (format!("s3://{}/{prefix}*", self.bucket), None)
+                (
+                    format!("s3://{}/{prefix}*", self.bucket),
+                    None,
+                    GrantScope::Prefix(format!("s3://{}/{prefix}", self.bucket)),
+                )
             }
             S3AccessGrantsTargetKind::Object(object_key) => {
                 validate_object_key_component(object_key, "object key", true)?;
-                (format!("s3://{}/{object_key}", self.bucket), Some("Object"))
+                let value = format!("s3://{}/{object_key}", self.bucket);
+                (value.clone(), Some("Object"), GrantScope::Exact(value))
             }
         };
         if value.chars().count() > MAX_TARGET_CHARACTERS {
@@ -255,7 +273,11 @@ impl S3AccessGrantsTarget {
             ));
         }
 
-        Ok(ValidatedTarget { value, target_type })
+        Ok(ValidatedTarget {
+            value,
+            target_type,
+            scope,
+        })
     }
 }
 
@@ -345,6 +367,44 @@ fn validate_object_key_component(value: &str, kind: &str, allow_wildcard: bool)
 struct ValidatedTarget {
     value: String,
     target_type: Option<&'static str>,
+    scope: GrantScope,
+}
+
+/// The set of S3 locations a granted credential is scoped to.
+#[derive(Clone, PartialEq, Eq)]
+enum GrantScope {
+    /// Every location starting with this `s3://` prefix.
+    Prefix(String),
+    /// One exact object location.
+    Exact(String),
+}
+
+impl GrantScope {
+    /// Parse a `MatchedGrantTarget` such as `s3://bucket/prefix/*`.
+    fn from_matched_grant_target(target: &str) -> Option<Self> {
+        let location = target.strip_prefix("s3://")?;
+        if location.is_empty() {
+            return None;
+        }
+        if let Some(prefix) = target.strip_suffix('*') {
+            return Some(Self::Prefix(prefix.to_string()));
+        }
+        if location.contains('/') {
+            Some(Self::Exact(target.to_string()))
+        } else {
+            Some(Self::Prefix(format!("{target}/")))
+        }
+    }
+
+    fn covers(&self, requested: &GrantScope) -> bool {
+        match (self, requested) {
+            (Self::Prefix(scope), Self::Prefix(requested) | Self::Exact(requested)) => {
+                requested.starts_with(scope.as_str())
+            }
+            (Self::Exact(scope), Self::Exact(requested)) => scope == requested,
+            (Self::Exact(_), Self::Prefix(_)) => false,
+        }
+    }
 }
 
 /// The permission requested from S3 Access Grants.
@@ -475,6 +535,162 @@ struct ValidatedGrant {
     audit_context: Option<String>,
 }
 
+/// A shared cache of S3 Access Grants results and resolved bucket owners.
+///
+/// Attach one cache to any number of granters with
+/// [`S3AccessGrantsGranter::with_cache`]. A later grant whose target lies inside
+/// an already granted scope reuses the cached temporary credential instead of
+/// calling `GetDataAccess` again. With [`S3AccessGrantsPrivilege::Default`] the
+/// cached scope is the `MatchedGrantTarget` returned by AWS; with
+/// [`S3AccessGrantsPrivilege::Minimal`] it is the requested target, because the
+/// issued credential is restricted to it.
+///
+/// Entries are only shared between grants for the same instance, source access
+/// key, permission, privilege, explicit lifetime, and audit context. A cached
+/// credential is reused while it stays valid for the minimum remaining lifetime,
+/// five minutes by default. A resolved bucket owner is reused for one hour by
+/// default. Both tables are bounded and evict the least recently used entry.
+#[derive(Clone)]
+pub struct S3AccessGrantsCache {
+    state: Arc<Mutex<CacheState>>,
+    min_remaining: Duration,
+    bucket_owner_ttl: Duration,
+}
+
+impl Debug for S3AccessGrantsCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3AccessGrantsCache")
+            .field("min_remaining", &self.min_remaining)
+            .field("bucket_owner_ttl", &self.bucket_owner_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for S3AccessGrantsCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl S3AccessGrantsCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CacheState::default())),
+            min_remaining: DEFAULT_CACHE_MIN_REMAINING,
+            bucket_owner_ttl: DEFAULT_BUCKET_OWNER_TTL,
+        }
+    }
+
+    /// Set how long a cached credential must remain valid to be reused.
+    ///
+    /// Values shorter than the signing headroom of ten seconds are raised to it.
+    pub fn with_min_remaining(mut self, min_remaining: Duration) -> Self {
+        self.min_remaining = min_remaining.max(GET_DATA_ACCESS_HEADROOM);
+        self
+    }
+
+    /// Set how long a resolved bucket owner is reused before it is looked up again.
+    pub fn with_bucket_owner_ttl(mut self, ttl: Duration) -> Self {
+        self.bucket_owner_ttl = ttl;
+        self
+    }
+
+    fn get_grant(
+        &self,
+        key: &GrantCacheKey,
+        requested: &GrantScope,
+        now: Timestamp,
+    ) -> Option<Credential> {
+        let mut state = self.state.lock().expect("lock poisoned");
+        state
+            .grants
+            .retain(|entry| entry.credential.is_valid_at(now + GET_DATA_ACCESS_HEADROOM));
+        let index = state.grants.iter().position(|entry| {
+            entry.key == *key
+                && entry.scope.covers(requested)
+                && entry.credential.is_valid_at(now + self.min_remaining)
+        })?;
+        let entry = state.grants.remove(index)?;
+        let credential = entry.credential.clone();
+        state.grants.push_back(entry);
+        Some(credential)
+    }
+
+    fn insert_grant(&self, key: GrantCacheKey, scope: GrantScope, credential: Credential) {
+        let mut state = self.state.lock().expect("lock poisoned");
+        state
+            .grants
+            .retain(|entry| entry.key != key || entry.scope != scope);
+        state.grants.push_back(CachedGrant {
+            key,
+            scope,
+            credential,
+        });
+        while state.grants.len() > MAX_CACHED_GRANTS {
+            state.grants.pop_front();
+        }
+    }
+
+    fn get_bucket_owner(&self, requester: &str, bucket: &str, now: Timestamp) -> Option<String> {
+        let mut state = self.state.lock().expect("lock poisoned");
+        state.owners.retain(|entry| entry.expires_at > now);
+        let index = state
+            .owners
+            .iter()
+            .position(|entry| entry.requester == requester && entry.bucket == bucket)?;
+        let entry = state.owners.remove(index)?;
+        let account_id = entry.account_id.clone();
+        state.owners.push_back(entry);
+        Some(account_id)
+    }
+
+    fn insert_bucket_owner(&self, requester: &str, bucket: &str, account_id: &str, now: Timestamp) {
+        let mut state = self.state.lock().expect("lock poisoned");
+        state
+            .owners
+            .retain(|entry| entry.requester != requester || entry.bucket != bucket);
+        state.owners.push_back(CachedBucketOwner {
+            requester: requester.to_string(),
+            bucket: bucket.to_string(),
+            account_id: account_id.to_string(),
+            expires_at: now + self.bucket_owner_ttl,
+        });
+        while state.owners.len() > MAX_CACHED_BUCKET_OWNERS {
+            state.owners.pop_front();
+        }
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    grants: VecDeque<CachedGrant>,
+    owners: VecDeque<CachedBucketOwner>,
+}
+
+#[derive(PartialEq, Eq)]
+struct GrantCacheKey {
+    authority: String,
+    source_access_key_id: String,
+    permission: &'static str,
+    privilege: &'static str,
+    duration_seconds: Option<u32>,
+    audit_context: Option<String>,
+}
+
+struct CachedGrant {
+    key: GrantCacheKey,
+    scope: GrantScope,
+    credential: Credential,
+}
+
+struct CachedBucketOwner {
+    requester: String,
+    bucket: String,
+    account_id: String,
+    expires_at: Timestamp,
+}
+
 /// Grants temporary AWS credentials through S3 Access Grants `GetDataAccess`.
 ///
 /// The granter binds one stable S3 Access Grants instance configuration and one
@@ -488,6 +704,11 @@ struct ValidatedGrant {
 /// inclusive range from 15 minutes to 12 hours. The returned credential always
 /// uses the authoritative absolute `Expiration` from the AWS response.
 ///
+/// Results are not cached unless an [`S3AccessGrantsCache`] is attached with
+/// [`Self::with_cache`]. When the bucket owner is not known up front, configure
+/// the requesting account instead and enable
+/// [`Self::with_bucket_owner_resolution`].
+///
 /// # Example
 ///
 /// ```no_run
@@ -523,6 +744,8 @@ struct ValidatedGrant {
 pub struct S3AccessGrantsGranter {
     config: S3AccessGrantsConfig,
     grant: S3AccessGrantsGrant,
+    cache: Option<S3AccessGrantsCache>,
+    resolve_bucket_owner: bool,
     #[cfg(test)]
     time: Option<Timestamp>,
     #[cfg(test)]
@@ -542,6 +765,8 @@ impl S3AccessGrantsGranter {
         Self {
             config,
             grant,
+            cache: None,
+            resolve_bucket_owner: false,
             #[cfg(test)]
             time: None,
             #[cfg(test)]
@@ -555,6 +780,24 @@ impl S3AccessGrantsGranter {
         self
     }
 
+    /// Reuse granted credentials and resolved bucket owners through a shared cache.
+    pub fn with_cache(mut self, cache: S3AccessGrantsCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Resolve the S3 Access Grants instance that owns the target bucket.
+    ///
+    /// The configured account becomes the requesting account. Before calling
+    /// `GetDataAccess`, the granter calls `GetAccessGrantsInstanceForPrefix`
+    /// in the configured Region to find the account that owns the instance
+    /// registered for the target bucket, then sends `GetDataAccess` to that
+    /// account's default endpoint. Resolution requires the default endpoint.
+    pub fn with_bucket_owner_resolution(mut self) -> Self {
+        self.resolve_bucket_owner = true;
+        self
+    }
+
     fn now(&self) -> Timestamp {
         #[cfg(test)]
         if let Some(time) = self.time {
@@ -660,22 +903,20 @@ impl S3AccessGrantsGranter {
             .method(Method::GET)
             .uri(uri)
             .header(HOST, &config.authority)
-            .header(X_AMZ_ACCOUNT_ID, &self.config.account_id)
+            .header(X_AMZ_ACCOUNT_ID, &config.account_id)
             .header(X_AMZ_CONTENT_SHA_256, EMPTY_STRING_SHA256)
             .header(X_AMZ_CHECKSUM_CRC64NVME, EMPTY_CRC64NVME_BASE64)
             .body(Bytes::new())
             .map_err(|_| Error::request_invalid("failed to build S3 Access Grants request"))
     }
 
-    async fn get_data_access(
+    async fn send_signed(
         &self,
         ctx: &Context,
         credential: &Credential,
-        config: &ValidatedConfig,
-        grant: ValidatedGrant,
-        duration_seconds: Option<u32>,
-    ) -> Result<Credential> {
-        let request = self.build_request(config, grant, duration_seconds)?;
+        request: Request<Bytes>,
+        operation: &'static str,
+    ) -> Result<Bytes> {
         let (mut parts, body) = request.into_parts();
         let signer =
             RequestSigner::new("s3", &self.config.region).with_standard_session_token_header();
@@ -693,31 +934,96 @@ impl S3AccessGrantsGranter {
         let response = ctx.http_send(request).await.map_err(|err| {
             Error::new(
                 err.kind(),
-                "failed to send S3 Access Grants GetDataAccess request",
+                format!("failed to send S3 Access Grants {operation} request"),
             )
-            .with_context("operation: GetDataAccess")
+            .with_context(format!("operation: {operation}"))
             .set_retryable(err.is_retryable())
         })?;
         let status = response.status();
         let request_id = response_request_id(response.headers());
         let body = response.into_body();
         if status != StatusCode::OK {
-            return Err(parse_get_data_access_error(
+            return Err(parse_access_grants_error(
+                operation,
                 status,
                 &body,
                 request_id.as_deref(),
             ));
         }
+        Ok(body)
+    }
+
+    async fn get_data_access(
+        &self,
+        ctx: &Context,
+        credential: &Credential,
+        config: &ValidatedConfig,
+        grant: ValidatedGrant,
+        duration_seconds: Option<u32>,
+    ) -> Result<GetDataAccessOutput> {
+        let request = self.build_request(config, grant, duration_seconds)?;
+        let body = self
+            .send_signed(ctx, credential, request, "GetDataAccess")
+            .await?;
 
-        let credential = parse_get_data_access_result(&body)?;
+        let output = parse_get_data_access_result(&body)?;
         let validated_at = self.now_after_request();
-        if !credential.is_valid_at(validated_at + GET_DATA_ACCESS_HEADROOM) {
+        if !output
+            .credential
+            .is_valid_at(validated_at + GET_DATA_ACCESS_HEADROOM)
+        {
             return Err(Error::credential_invalid(
                 "S3 Access Grants returned a credential that cannot satisfy the next signing operation",
             )
             .with_context("operation: GetDataAccess"));
         }
-        Ok(credential)
+        Ok(output)
+    }
+
+    /// Find the account that owns the S3 Access Grants instance for the target bucket.
+    async fn resolve_bucket_owner(
+        &self,
+        ctx: &Context,
+        credential: &Credential,
+    ) -> Result<S3AccessGrantsConfig> {
+        if self.config.endpoint.is_some() {
+            return Err(Error::config_invalid(
+                "S3 Access Grants bucket owner resolution requires the default endpoint",
+            ));
+        }
+        let requester = self.config.validate()?;
+        let bucket = &self.grant.target.bucket;
+        // Account regional bucket names embed the owning account, which is the
+        // only account their name is valid for.
+        let name_account = account_regional_bucket_owner(bucket, &self.config.region)
+            .unwrap_or(&requester.account_id);
+        validate_bucket_name(bucket, name_account, &self.config.region)?;
+
+        let cached = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get_bucket_owner(&requester.authority, bucket, self.now()));
+        let account_id = match cached {
+            Some(account_id) => account_id,
+            None => {
+                let request = build_instance_for_prefix_request(&requester, bucket)?;
+                let body = self
+                    .send_signed(ctx, credential, request, "GetAccessGrantsInstanceForPrefix")
+                    .await?;
+                let account_id = parse_instance_for_prefix_result(&body, &self.config.region)?;
+                if let Some(cache) = &self.cache {
+                    cache.insert_bucket_owner(
+                        &requester.authority,
+                        bucket,
+                        &account_id,
+                        self.now_after_request(),
+                    );
+                }
+                account_id
+            }
+        };
+
+        Ok(S3AccessGrantsConfig::new(account_id, &self.config.region))
     }
 }
 
@@ -740,19 +1046,133 @@ impl GrantCredential for S3AccessGrantsGranter {
     ) -> Result<Self::Credential> {
         let required_until = self.required_valid_until(credential, expires_in);
         self.validate_source(credential, required_until)?;
-        let config = self.config.validate()?;
-        let grant = self.grant.validate(&self.config)?;
         let duration_seconds = Self::duration_seconds(expires_in)?;
+        let instance = if self.resolve_bucket_owner {
+            self.resolve_bucket_owner(ctx, credential).await?
+        } else {
+            self.config.clone()
+        };
+        let config = instance.validate()?;
+        let grant = self.grant.validate(&instance)?;
 
-        self.get_data_access(ctx, credential, &config, grant, duration_seconds)
-            .await
+        let Some(cache) = &self.cache else {
+            return self
+                .get_data_access(ctx, credential, &config, grant, duration_seconds)
+                .await
+                .map(|output| output.credential);
+        };
+        let key = GrantCacheKey {
+            authority: config.authority.clone(),
+            source_access_key_id: credential.access_key_id.clone(),
+            permission: grant.permission,
+            privilege: grant.privilege,
+            duration_seconds,
+            audit_context: grant.audit_context.clone(),
+        };
+        let requested = grant.target.scope.clone();
+        if let Some(credential) = cache.get_grant(&key, &requested, self.now()) {
+            return Ok(credential);
+        }
+
+        let output = self
+            .get_data_access(ctx, credential, &config, grant, duration_seconds)
+            .await?;
+        // Minimal privilege restricts the credential to the requested target
+        // even when a broader grant matched.
+        let scope = match (self.grant.privilege, output.matched_grant_target.as_deref()) {
+            (S3AccessGrantsPrivilege::Default, Some(matched)) => {
+                GrantScope::from_matched_grant_target(matched)
+                    .filter(|scope| scope.covers(&requested))
+                    .unwrap_or(requested)
+            }
+            _ => requested,
+        };
+        cache.insert_grant(key, scope, output.credential.clone());
+        Ok(output.credential)
+    }
+}
+
+fn account_regional_bucket_owner<'a>(bucket: &'a str, region: &str) -> Option<&'a str> {
+    let name = bucket.strip_suffix("-an")?.strip_suffix(region)?;
+    let name = name.strip_suffix('-')?;
+    let account_id = name.get(name.len().checked_sub(12)?..)?;
+    let separated = name[..name.len() - 12].ends_with('-');
+    (separated && account_id.bytes().all(|byte| byte.is_ascii_digit())).then_some(account_id)
+}
+
+fn build_instance_for_prefix_request(
+    requester: &ValidatedConfig,
+    bucket: &str,
+) -> Result<Request<Bytes>> {
+    let base_uri: Uri = format!(
+        "https://{}{}",
+        requester.authority, GET_INSTANCE_FOR_PREFIX_PATH
+    )
+    .parse()
+    .map_err(|_| Error::request_invalid("failed to build S3 Access Grants request endpoint"))?;
+    let query = [("s3prefix".to_string(), format!("s3://{bucket}"))];
+    let uri = append_query_pairs(&base_uri, &query)
+        .map_err(|_| Error::request_invalid("failed to encode S3 Access Grants request query"))?;
+
+    Request::builder()
+        .method(Method::GET)
+        .uri(uri)
+        .header(HOST, &requester.authority)
+        .header(X_AMZ_ACCOUNT_ID, &requester.account_id)
+        .header(X_AMZ_CONTENT_SHA_256, EMPTY_STRING_SHA256)
+        .body(Bytes::new())
+        .map_err(|_| Error::request_invalid("failed to build S3 Access Grants request"))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetAccessGrantsInstanceForPrefixResult {
+    access_grants_instance_arn: Option<String>,
+}
+
+/// Extract the owning account from `AccessGrantsInstanceArn`.
+fn parse_instance_for_prefix_result(body: &[u8], region: &str) -> Result<String> {
+    let parse_error = || {
+        Error::unexpected("failed to parse S3 Access Grants instance response")
+            .with_context("operation: GetAccessGrantsInstanceForPrefix")
+    };
+    let body = std::str::from_utf8(body).map_err(|_| parse_error())?;
+    let result: GetAccessGrantsInstanceForPrefixResult =
+        quick_xml::de::from_str(body).map_err(|_| parse_error())?;
+    let arn = result.access_grants_instance_arn.ok_or_else(parse_error)?;
+
+    // arn:<partition>:s3:<region>:<account>:access-grants/<instance>
+    let fields: Vec<&str> = arn.trim().splitn(6, ':').collect();
+    let [
+        prefix,
+        _partition,
+        service,
+        arn_region,
+        account_id,
+        resource,
+    ] = fields[..]
+    else {
+        return Err(parse_error());
+    };
+    if prefix != "arn"
+        || service != "s3"
+        || arn_region != region
+        || !resource.starts_with("access-grants/")
+        || validate_account_id(account_id).is_err()
+    {
+        return Err(
+            Error::unexpected("S3 Access Grants returned an invalid instance ARN")
+                .with_context("operation: GetAccessGrantsInstanceForPrefix"),
+        );
     }
+    Ok(account_id.to_string())
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
 struct GetDataAccessResult {
     credentials: GetDataAccessCredentials,
+    matched_grant_target: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -764,7 +1184,12 @@ struct GetDataAccessCredentials {
     expiration: Option<String>,
 }
 
-fn parse_get_data_access_result(body: &[u8]) -> Result<Credential> {
+struct GetDataAccessOutput {
+    credential: Credential,
+    matched_grant_target: Option<String>,
+}
+
+fn parse_get_data_access_result(body: &[u8]) -> Result<GetDataAccessOutput> {
     let body = std::str::from_utf8(body).map_err(|_| {
         Error::unexpected("failed to parse S3 Access Grants response")
             .with_context("operation: GetDataAccess")
@@ -793,11 +1218,14 @@ fn parse_get_data_access_result(body: &[u8]) -> Result<Credential> {
             .with_context("operation: GetDataAccess")
     })?;
 
-    Ok(Credential {
-        access_key_id,
-        secret_access_key,
-        session_token: Some(session_token),
-        expires_in: Some(expiration),
+    Ok(GetDataAccessOutput {
+        credential: Credential {
+            access_key_id,
+            secret_access_key,
+            session_token: Some(session_token),
+            expires_in: Some(expiration),
+        },
+        matched_grant_target: result.matched_grant_target,
     })
 }
 
@@ -835,7 +1263,8 @@ fn sanitize_aws_identifier(value: &str) -> Option<String> {
     Some(value.to_string())
 }
 
-fn parse_get_data_access_error(
+fn parse_access_grants_error(
+    operation: &str,
     status: StatusCode,
     body: &[u8],
     header_request_id: Option<&str>,
@@ -889,7 +1318,7 @@ fn parse_get_data_access_error(
             | "UnauthorizedAccess"
             | "NoSuchAccessGrantError"
             | "StsNotAuthorizedError",
-        ) => Error::permission_denied("S3 Access Grants GetDataAccess was denied"),
+        ) => Error::permission_denied(format!("S3 Access Grants {operation} was denied")),
         Some(
             "ExpiredToken"
             | "InvalidAccessKeyId"
@@ -906,7 +1335,7 @@ fn parse_get_data_access_error(
             | "InvalidAccessGrant"
             | "StsPackedPolicyTooLargeError"
             | "StsValidationError",
-        ) => Error::request_invalid("S3 Access Grants rejected the GetDataAccess request"),
+        ) => Error::request_invalid(format!("S3 Access Grants rejected the {operation} request")),
         Some(
             "AccessGrantsInstanceNotExistsError"
             | "NoSuchAccessGrantsInstance"
@@ -925,7 +1354,7 @@ fn parse_get_data_access_error(
                 Error::credential_invalid("AWS source credential was rejected by S3 Access Grants")
             }
             StatusCode::FORBIDDEN => {
-                Error::permission_denied("S3 Access Grants GetDataAccess was denied")
+                Error::permission_denied(format!("S3 Access Grants {operation} was denied"))
             }
             StatusCode::NOT_FOUND => {
                 Error::config_invalid("S3 Access Grants instance was not found")
@@ -934,16 +1363,16 @@ fn parse_get_data_access_error(
                 Error::rate_limited("S3 Access Grants rate limit exceeded")
             }
             status if status.is_client_error() => {
-                Error::request_invalid("S3 Access Grants rejected the GetDataAccess request")
+                Error::request_invalid(format!("S3 Access Grants rejected the {operation} request"))
             }
             status if status.is_server_error() => {
                 Error::unexpected("S3 Access Grants service error").set_retryable(true)
             }
-            _ => Error::unexpected("S3 Access Grants GetDataAccess failed"),
+            _ => Error::unexpected(format!("S3 Access Grants {operation} failed")),
         },
     };
     error = error
-        .with_context("operation: GetDataAccess")
+        .with_context(format!("operation: {operation}"))
         .with_context(format!("http_status: {status}"));
     if let Some(code) = recognized_code.and_then(sanitize_aws_identifier) {
         error = error.with_context(format!("error_code: {code}"));
@@ -1722,7 +2151,7 @@ mod tests {
   <Message>target s3://sensitive-bucket/private/* uses SECRET-TOKEN</Message>
   <RequestId>request-123</RequestId>
 </Error>"#;
-        let error = parse_get_data_access_error(StatusCode::FORBIDDEN, body, None);
+        let error = parse_access_grants_error("GetDataAccess", StatusCode::FORBIDDEN, body, None);
         assert_eq!(error.kind(), ErrorKind::PermissionDenied);
         assert!(!error.is_retryable());
         assert!(
@@ -1742,7 +2171,8 @@ mod tests {
         assert!(!debug.contains("SECRET-TOKEN"));
         assert!(!debug.contains("<Error>"));
 
-        let throttled = parse_get_data_access_error(
+        let throttled = parse_access_grants_error(
+            "GetDataAccess",
             StatusCode::BAD_REQUEST,
             br#"<Error><Code>SlowDown</Code><Message>secret</Message></Error>"#,
             Some("header-request-id"),
@@ -1750,7 +2180,8 @@ mod tests {
         assert_eq!(throttled.kind(), ErrorKind::RateLimited);
         assert!(throttled.is_retryable());
 
-        let unavailable = parse_get_data_access_error(
+        let unavailable = parse_access_grants_error(
+            "GetDataAccess",
             StatusCode::SERVICE_UNAVAILABLE,
             b"raw target and credential response",
             None,
@@ -1782,7 +2213,7 @@ mod tests {
             ),
         ] {
             let body = format!("<Error><Code>{code}</Code><Message>secret</Message></Error>");
-            let error = parse_get_data_access_error(status, body.as_bytes(), None);
+            let error = parse_access_grants_error("GetDataAccess", status, body.as_bytes(), None);
             assert_eq!(error.kind(), kind);
             assert!(
                 error
@@ -1793,7 +2224,8 @@ mod tests {
             assert!(!format!("{error:?}").contains("secret"));
         }
 
-        let unknown = parse_get_data_access_error(
+        let unknown = parse_access_grants_error(
+            "GetDataAccess",
             StatusCode::BAD_REQUEST,
             b"<Error><Code>AKIASECRETSHAPEDVALUE</Code></Error>",
             None,
@@ -1984,6 +2416,7 @@ mod tests {
                 "<GetDataAccessResult><Credentials>{credentials}</Credentials></GetDataAccessResult>"
             );
             let error = parse_get_data_access_result(body.as_bytes())
+                .map(|output| output.credential)
                 .expect_err("incomplete temporary credential must fail");
             assert_eq!(error.kind(), ErrorKind::CredentialInvalid);
             let debug = format!("{error:?}");
@@ -2113,6 +2546,385 @@ mod tests {
         assert_eq!(http.requests().len(), 2);
     }
 
+    fn success_response_with_target(
+        access_key_id: &str,
+        expiration: &str,
+        matched_grant_target: &str,
+    ) -> Response<Bytes> {
+        response(
+            StatusCode::OK,
+            format!(
+                r#"<GetDataAccessResult>
+  <Credentials>
+    <AccessKeyId>{access_key_id}</AccessKeyId>
+    <SecretAccessKey>granted-secret</SecretAccessKey>
+    <SessionToken>granted-token</SessionToken>
+    <Expiration>{expiration}</Expiration>
+  </Credentials>
+  <MatchedGrantTarget>{matched_grant_target}</MatchedGrantTarget>
+</GetDataAccessResult>"#
+            ),
+        )
+    }
+
+    fn instance_for_prefix_response(arn: &str) -> Response<Bytes> {
+        response(
+            StatusCode::OK,
+            format!(
+                r#"<GetAccessGrantsInstanceForPrefixResult>
+  <AccessGrantsInstanceArn>{arn}</AccessGrantsInstanceArn>
+  <AccessGrantsInstanceId>default</AccessGrantsInstanceId>
+</GetAccessGrantsInstanceForPrefixResult>"#
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn cache_reuses_default_privilege_credential_under_matched_grant_target() {
+        let http = MockHttpSend::new([
+            success_response_with_target(
+                "ASIAREPORTS",
+                RESPONSE_EXPIRATION,
+                "s3://example-bucket/reports/*",
+            ),
+            success_response_with_target(
+                "ASIAOTHER",
+                RESPONSE_EXPIRATION,
+                "s3://example-bucket/other/*",
+            ),
+        ]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let cache = S3AccessGrantsCache::new();
+        let default_read = |target| {
+            S3AccessGrantsGrant::new(
+                target,
+                S3AccessGrantsPermission::Read,
+                S3AccessGrantsPrivilege::Default,
+            )
+        };
+        let granter = operation(default_read(S3AccessGrantsTarget::for_prefix(
+            "example-bucket",
+            "reports/2024/",
+        )))
+        .with_cache(cache.clone());
+
+        let first = granter
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("first grant must succeed");
+        let second = granter
+            .clone()
+            .with_grant(default_read(S3AccessGrantsTarget::for_object(
+                "example-bucket",
+                "reports/2025/summary.csv",
+            )))
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("covered grant must be served from the cache");
+        let third = granter
+            .clone()
+            .with_grant(default_read(S3AccessGrantsTarget::for_prefix(
+                "example-bucket",
+                "other/",
+            )))
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("uncovered grant must call GetDataAccess");
+
+        assert_eq!(first.access_key_id, "ASIAREPORTS");
+        assert_eq!(second.access_key_id, "ASIAREPORTS");
+        assert_eq!(third.access_key_id, "ASIAOTHER");
+        assert_eq!(http.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn cache_scopes_minimal_privilege_to_requested_target() {
+        let http = MockHttpSend::new([
+            success_response_with_target("ASIAFIRST", RESPONSE_EXPIRATION, "s3://example-bucket/*"),
+            success_response_with_target(
+                "ASIASECOND",
+                RESPONSE_EXPIRATION,
+                "s3://example-bucket/*",
+            ),
+        ]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let granter = operation(minimal_read_grant(S3AccessGrantsTarget::for_prefix(
+            "example-bucket",
+            "customer/",
+        )))
+        .with_cache(S3AccessGrantsCache::new());
+
+        granter
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("first grant must succeed");
+        let covered = granter
+            .clone()
+            .with_grant(minimal_read_grant(S3AccessGrantsTarget::for_object(
+                "example-bucket",
+                "customer/file.txt",
+            )))
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("covered grant must succeed");
+        let sibling = granter
+            .clone()
+            .with_grant(minimal_read_grant(S3AccessGrantsTarget::for_object(
+                "example-bucket",
+                "other/file.txt",
+            )))
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("sibling grant must succeed");
+
+        assert_eq!(covered.access_key_id, "ASIAFIRST");
+        assert_eq!(sibling.access_key_id, "ASIASECOND");
+        assert_eq!(http.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn cache_separates_grant_dimensions_and_refreshes_near_expiry() {
+        let http = MockHttpSend::new([
+            success_response_with_target("ASIAREAD", RESPONSE_EXPIRATION, "s3://example-bucket/*"),
+            success_response_with_target("ASIAWRITE", RESPONSE_EXPIRATION, "s3://example-bucket/*"),
+            success_response_with_target(
+                "ASIASHORT",
+                "2099-01-01T00:04:00Z",
+                "s3://example-bucket/*",
+            ),
+            success_response_with_target(
+                "ASIAREFRESHED",
+                RESPONSE_EXPIRATION,
+                "s3://example-bucket/*",
+            ),
+        ]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let cache = S3AccessGrantsCache::new();
+        let grant = |permission| {
+            S3AccessGrantsGrant::new(
+                S3AccessGrantsTarget::for_bucket("example-bucket"),
+                permission,
+                S3AccessGrantsPrivilege::Default,
+            )
+        };
+
+        let read = operation(grant(S3AccessGrantsPermission::Read))
+            .with_cache(cache.clone())
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("read grant must succeed");
+        let write = operation(grant(S3AccessGrantsPermission::Write))
+            .with_cache(cache.clone())
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("write grant must succeed");
+        let short = operation(grant(S3AccessGrantsPermission::ReadWrite))
+            .with_cache(cache.clone())
+            .grant_credential(&ctx, &source_credential(), Some(Duration::from_secs(900)))
+            .await
+            .expect("short grant must succeed");
+        let refreshed = operation(grant(S3AccessGrantsPermission::ReadWrite))
+            .with_cache(cache.clone())
+            .grant_credential(&ctx, &source_credential(), Some(Duration::from_secs(900)))
+            .await
+            .expect("near-expiry grant must be refreshed");
+        let reused = operation(grant(S3AccessGrantsPermission::Read))
+            .with_cache(cache)
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("read grant must be reused");
+
+        assert_eq!(read.access_key_id, "ASIAREAD");
+        assert_eq!(write.access_key_id, "ASIAWRITE");
+        assert_eq!(short.access_key_id, "ASIASHORT");
+        assert_eq!(refreshed.access_key_id, "ASIAREFRESHED");
+        assert_eq!(reused.access_key_id, "ASIAREAD");
+        assert_eq!(http.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn resolves_bucket_owner_before_get_data_access() {
+        let http = MockHttpSend::new([
+            instance_for_prefix_response("arn:aws:s3:us-east-2:444455556666:access-grants/default"),
+            success_response_with_target("ASIAOWNER", RESPONSE_EXPIRATION, "s3://shared-bucket/*"),
+            success_response_with_target("ASIAOWNER2", RESPONSE_EXPIRATION, "s3://shared-bucket/*"),
+        ]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let cache = S3AccessGrantsCache::new();
+        let granter = operation(minimal_read_grant(S3AccessGrantsTarget::for_prefix(
+            "shared-bucket",
+            "a/",
+        )))
+        .with_bucket_owner_resolution()
+        .with_cache(cache);
+
+        let credential = granter
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("resolved grant must succeed");
+        granter
+            .clone()
+            .with_grant(minimal_read_grant(S3AccessGrantsTarget::for_prefix(
+                "shared-bucket",
+                "b/",
+            )))
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("second grant must reuse the resolved owner");
+
+        assert_eq!(credential.access_key_id, "ASIAOWNER");
+        let requests = http.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[0].uri,
+            concat!(
+                "https://111122223333.s3-control.us-east-2.amazonaws.com",
+                "/v20180820/accessgrantsinstance/prefix",
+                "?s3prefix=s3%3A%2F%2Fshared-bucket"
+            )
+            .parse::<Uri>()
+            .expect("expected URI must parse")
+        );
+        assert_eq!(requests[0].headers[X_AMZ_ACCOUNT_ID], ACCOUNT_ID);
+        assert!(
+            requests[0].headers[AUTHORIZATION]
+                .to_str()
+                .expect("authorization must be text")
+                .contains("/20990101/us-east-2/s3/aws4_request")
+        );
+        for request in &requests[1..] {
+            assert_eq!(
+                request.uri.host(),
+                Some("444455556666.s3-control.us-east-2.amazonaws.com")
+            );
+            assert_eq!(request.headers[X_AMZ_ACCOUNT_ID], "444455556666");
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_bucket_owner_again_after_ttl() {
+        let http = MockHttpSend::new([
+            instance_for_prefix_response("arn:aws:s3:us-east-2:444455556666:access-grants/default"),
+            success_response_with_target("ASIAOWNER", RESPONSE_EXPIRATION, "s3://shared-bucket/*"),
+            instance_for_prefix_response("arn:aws:s3:us-east-2:777788889999:access-grants/default"),
+            success_response_with_target("ASIAMOVED", RESPONSE_EXPIRATION, "s3://shared-bucket/*"),
+        ]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let cache = S3AccessGrantsCache::new().with_bucket_owner_ttl(Duration::from_secs(60));
+        let granter = operation(minimal_read_grant(S3AccessGrantsTarget::for_prefix(
+            "shared-bucket",
+            "a/",
+        )))
+        .with_bucket_owner_resolution()
+        .with_cache(cache);
+
+        granter
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("resolved grant must succeed");
+        let credential = granter
+            .with_grant(minimal_read_grant(S3AccessGrantsTarget::for_prefix(
+                "shared-bucket",
+                "b/",
+            )))
+            .with_time(timestamp("2099-01-01T00:01:01Z"))
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect("expired owner must be resolved again");
+
+        assert_eq!(credential.access_key_id, "ASIAMOVED");
+        let requests = http.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(
+            requests[2].uri.path(),
+            "/v20180820/accessgrantsinstance/prefix"
+        );
+        assert_eq!(requests[3].headers[X_AMZ_ACCOUNT_ID], "777788889999");
+    }
+
+    #[tokio::test]
+    async fn bucket_owner_resolution_rejects_untrusted_results() {
+        for arn in [
+            "arn:aws:s3:us-west-2:444455556666:access-grants/default",
+            "arn:aws:iam::444455556666:role/default",
+            "arn:aws:s3:us-east-2:4444:access-grants/default",
+            "not-an-arn",
+        ] {
+            let http = MockHttpSend::new([instance_for_prefix_response(arn)]);
+            let ctx = Context::new().with_http_send(http.clone());
+            let error = operation(minimal_read_grant(S3AccessGrantsTarget::for_bucket(
+                "shared-bucket",
+            )))
+            .with_bucket_owner_resolution()
+            .grant_credential(&ctx, &source_credential(), None)
+            .await
+            .expect_err("invalid instance ARN must fail");
+            assert_eq!(error.kind(), ErrorKind::Unexpected);
+            assert_eq!(http.requests().len(), 1);
+        }
+
+        let http = MockHttpSend::new([response(
+            StatusCode::NOT_FOUND,
+            "<Error><Code>AccessGrantsInstanceNotExistsError</Code></Error>",
+        )]);
+        let ctx = Context::new().with_http_send(http);
+        let error = operation(minimal_read_grant(S3AccessGrantsTarget::for_bucket(
+            "shared-bucket",
+        )))
+        .with_bucket_owner_resolution()
+        .grant_credential(&ctx, &source_credential(), None)
+        .await
+        .expect_err("missing instance must fail");
+        assert_eq!(error.kind(), ErrorKind::ConfigInvalid);
+        assert!(
+            error
+                .context()
+                .iter()
+                .any(|value| value == "operation: GetAccessGrantsInstanceForPrefix")
+        );
+
+        let http = MockHttpSend::new([]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let error = S3AccessGrantsGranter::new(
+            config().with_trusted_endpoint("https://111122223333.s3-control.example.com"),
+            minimal_read_grant(S3AccessGrantsTarget::for_bucket("shared-bucket")),
+        )
+        .with_bucket_owner_resolution()
+        .grant_credential(&ctx, &source_credential(), None)
+        .await
+        .expect_err("custom endpoint cannot be resolved");
+        assert_eq!(error.kind(), ErrorKind::ConfigInvalid);
+        assert!(http.requests().is_empty());
+    }
+
+    #[test]
+    fn grant_scopes_cover_only_nested_locations() {
+        let scope = |target| GrantScope::from_matched_grant_target(target).expect("scope");
+        let prefix = GrantScope::Prefix("s3://bucket/data/".to_string());
+        let object = GrantScope::Exact("s3://bucket/data/file".to_string());
+
+        assert!(scope("s3://bucket/data/*").covers(&object));
+        assert!(scope("s3://bucket/*").covers(&prefix));
+        assert!(scope("s3://bucket").covers(&prefix));
+        assert!(scope("s3://*").covers(&prefix));
+        assert!(scope("s3://bucket/data/file").covers(&object));
+        assert!(!scope("s3://bucket/data/file").covers(&prefix));
+        assert!(!scope("s3://bucket/other/*").covers(&object));
+        assert!(!scope("s3://bucket-2/*").covers(&prefix));
+        assert!(GrantScope::from_matched_grant_target("https://bucket/*").is_none());
+        assert!(GrantScope::from_matched_grant_target("s3://").is_none());
+
+        assert_eq!(
+            account_regional_bucket_owner("data-444455556666-us-east-2-an", REGION),
+            Some("444455556666")
+        );
+        assert_eq!(
+            account_regional_bucket_owner("data444455556666-us-east-2-an", REGION),
+            None
+        );
+        assert_eq!(account_regional_bucket_owner("shared-bucket", REGION), None);
+    }
+
     #[test]
     fn response_request_id_accepts_only_bounded_safe_identifiers() {
         let mut headers = HeaderMap::new();
@@ -20,12 +20,16 @@ use http::request::Parts;
 use http::{HeaderValue, header};
 use log::debug;
 use reqsign_aws_core::signing::{
-    append_query_fragment, append_query_pairs, canonical_request_string, canonicalize_headers,
-    canonicalize_headers_with_standard_session_token, canonicalize_query,
+    append_query_fragment, append_query_pairs, canonical_request_string_with_signed_headers,
+    canonicalize_headers, canonicalize_headers_with_standard_session_token, canonicalize_query,
+    signed_header_names,
 };
 use reqsign_core::hash::{hex_hmac_sha256, hex_sha256, hmac_sha256};
 use reqsign_core::time::Timestamp;
-use reqsign_core::{Context, Result, SignRequest, SigningCredential, SigningRequest};
+use reqsign_core::{
+    Context, Result, SignRequest, SigningCredential, SigningOverrideField, SigningOverrides,
+    SigningRequest,
+};
 use std::fmt::Write;
 use std::time::Duration;
 
@@ -33,6 +37,9 @@ const CREDENTIAL_OPERATION_HEADROOM: Duration = Duration::from_secs(10);
 
 /// RequestSigner that implement AWS SigV4.
 ///
+/// Supports every [`SigningOverrides`] field. `host` and `x-amz-*` headers
+/// are always signed.
+///
 /// - [Signature Version 4 signing process](https://docs.aws.amazon.com/general/latest/gr/signature-version-4.html)
 #[derive(Debug)]
 pub struct RequestSigner {
@@ -96,6 +103,16 @@ impl SignRequest for RequestSigner {
         self.required_valid_until_at(self.get_time(), expires_in)
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &Parts,
+        _credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        let now = SigningOverrides::signing_time_or(req, || self.get_time());
+        self.required_valid_until_at(now, expires_in)
+    }
+
     async fn sign_request(
         &self,
         _: &Context,
@@ -107,7 +124,18 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let now = self.get_time();
+        let (expires_in, now) = SigningOverrides::resolve(
+            req,
+            &[
+                SigningOverrideField::Region,
+                SigningOverrideField::Service,
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::SignedHeaders,
+                SigningOverrideField::ExpiresIn,
+            ],
+            expires_in,
+            || self.get_time(),
+        )?;
         let required_until = self.required_valid_until_at(now, expires_in);
         if !cred.is_valid_at(required_until) {
             return Err(reqsign_core::Error::credential_invalid(
@@ -117,6 +145,13 @@ impl SignRequest for RequestSigner {
 
         let original_uri = req.uri.clone();
         let mut signed_req = SigningRequest::build(req)?;
+        let overrides = SigningOverrides::from_request(req);
+        let service = overrides
+            .and_then(SigningOverrides::service)
+            .unwrap_or(&self.service);
+        let region = overrides
+            .and_then(SigningOverrides::region)
+            .unwrap_or(&self.region);
 
         // canonicalize context
         if self.force_standard_session_token {
@@ -129,27 +164,21 @@ impl SignRequest for RequestSigner {
         } else {
             canonicalize_headers(&mut signed_req, cred, expires_in, now)?;
         }
-        let authentication_query = authentication_query(
-            &signed_req,
-            cred,
-            expires_in,
-            now,
-            &self.service,
-            &self.region,
-        );
+        let signed_headers = signed_header_names(&signed_req, overrides).join(";");
+        let authentication_query =
+            authentication_query(&signed_headers, cred, expires_in, now, service, region);
         let canonical_query = canonicalize_query(&signed_req, &authentication_query);
 
         // build canonical request and string to sign.
-        let creq = canonical_request_string(&signed_req, &canonical_query)?;
+        let creq = canonical_request_string_with_signed_headers(
+            &signed_req,
+            &canonical_query,
+            &signed_header_names(&signed_req, overrides),
+        )?;
         let encoded_req = hex_sha256(creq.as_bytes());
 
         // Scope: "20220313/<region>/<service>/aws4_request"
-        let scope = format!(
-            "{}/{}/{}/aws4_request",
-            now.format_date(),
-            self.region,
-            self.service
-        );
+        let scope = format!("{}/{}/{}/aws4_request", now.format_date(), region, service);
         debug!("calculated scope: {scope}");
 
         // StringToSign:
@@ -176,8 +205,7 @@ impl SignRequest for RequestSigner {
         };
         debug!("calculated string to sign: {string_to_sign}");
 
-        let signing_key =
-            generate_signing_key(&cred.secret_access_key, now, &self.region, &self.service);
+        let signing_key = generate_signing_key(&cred.secret_access_key, now, region, service);
         let signature = hex_hmac_sha256(&signing_key, string_to_sign.as_bytes());
 
         let final_uri = if expires_in.is_some() {
@@ -189,10 +217,7 @@ impl SignRequest for RequestSigner {
         } else {
             let mut authorization = HeaderValue::from_str(&format!(
                 "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
-                cred.access_key_id,
-                scope,
-                signed_req.header_name_to_vec_sorted().join(";"),
-                signature
+                cred.access_key_id, scope, signed_headers, signature
             ))
             .map_err(|e| {
                 reqsign_core::Error::unexpected(format!(
@@ -217,7 +242,7 @@ impl SignRequest for RequestSigner {
 }
 
 fn authentication_query(
-    ctx: &SigningRequest,
+    signed_headers: &str,
     cred: &Credential,
     expires_in: Option<Duration>,
     now: Timestamp,
@@ -239,10 +264,7 @@ fn authentication_query(
         ));
         query.push(("X-Amz-Date".into(), now.format_iso8601()));
         query.push(("X-Amz-Expires".into(), expire.as_secs().to_string()));
-        query.push((
-            "X-Amz-SignedHeaders".into(),
-            ctx.header_name_to_vec_sorted().join(";"),
-        ));
+        query.push(("X-Amz-SignedHeaders".into(), signed_headers.to_string()));
 
         if let Some(token) = &cred.session_token {
             query.push(("X-Amz-Security-Token".into(), token.into()));
@@ -324,6 +346,19 @@ mod tests {
             signer.required_valid_until(&credential, None),
             now + CREDENTIAL_OPERATION_HEADROOM
         );
+
+        let later = now + Duration::from_secs(7200);
+        let mut req = Request::get("https://example.com/")
+            .body(())
+            .expect("request must build")
+            .into_parts()
+            .0;
+        req.extensions
+            .insert(SigningOverrides::new().with_signing_time(later));
+        assert_eq!(
+            signer.required_valid_until_for(&req, &credential, Some(Duration::from_secs(3600))),
+            later + Duration::from_secs(3600)
+        );
     }
 
     #[tokio::test]
@@ -368,6 +403,89 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn signing_overrides_replace_signer_configuration() -> Result<()> {
+        let now: Timestamp = "2026-07-22T00:00:00Z".parse()?;
+        let credential = Credential {
+            access_key_id: "access-key".to_string(),
+            secret_access_key: "secret-key".to_string(),
+            session_token: None,
+            expires_in: None,
+        };
+        let uri = "https://api.example.com/prod/items?b=2&a=1";
+
+        let mut expected = Request::get(uri).body(())?.into_parts().0;
+        RequestSigner::new("execute-api", "eu-west-1")
+            .with_time(now)
+            .sign_request(
+                &Context::new(),
+                &mut expected,
+                Some(&credential),
+                Some(Duration::from_secs(300)),
+            )
+            .await?;
+
+        let mut actual = Request::get(uri).body(())?.into_parts().0;
+        actual.extensions.insert(
+            SigningOverrides::new()
+                .with_region("eu-west-1")
+                .with_service("execute-api")
+                .with_signing_time(now)
+                .with_expires_in(Duration::from_secs(300)),
+        );
+        RequestSigner::new("s3", "us-east-1")
+            .sign_request(&Context::new(), &mut actual, Some(&credential), None)
+            .await?;
+
+        assert_eq!(actual.uri, expected.uri);
+        assert_eq!(actual.headers, expected.headers);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn signed_header_override_excludes_unlisted_headers() -> Result<()> {
+        let now: Timestamp = "2026-07-22T00:00:00Z".parse()?;
+        let credential = Credential {
+            access_key_id: "access-key".to_string(),
+            secret_access_key: "secret-key".to_string(),
+            session_token: None,
+            expires_in: None,
+        };
+        let request = |custom: bool| -> Result<Parts> {
+            let mut builder = Request::put("https://bucket.s3.us-east-1.amazonaws.com/key")
+                .header(header::CONTENT_TYPE, "text/plain")
+                .header("x-amz-meta-owner", "team");
+            if custom {
+                builder = builder.header("x-proxy-trace", "abc");
+            }
+            Ok(builder.body(())?.into_parts().0)
+        };
+        let signer = RequestSigner::new("s3", "us-east-1").with_time(now);
+
+        let mut expected = request(false)?;
+        signer
+            .sign_request(&Context::new(), &mut expected, Some(&credential), None)
+            .await?;
+        let mut actual = request(true)?;
+        actual
+            .extensions
+            .insert(SigningOverrides::new().with_signed_headers([header::CONTENT_TYPE]));
+        signer
+            .sign_request(&Context::new(), &mut actual, Some(&credential), None)
+            .await?;
+
+        let authorization = actual.headers[header::AUTHORIZATION].to_str()?;
+        assert!(authorization.contains(
+            "SignedHeaders=content-type;host;x-amz-content-sha256;x-amz-date;x-amz-meta-owner,"
+        ));
+        assert_eq!(
+            actual.headers[header::AUTHORIZATION],
+            expected.headers[header::AUTHORIZATION]
+        );
+        assert_eq!(actual.headers["x-proxy-trace"], "abc");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn presign_refreshes_credential_that_cannot_cover_url_lifetime() -> Result<()> {
         let now = Timestamp::now();
@@ -673,7 +791,7 @@ mod tests {
             now,
         )?;
         let auth_query = authentication_query(
-            &signing_req,
+            &signing_req.header_name_to_vec_sorted().join(";"),
             &credential,
             Some(Duration::from_secs(60)),
             now,
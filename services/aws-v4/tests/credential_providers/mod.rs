@@ -20,6 +20,7 @@ mod assume_role_with_web_identity;
 mod cognito;
 mod default;
 mod ecs;
+mod eks_pod_identity;
 mod env;
 mod imds;
 #[cfg(not(target_arch = "wasm32"))]
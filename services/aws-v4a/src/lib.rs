@@ -22,13 +22,21 @@ mod sign_request;
 
 pub use region_set::SigningRegionSet;
 pub use reqsign_aws_core::constants;
+pub use reqsign_aws_core::endpoint;
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqsign_aws_core::shared_config;
 pub use reqsign_aws_core::{
-    AssumeRoleCredentialProvider, AssumeRoleWithWebIdentityCredentialProvider,
-    CognitoIdentityCredentialProvider, Credential, DefaultCredentialProvider,
-    DefaultCredentialProviderBuilder, ECSCredentialProvider, EMPTY_STRING_SHA256,
-    EnvCredentialProvider, IMDSv2CredentialProvider, ProfileCredentialProvider,
+    AssumeRoleCredentialProvider, AssumeRoleWithSAMLCredentialProvider,
+    AssumeRoleWithWebIdentityCredentialProvider, CognitoIdentityCredentialProvider, Credential,
+    DefaultCredentialProvider, DefaultCredentialProviderBuilder, ECSCredentialProvider,
+    EKSPodIdentityCredentialProvider, EMPTY_STRING_SHA256, Endpoint, EndpointConfig,
+    EnvCredentialProvider, GetFederationTokenCredentialProvider, GetSessionTokenCredentialProvider,
+    IMDSEndpointMode, IMDSv2CredentialProvider, ProfileCredentialProvider,
     StaticCredentialProvider,
 };
 #[cfg(not(target_arch = "wasm32"))]
-pub use reqsign_aws_core::{ProcessCredentialProvider, SSOCredentialProvider};
+pub use reqsign_aws_core::{
+    LoginCredentialProvider, ProcessCredentialProvider, ProfileFiles, ProfileSet,
+    RolesAnywhereCredentialProvider, SSOCredentialProvider,
+};
 pub use sign_request::RequestSigner;
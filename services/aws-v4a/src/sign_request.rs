@@ -25,12 +25,15 @@ use p256::ecdsa::signature::Signer as _;
 use p256::ecdsa::{DerSignature, SigningKey};
 use reqsign_aws_core::Credential;
 use reqsign_aws_core::signing::{
-    append_query_fragment, append_query_pairs, canonical_request_string, canonicalize_headers,
-    canonicalize_query,
+    append_query_fragment, append_query_pairs, canonical_request_string_with_signed_headers,
+    canonicalize_headers, canonicalize_query, signed_header_names,
 };
 use reqsign_core::hash::{hex_sha256, hmac_sha256};
 use reqsign_core::time::Timestamp;
-use reqsign_core::{Context, Error, Result, SignRequest, SigningCredential, SigningRequest};
+use reqsign_core::{
+    Context, Error, Result, SignRequest, SigningCredential, SigningOverrideField, SigningOverrides,
+    SigningRequest,
+};
 use zeroize::Zeroizing;
 
 use crate::SigningRegionSet;
@@ -41,6 +44,9 @@ const CREDENTIAL_OPERATION_HEADROOM: Duration = Duration::from_secs(10);
 
 /// Request signer implementing
 /// [AWS Signature Version 4A](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_sigv-create-signed-request.html).
+///
+/// Supports every [`SigningOverrides`] field. The region override is parsed
+/// as a comma-separated region set.
 #[derive(Debug)]
 pub struct RequestSigner {
     service: String,
@@ -92,6 +98,16 @@ impl SignRequest for RequestSigner {
         self.required_valid_until_at(self.get_time(), expires_in)
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &Parts,
+        _credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        let now = SigningOverrides::signing_time_or(req, || self.get_time());
+        self.required_valid_until_at(now, expires_in)
+    }
+
     async fn sign_request(
         &self,
         _: &Context,
@@ -103,7 +119,23 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let now = self.get_time();
+        let (expires_in, now) = SigningOverrides::resolve(
+            req,
+            &[
+                SigningOverrideField::Region,
+                SigningOverrideField::Service,
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::SignedHeaders,
+                SigningOverrideField::ExpiresIn,
+            ],
+            expires_in,
+            || self.get_time(),
+        )?;
+        let region_set =
+            match SigningOverrides::from_request(req).and_then(SigningOverrides::region) {
+                Some(region) => SigningRegionSet::new(region)?,
+                None => self.region_set.clone(),
+            };
         let required_until = self.required_valid_until_at(now, expires_in);
         if !credential.is_valid_at(required_until) {
             return Err(Error::credential_invalid(
@@ -113,29 +145,38 @@ impl SignRequest for RequestSigner {
 
         let original_uri = req.uri.clone();
         let mut signing_request = SigningRequest::build(req)?;
+        let overrides = SigningOverrides::from_request(req);
+        let service = overrides
+            .and_then(SigningOverrides::service)
+            .unwrap_or(&self.service);
         canonicalize_headers(&mut signing_request, credential, expires_in, now)?;
 
         if expires_in.is_none() {
-            let region_set = HeaderValue::from_str(self.region_set.as_str()).map_err(|e| {
+            let region_set = HeaderValue::from_str(region_set.as_str()).map_err(|e| {
                 Error::request_invalid("AWS signing region set is not a valid header value")
                     .with_source(e)
             })?;
             signing_request.headers.insert(X_AMZ_REGION_SET, region_set);
         }
 
+        let signed_headers = signed_header_names(&signing_request, overrides).join(";");
         let authentication_query = authentication_query(
-            &signing_request,
+            &signed_headers,
             credential,
             expires_in,
             now,
-            &self.service,
-            &self.region_set,
+            service,
+            &region_set,
         );
         let canonical_query = canonicalize_query(&signing_request, &authentication_query);
-        let canonical_request = canonical_request_string(&signing_request, &canonical_query)?;
+        let canonical_request = canonical_request_string_with_signed_headers(
+            &signing_request,
+            &canonical_query,
+            &signed_header_names(&signing_request, overrides),
+        )?;
         let encoded_request = hex_sha256(canonical_request.as_bytes());
 
-        let scope = format!("{}/{}/aws4_request", now.format_date(), self.service);
+        let scope = format!("{}/{}/aws4_request", now.format_date(), service);
         debug!("calculated scope: {scope}");
 
         let string_to_sign = string_to_sign(now, &scope, &encoded_request)?;
@@ -154,8 +195,7 @@ impl SignRequest for RequestSigner {
         } else {
             let mut authorization = HeaderValue::from_str(&format!(
                 "{ALGORITHM} Credential={}/{scope}, SignedHeaders={}, Signature={signature}",
-                credential.access_key_id,
-                signing_request.header_name_to_vec_sorted().join(";"),
+                credential.access_key_id, signed_headers,
             ))
             .map_err(|e| {
                 Error::unexpected(format!("failed to create authorization header: {e}"))
@@ -176,7 +216,7 @@ impl SignRequest for RequestSigner {
 }
 
 fn authentication_query(
-    request: &SigningRequest,
+    signed_headers: &str,
     credential: &Credential,
     expires_in: Option<Duration>,
     now: Timestamp,
@@ -209,7 +249,7 @@ fn authentication_query(
         ),
         (
             "X-Amz-SignedHeaders".to_string(),
-            request.header_name_to_vec_sorted().join(";"),
+            signed_headers.to_string(),
         ),
     ];
 
@@ -534,6 +574,58 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn signing_overrides_replace_signer_configuration() -> AnyResult<()> {
+        let uri = "https://mrap.accesspoint.s3-global.amazonaws.com/key";
+
+        let mut expected = Request::get(uri).body(())?.into_parts().0;
+        RequestSigner::new("s3", SigningRegionSet::new("us-east-1,us-west-2")?)
+            .with_time("2015-08-30T12:36:00Z".parse()?)
+            .sign_request(
+                &Context::new(),
+                &mut expected,
+                Some(&credential()),
+                Some(Duration::from_secs(300)),
+            )
+            .await?;
+
+        let mut actual = Request::get(uri).body(())?.into_parts().0;
+        actual.extensions.insert(
+            SigningOverrides::new()
+                .with_region("us-east-1,us-west-2")
+                .with_service("s3")
+                .with_signing_time("2015-08-30T12:36:00Z".parse()?)
+                .with_expires_in(Duration::from_secs(300)),
+        );
+        RequestSigner::new("service", SigningRegionSet::new("*")?)
+            .sign_request(&Context::new(), &mut actual, Some(&credential()), None)
+            .await?;
+
+        assert_eq!(actual.uri, expected.uri);
+        assert_eq!(actual.headers, expected.headers);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_region_override_does_not_mutate_request() -> AnyResult<()> {
+        let mut parts = Request::get("https://example.amazonaws.com/")
+            .body(())?
+            .into_parts()
+            .0;
+        parts
+            .extensions
+            .insert(SigningOverrides::new().with_region(""));
+        let original_headers = parts.headers.clone();
+
+        signer()
+            .sign_request(&Context::new(), &mut parts, Some(&credential()), None)
+            .await
+            .expect_err("empty region set must be rejected");
+
+        assert_eq!(parts.headers, original_headers);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn error_does_not_mutate_request() -> AnyResult<()> {
         let mut parts = Request::get("https://example.amazonaws.com/")
@@ -0,0 +1,739 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::Credential;
+use crate::service_sas::{
+    STORAGE_ACCOUNT_KEY_LENGTH, ServiceSasConfig, encode_query_pairs, expiry_at_wire_second,
+};
+use crate::user_delegation::ceil_to_wire_second;
+use reqsign_core::hash;
+use reqsign_core::time::Timestamp;
+use reqsign_core::{Context, Error, GrantCredential, Result, SigningCredential};
+use std::fmt::{Debug, Formatter};
+use std::ops::{BitOr, BitOrAssign};
+use std::time::Duration;
+
+/// Storage services reachable with an Azure account SAS.
+///
+/// Combining values with `|` produces Azure's canonical service order.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountSasServices(u8);
+
+impl AccountSasServices {
+    /// Blob Storage.
+    pub const BLOB: Self = Self(1 << 0);
+    /// Azure Files.
+    pub const FILE: Self = Self(1 << 1);
+    /// Queue Storage.
+    pub const QUEUE: Self = Self(1 << 2);
+    /// Table Storage.
+    pub const TABLE: Self = Self(1 << 3);
+
+    /// Return whether no service is selected.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return whether all services in `other` are selected.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn as_canonical_string(self) -> String {
+        [
+            (Self::BLOB, 'b'),
+            (Self::FILE, 'f'),
+            (Self::QUEUE, 'q'),
+            (Self::TABLE, 't'),
+        ]
+        .into_iter()
+        .filter_map(|(service, symbol)| self.contains(service).then_some(symbol))
+        .collect()
+    }
+}
+
+impl Debug for AccountSasServices {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AccountSasServices")
+            .field(&self.as_canonical_string())
+            .finish()
+    }
+}
+
+impl BitOr for AccountSasServices {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AccountSasServices {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Resource types reachable with an Azure account SAS.
+///
+/// Combining values with `|` produces Azure's canonical resource type order.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountSasResourceTypes(u8);
+
+impl AccountSasResourceTypes {
+    /// Service-level APIs, such as listing containers, queues, tables or shares.
+    pub const SERVICE: Self = Self(1 << 0);
+    /// Container-level APIs on containers, queues, tables and shares.
+    pub const CONTAINER: Self = Self(1 << 1);
+    /// Object-level APIs on blobs, messages, entities and files.
+    pub const OBJECT: Self = Self(1 << 2);
+
+    /// Return whether no resource type is selected.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return whether all resource types in `other` are selected.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn as_canonical_string(self) -> String {
+        [
+            (Self::SERVICE, 's'),
+            (Self::CONTAINER, 'c'),
+            (Self::OBJECT, 'o'),
+        ]
+        .into_iter()
+        .filter_map(|(resource_type, symbol)| self.contains(resource_type).then_some(symbol))
+        .collect()
+    }
+}
+
+impl Debug for AccountSasResourceTypes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AccountSasResourceTypes")
+            .field(&self.as_canonical_string())
+            .finish()
+    }
+}
+
+impl BitOr for AccountSasResourceTypes {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AccountSasResourceTypes {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Permissions for an Azure account SAS.
+///
+/// Permissions apply to every selected service and resource type where they
+/// are meaningful. Combining values with `|` produces Azure's canonical
+/// permission order.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountSasPermissions(u16);
+
+impl AccountSasPermissions {
+    /// Read resources.
+    pub const READ: Self = Self(1 << 0);
+    /// Write resources.
+    pub const WRITE: Self = Self(1 << 1);
+    /// Delete resources.
+    pub const DELETE: Self = Self(1 << 2);
+    /// Delete blob versions.
+    pub const DELETE_VERSION: Self = Self(1 << 3);
+    /// Permanently delete blob snapshots or versions.
+    pub const PERMANENT_DELETE: Self = Self(1 << 4);
+    /// List resources.
+    pub const LIST: Self = Self(1 << 5);
+    /// Add queue messages, table entities or append blocks.
+    pub const ADD: Self = Self(1 << 6);
+    /// Create blobs and files.
+    pub const CREATE: Self = Self(1 << 7);
+    /// Update queue messages and table entities.
+    pub const UPDATE: Self = Self(1 << 8);
+    /// Process and delete queue messages.
+    pub const PROCESS: Self = Self(1 << 9);
+    /// Read or write blob index tags.
+    pub const TAGS: Self = Self(1 << 10);
+    /// Find blobs by index tags.
+    pub const FILTER_BY_TAGS: Self = Self(1 << 11);
+    /// Set an immutability policy or legal hold.
+    pub const SET_IMMUTABILITY_POLICY: Self = Self(1 << 12);
+
+    /// Return whether no permission is selected.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return whether all permissions in `other` are selected.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn as_canonical_string(self) -> String {
+        [
+            (Self::READ, 'r'),
+            (Self::WRITE, 'w'),
+            (Self::DELETE, 'd'),
+            (Self::DELETE_VERSION, 'x'),
+            (Self::PERMANENT_DELETE, 'y'),
+            (Self::LIST, 'l'),
+            (Self::ADD, 'a'),
+            (Self::CREATE, 'c'),
+            (Self::UPDATE, 'u'),
+            (Self::PROCESS, 'p'),
+            (Self::TAGS, 't'),
+            (Self::FILTER_BY_TAGS, 'f'),
+            (Self::SET_IMMUTABILITY_POLICY, 'i'),
+        ]
+        .into_iter()
+        .filter_map(|(permission, symbol)| self.contains(permission).then_some(symbol))
+        .collect()
+    }
+}
+
+impl Debug for AccountSasPermissions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AccountSasPermissions(REDACTED)")
+    }
+}
+
+impl BitOr for AccountSasPermissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AccountSasPermissions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// One complete, typed Azure account SAS grant.
+#[derive(Clone)]
+pub struct AccountSasGrant {
+    services: AccountSasServices,
+    resource_types: AccountSasResourceTypes,
+    permissions: AccountSasPermissions,
+}
+
+impl Debug for AccountSasGrant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccountSasGrant").finish_non_exhaustive()
+    }
+}
+
+impl AccountSasGrant {
+    /// Grant permissions over resource types in one or more services.
+    pub fn new(
+        services: AccountSasServices,
+        resource_types: AccountSasResourceTypes,
+        permissions: AccountSasPermissions,
+    ) -> Self {
+        Self {
+            services,
+            resource_types,
+            permissions,
+        }
+    }
+
+    fn validate(&self) -> Result<ValidatedGrant> {
+        if self.services.is_empty() {
+            return Err(Error::request_invalid(
+                "Azure account SAS services must not be empty",
+            ));
+        }
+        if self.resource_types.is_empty() {
+            return Err(Error::request_invalid(
+                "Azure account SAS resource types must not be empty",
+            ));
+        }
+        if self.permissions.is_empty() {
+            return Err(Error::request_invalid(
+                "Azure account SAS permissions must not be empty",
+            ));
+        }
+        Ok(ValidatedGrant {
+            services: self.services.as_canonical_string(),
+            resource_types: self.resource_types.as_canonical_string(),
+            permissions: self.permissions.as_canonical_string(),
+        })
+    }
+}
+
+struct ValidatedGrant {
+    services: String,
+    resource_types: String,
+    permissions: String,
+}
+
+impl Debug for ValidatedGrant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidatedGrant").finish_non_exhaustive()
+    }
+}
+
+/// Fields signed into one account SAS.
+struct AccountSharedAccessSignature {
+    account: String,
+    services: String,
+    resource_types: String,
+    permissions: String,
+    start: Option<Timestamp>,
+    expiry: Timestamp,
+    ip: Option<String>,
+    protocol: String,
+    version: String,
+}
+
+impl AccountSharedAccessSignature {
+    /// Build the string-to-sign for service versions `2020-12-06` and later.
+    fn string_to_sign(&self) -> String {
+        [
+            self.account.clone(),
+            self.permissions.clone(),
+            self.services.clone(),
+            self.resource_types.clone(),
+            self.start
+                .map_or_else(String::new, Timestamp::format_rfc3339_zulu),
+            self.expiry.format_rfc3339_zulu(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.clone(),
+            self.version.clone(),
+            String::new(), // encryption scope
+            String::new(),
+        ]
+        .join("\n")
+    }
+
+    fn token_with_decoded_key(&self, decoded_key: &[u8]) -> Vec<(String, String)> {
+        let mut elements: Vec<(String, String)> = vec![
+            ("sv".to_string(), self.version.clone()),
+            ("ss".to_string(), self.services.clone()),
+            ("srt".to_string(), self.resource_types.clone()),
+            ("sp".to_string(), self.permissions.clone()),
+            ("se".to_string(), self.expiry.format_rfc3339_zulu()),
+        ];
+        if let Some(start) = &self.start {
+            elements.push(("st".to_string(), start.format_rfc3339_zulu()));
+        }
+        if let Some(ip) = &self.ip {
+            elements.push(("sip".to_string(), ip.clone()));
+        }
+        elements.push(("spr".to_string(), self.protocol.clone()));
+        elements.push((
+            "sig".to_string(),
+            hash::base64_hmac_sha256(decoded_key, self.string_to_sign().as_bytes()),
+        ));
+        elements
+    }
+}
+
+struct OperationTimes {
+    now: Timestamp,
+    start: Option<Timestamp>,
+    expiry: Timestamp,
+}
+
+/// Grants expiration-aware Azure account SAS credentials.
+///
+/// An account SAS delegates access across services and resource types of one
+/// storage account, such as listing containers or reading from Blob, Queue
+/// and Table Storage with one token.
+///
+/// The source credential must be [`Credential::SharedKey`] for exactly the
+/// account bound in [`ServiceSasConfig`], with a Base64-encoded 512-bit Azure
+/// account key. Account SAS generation is local and performs no network I/O.
+/// `expires_in` must be explicit and non-zero, and wire timestamps are
+/// rounded as for [`crate::ServiceSasGranter`].
+///
+/// Reference: <https://learn.microsoft.com/en-us/rest/api/storageservices/create-account-sas>
+#[derive(Clone)]
+pub struct AccountSasGranter {
+    config: ServiceSasConfig,
+    grant: AccountSasGrant,
+    #[cfg(test)]
+    time: Option<Timestamp>,
+}
+
+impl Debug for AccountSasGranter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccountSasGranter").finish_non_exhaustive()
+    }
+}
+
+impl AccountSasGranter {
+    /// Create a granter for a bound account configuration and typed grant.
+    pub fn new(config: ServiceSasConfig, grant: AccountSasGrant) -> Self {
+        Self {
+            config,
+            grant,
+            #[cfg(test)]
+            time: None,
+        }
+    }
+
+    /// Replace the bound services, resource types and permissions.
+    pub fn with_grant(mut self, grant: AccountSasGrant) -> Self {
+        self.grant = grant;
+        self
+    }
+
+    #[cfg(test)]
+    fn with_time(mut self, time: Timestamp) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    fn now(&self) -> Timestamp {
+        #[cfg(test)]
+        if let Some(time) = self.time {
+            return time;
+        }
+        Timestamp::now()
+    }
+
+    fn operation_times(
+        &self,
+        start: Option<Timestamp>,
+        expires_in: Option<Duration>,
+    ) -> Result<OperationTimes> {
+        let expires_in = expires_in.ok_or_else(|| {
+            Error::request_invalid("Azure account SAS requires an explicit lifetime")
+        })?;
+        if expires_in.is_zero() {
+            return Err(Error::request_invalid(
+                "Azure account SAS lifetime must be greater than zero",
+            ));
+        }
+
+        let now = self.now();
+        let expiry = expiry_at_wire_second(now, expires_in)?;
+        if expiry <= now {
+            return Err(Error::request_invalid(
+                "Azure account SAS lifetime does not reach a future wire timestamp",
+            ));
+        }
+        let start = start.map(ceil_to_wire_second).transpose()?;
+        if start.is_some_and(|start| start > now) {
+            return Err(Error::request_invalid(
+                "Azure account SAS start time must not be in the future",
+            ));
+        }
+        if start.is_some_and(|start| start >= expiry) {
+            return Err(Error::request_invalid(
+                "Azure account SAS start time must be before its expiration",
+            ));
+        }
+
+        Ok(OperationTimes { now, start, expiry })
+    }
+}
+
+impl GrantCredential for AccountSasGranter {
+    type Credential = Credential;
+
+    fn required_valid_until(
+        &self,
+        _credential: &Self::Credential,
+        _expires_in: Option<Duration>,
+    ) -> Timestamp {
+        self.now()
+    }
+
+    async fn grant_credential(
+        &self,
+        _ctx: &Context,
+        credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Result<Self::Credential> {
+        let Credential::SharedKey {
+            account_name,
+            account_key,
+        } = credential
+        else {
+            return Err(Error::credential_invalid(
+                "Azure account SAS requires a Shared Key source credential",
+            ));
+        };
+
+        let config = self.config.validate()?;
+        if account_name != &config.account {
+            return Err(Error::credential_invalid(
+                "Azure Shared Key source account does not match the account SAS account",
+            ));
+        }
+        let decoded_key = hash::base64_decode(account_key)
+            .ok()
+            .filter(|key| key.len() == STORAGE_ACCOUNT_KEY_LENGTH)
+            .ok_or_else(|| {
+                Error::credential_invalid("Azure account SAS source account key is invalid")
+            })?;
+
+        let grant = self.grant.validate()?;
+        let times = self.operation_times(config.start, expires_in)?;
+        if !credential.is_valid_at(times.now) {
+            return Err(Error::credential_invalid(
+                "Azure account SAS source credential is not currently usable",
+            ));
+        }
+
+        let signature = AccountSharedAccessSignature {
+            account: config.account,
+            services: grant.services,
+            resource_types: grant.resource_types,
+            permissions: grant.permissions,
+            start: times.start,
+            expiry: times.expiry,
+            ip: config.ip,
+            protocol: config.protocol.as_str().to_string(),
+            version: config.version.as_str().to_string(),
+        };
+        let token = encode_query_pairs(&signature.token_with_decoded_key(&decoded_key));
+
+        Ok(Credential::with_sas_token_expires_at(&token, times.expiry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SasIpRange, SasProtocol, ServiceSasVersion, StaticCredentialProvider};
+    use reqsign_core::{ErrorKind, Granter};
+    use std::net::Ipv4Addr;
+
+    const ACCOUNT_KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn timestamp(value: &str) -> Timestamp {
+        value.parse().expect("timestamp must parse")
+    }
+
+    fn shared_key(account: &str) -> Credential {
+        Credential::with_shared_key(account, &hash::base64_encode(ACCOUNT_KEY.as_bytes()))
+    }
+
+    fn query_value(token: &str, key: &str) -> Option<String> {
+        form_urlencoded::parse(token.as_bytes())
+            .find_map(|(candidate, value)| (candidate == key).then(|| value.into_owned()))
+    }
+
+    #[test]
+    fn flags_use_canonical_order() {
+        let services = AccountSasServices::TABLE
+            | AccountSasServices::QUEUE
+            | AccountSasServices::FILE
+            | AccountSasServices::BLOB;
+        let resource_types = AccountSasResourceTypes::OBJECT
+            | AccountSasResourceTypes::CONTAINER
+            | AccountSasResourceTypes::SERVICE;
+        let permissions = AccountSasPermissions::SET_IMMUTABILITY_POLICY
+            | AccountSasPermissions::FILTER_BY_TAGS
+            | AccountSasPermissions::TAGS
+            | AccountSasPermissions::PROCESS
+            | AccountSasPermissions::UPDATE
+            | AccountSasPermissions::CREATE
+            | AccountSasPermissions::ADD
+            | AccountSasPermissions::LIST
+            | AccountSasPermissions::PERMANENT_DELETE
+            | AccountSasPermissions::DELETE_VERSION
+            | AccountSasPermissions::DELETE
+            | AccountSasPermissions::WRITE
+            | AccountSasPermissions::READ;
+
+        let grant = AccountSasGrant::new(services, resource_types, permissions)
+            .validate()
+            .expect("grant must be valid");
+        assert_eq!(grant.services, "bfqt");
+        assert_eq!(grant.resource_types, "sco");
+        assert_eq!(grant.permissions, "rwdxylacuptfi");
+    }
+
+    #[test]
+    fn rejects_empty_grants() {
+        for grant in [
+            AccountSasGrant::new(
+                AccountSasServices::default(),
+                AccountSasResourceTypes::SERVICE,
+                AccountSasPermissions::LIST,
+            ),
+            AccountSasGrant::new(
+                AccountSasServices::BLOB,
+                AccountSasResourceTypes::default(),
+                AccountSasPermissions::LIST,
+            ),
+            AccountSasGrant::new(
+                AccountSasServices::BLOB,
+                AccountSasResourceTypes::SERVICE,
+                AccountSasPermissions::default(),
+            ),
+        ] {
+            assert_eq!(
+                grant.validate().expect_err("empty grant must fail").kind(),
+                ErrorKind::RequestInvalid
+            );
+        }
+    }
+
+    #[test]
+    fn string_to_sign_matches_documented_layout() {
+        let signature = AccountSharedAccessSignature {
+            account: "myaccount".to_string(),
+            services: "bqt".to_string(),
+            resource_types: "sco".to_string(),
+            permissions: "rl".to_string(),
+            start: Some(timestamp("2030-01-01T00:00:00Z")),
+            expiry: timestamp("2030-01-01T01:00:00Z"),
+            ip: Some("168.1.5.60-168.1.5.70".to_string()),
+            protocol: "https".to_string(),
+            version: "2022-11-02".to_string(),
+        };
+
+        assert_eq!(
+            signature.string_to_sign(),
+            "myaccount\nrl\nbqt\nsco\n2030-01-01T00:00:00Z\n2030-01-01T01:00:00Z\n168.1.5.60-168.1.5.70\nhttps\n2022-11-02\n\n"
+        );
+        let pairs = signature.token_with_decoded_key(ACCOUNT_KEY.as_bytes());
+        assert_eq!(
+            pairs.last().unwrap(),
+            &(
+                "sig".to_string(),
+                "gOqd1kAfjuQxv0ssGeN2jB5FIdHzLLLGM3aI2iJq4fI=".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn grants_account_sas_from_shared_key() {
+        let now = timestamp("2030-01-01T00:00:00.250Z");
+        let config = ServiceSasConfig::new("myaccount")
+            .with_start(timestamp("2029-12-31T23:59:59.500Z"))
+            .with_ip(
+                SasIpRange::range(Ipv4Addr::new(168, 1, 5, 60), Ipv4Addr::new(168, 1, 5, 70))
+                    .unwrap(),
+            )
+            .with_protocol(SasProtocol::HttpsAndHttp)
+            .with_version(ServiceSasVersion::new("2022-11-02").unwrap());
+        let grant = AccountSasGrant::new(
+            AccountSasServices::BLOB | AccountSasServices::QUEUE,
+            AccountSasResourceTypes::SERVICE | AccountSasResourceTypes::CONTAINER,
+            AccountSasPermissions::READ | AccountSasPermissions::LIST,
+        );
+
+        let output = AccountSasGranter::new(config, grant)
+            .with_time(now)
+            .grant_credential(
+                &Context::new(),
+                &shared_key("myaccount"),
+                Some(Duration::from_secs(3600)),
+            )
+            .await
+            .expect("account SAS must be granted");
+
+        let Credential::SasToken {
+            token,
+            expires_at: Some(expires_at),
+        } = output
+        else {
+            panic!("expected expiring SAS credential");
+        };
+        assert_eq!(expires_at, timestamp("2030-01-01T01:00:00Z"));
+        for (key, value) in [
+            ("sv", "2022-11-02"),
+            ("ss", "bq"),
+            ("srt", "sc"),
+            ("sp", "rl"),
+            ("st", "2030-01-01T00:00:00Z"),
+            ("se", "2030-01-01T01:00:00Z"),
+            ("sip", "168.1.5.60-168.1.5.70"),
+            ("spr", "https,http"),
+        ] {
+            assert_eq!(query_value(&token, key).as_deref(), Some(value), "{key}");
+        }
+        assert!(query_value(&token, "sr").is_none());
+        assert!(query_value(&token, "sig").is_some());
+    }
+
+    #[tokio::test]
+    async fn rejects_unusable_source_credentials() {
+        let grant = AccountSasGrant::new(
+            AccountSasServices::BLOB,
+            AccountSasResourceTypes::SERVICE,
+            AccountSasPermissions::LIST,
+        );
+        let granter = AccountSasGranter::new(ServiceSasConfig::new("myaccount"), grant);
+
+        for credential in [
+            shared_key("otheraccount"),
+            Credential::with_shared_key("myaccount", "short"),
+            Credential::with_sas_token("sv=2022-11-02&sig=abc"),
+        ] {
+            let err = granter
+                .grant_credential(&Context::new(), &credential, Some(Duration::from_secs(60)))
+                .await
+                .expect_err("unusable source must fail");
+            assert_eq!(err.kind(), ErrorKind::CredentialInvalid);
+        }
+
+        let err = granter
+            .grant_credential(&Context::new(), &shared_key("myaccount"), None)
+            .await
+            .expect_err("missing lifetime must fail");
+        assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+    }
+
+    #[tokio::test]
+    async fn granted_token_is_usable_through_granter() {
+        let granter = Granter::new(
+            Context::new(),
+            StaticCredentialProvider::new_shared_key(
+                "myaccount",
+                &hash::base64_encode(ACCOUNT_KEY.as_bytes()),
+            ),
+            AccountSasGranter::new(
+                ServiceSasConfig::new("myaccount"),
+                AccountSasGrant::new(
+                    AccountSasServices::BLOB,
+                    AccountSasResourceTypes::SERVICE,
+                    AccountSasPermissions::LIST,
+                ),
+            ),
+        );
+
+        let output = granter
+            .grant(Some(Duration::from_secs(600)))
+            .await
+            .expect("account SAS must be granted");
+        assert!(matches!(
+            output,
+            Credential::SasToken {
+                expires_at: Some(_),
+                ..
+            }
+        ));
+    }
+}
@@ -17,6 +17,9 @@
 
 use percent_encoding::{AsciiSet, NON_ALPHANUMERIC};
 
+/// OAuth 2.0 scope requested by token providers when none is configured.
+pub const STORAGE_SCOPE: &str = "https://storage.azure.com/.default";
+
 // Headers used in azure services.
 pub const X_MS_DATE: &str = "x-ms-date";
 #[allow(dead_code)]
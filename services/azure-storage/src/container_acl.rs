@@ -0,0 +1,326 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::service_sas::{
+    ServiceSasContainerPermissions, validate_container, validate_signed_identifier,
+};
+use bytes::Bytes;
+use http::header;
+use reqsign_core::time::Timestamp;
+use reqsign_core::{Error, Result};
+use std::fmt::{Debug, Formatter};
+
+const SET_CONTAINER_ACL_VERSION: &str = "2020-12-06";
+const MAX_STORED_ACCESS_POLICIES: usize = 5;
+
+/// One stored access policy (signed identifier) on a container.
+///
+/// A Service SAS references the policy with
+/// [`ServiceSasGrant::with_signed_identifier`](crate::ServiceSasGrant::with_signed_identifier).
+/// Fields left unset here must be carried by the SAS instead.
+#[derive(Clone)]
+pub struct ContainerAccessPolicy {
+    id: String,
+    start: Option<Timestamp>,
+    expiry: Option<Timestamp>,
+    permissions: ServiceSasContainerPermissions,
+}
+
+impl Debug for ContainerAccessPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContainerAccessPolicy")
+            .field("id", &self.id)
+            .field("start", &self.start)
+            .field("expiry", &self.expiry)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ContainerAccessPolicy {
+    /// Create a policy with a unique identifier of up to 64 characters.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            start: None,
+            expiry: None,
+            permissions: ServiceSasContainerPermissions::default(),
+        }
+    }
+
+    /// Set the time the policy becomes valid.
+    pub fn with_start(mut self, start: Timestamp) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Set the time the policy expires.
+    pub fn with_expiry(mut self, expiry: Timestamp) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// Set the permissions granted by the policy.
+    pub fn with_permissions(mut self, permissions: ServiceSasContainerPermissions) -> Self {
+        self.permissions = permissions;
+        self
+    }
+}
+
+/// Builds a `Set Container ACL` request that replaces the stored access
+/// policies of one container.
+///
+/// The request carries no authorization; sign it with [`RequestSigner`] and a
+/// Shared Key credential. It sends no `x-ms-blob-public-access` header, so
+/// Azure also resets the container's public access level to private.
+///
+/// Reference: <https://learn.microsoft.com/en-us/rest/api/storageservices/set-container-acl>
+///
+/// [`RequestSigner`]: crate::RequestSigner
+#[derive(Clone)]
+pub struct SetContainerAcl {
+    container: String,
+    policies: Vec<ContainerAccessPolicy>,
+}
+
+impl Debug for SetContainerAcl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetContainerAcl")
+            .field("container", &self.container)
+            .field("policies", &self.policies)
+            .finish()
+    }
+}
+
+impl SetContainerAcl {
+    /// Create a request for a container. Without policies it removes all of
+    /// the container's stored access policies.
+    pub fn new(container: impl Into<String>) -> Self {
+        Self {
+            container: container.into(),
+            policies: Vec::new(),
+        }
+    }
+
+    /// Add a stored access policy. A container holds at most five.
+    pub fn with_policy(mut self, policy: ContainerAccessPolicy) -> Self {
+        self.policies.push(policy);
+        self
+    }
+
+    /// Build the `SignedIdentifiers` XML request body.
+    pub fn body(&self) -> Result<String> {
+        if self.policies.len() > MAX_STORED_ACCESS_POLICIES {
+            return Err(Error::request_invalid(
+                "Azure containers hold at most five stored access policies",
+            ));
+        }
+
+        let mut body =
+            String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?><SignedIdentifiers>");
+        for (index, policy) in self.policies.iter().enumerate() {
+            validate_signed_identifier(&policy.id)?;
+            if self.policies[..index].iter().any(|p| p.id == policy.id) {
+                return Err(Error::request_invalid(
+                    "stored access policy identifiers must be unique",
+                ));
+            }
+            if let (Some(start), Some(expiry)) = (policy.start, policy.expiry) {
+                if start >= expiry {
+                    return Err(Error::request_invalid(
+                        "stored access policy start time must be before its expiry",
+                    ));
+                }
+            }
+
+            body.push_str("<SignedIdentifier><Id>");
+            push_escaped(&mut body, &policy.id);
+            body.push_str("</Id><AccessPolicy>");
+            if let Some(start) = policy.start {
+                body.push_str(&format!("<Start>{}</Start>", start.format_rfc3339_zulu()));
+            }
+            if let Some(expiry) = policy.expiry {
+                body.push_str(&format!(
+                    "<Expiry>{}</Expiry>",
+                    expiry.format_rfc3339_zulu()
+                ));
+            }
+            if !policy.permissions.is_empty() {
+                body.push_str(&format!(
+                    "<Permission>{}</Permission>",
+                    policy.permissions.as_canonical_string()
+                ));
+            }
+            body.push_str("</AccessPolicy></SignedIdentifier>");
+        }
+        body.push_str("</SignedIdentifiers>");
+        Ok(body)
+    }
+
+    /// Build the unsigned request against a Blob service endpoint such as
+    /// `https://account.blob.core.windows.net`.
+    pub fn build(&self, endpoint: &str) -> Result<http::Request<Bytes>> {
+        validate_container(&self.container)?;
+        let body = self.body()?;
+        let uri: http::Uri = format!(
+            "{}/{}?restype=container&comp=acl",
+            endpoint.trim_end_matches('/'),
+            self.container
+        )
+        .parse()
+        .map_err(|e| Error::request_invalid("invalid Set Container ACL URI").with_source(e))?;
+        if uri.scheme().is_none() || uri.authority().is_none() {
+            return Err(Error::request_invalid(
+                "Set Container ACL endpoint must be an absolute URI",
+            ));
+        }
+
+        http::Request::put(uri)
+            .header("x-ms-version", SET_CONTAINER_ACL_VERSION)
+            .header(header::CONTENT_TYPE, "application/xml")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(Bytes::from(body))
+            .map_err(|e| {
+                Error::unexpected("failed to build Set Container ACL request").with_source(e)
+            })
+    }
+}
+
+fn push_escaped(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Credential, RequestSigner};
+    use reqsign_core::{Context, ErrorKind, SignRequest};
+
+    fn timestamp(value: &str) -> Timestamp {
+        value.parse().expect("timestamp must parse")
+    }
+
+    #[test]
+    fn builds_signed_identifiers_body() {
+        let body = SetContainerAcl::new("container")
+            .with_policy(
+                ContainerAccessPolicy::new("read-only")
+                    .with_start(timestamp("2030-01-01T00:00:00Z"))
+                    .with_expiry(timestamp("2030-02-01T00:00:00Z"))
+                    .with_permissions(
+                        ServiceSasContainerPermissions::LIST | ServiceSasContainerPermissions::READ,
+                    ),
+            )
+            .with_policy(ContainerAccessPolicy::new("a&b"))
+            .body()
+            .unwrap();
+
+        assert_eq!(
+            body,
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?><SignedIdentifiers>\
+             <SignedIdentifier><Id>read-only</Id><AccessPolicy>\
+             <Start>2030-01-01T00:00:00Z</Start><Expiry>2030-02-01T00:00:00Z</Expiry>\
+             <Permission>rl</Permission></AccessPolicy></SignedIdentifier>\
+             <SignedIdentifier><Id>a&amp;b</Id><AccessPolicy></AccessPolicy></SignedIdentifier>\
+             </SignedIdentifiers>"
+        );
+        assert_eq!(
+            SetContainerAcl::new("container").body().unwrap(),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?><SignedIdentifiers></SignedIdentifiers>"
+        );
+        assert!(format!("{:?}", ContainerAccessPolicy::new("read-only")).contains("\"read-only\""));
+    }
+
+    #[test]
+    fn rejects_invalid_policies() {
+        let too_many = (0..6).fold(SetContainerAcl::new("container"), |acl, index| {
+            acl.with_policy(ContainerAccessPolicy::new(format!("policy-{index}")))
+        });
+        for acl in [
+            too_many,
+            SetContainerAcl::new("container")
+                .with_policy(ContainerAccessPolicy::new("same"))
+                .with_policy(ContainerAccessPolicy::new("same")),
+            SetContainerAcl::new("container").with_policy(ContainerAccessPolicy::new("")),
+            SetContainerAcl::new("container")
+                .with_policy(ContainerAccessPolicy::new("x".repeat(65))),
+            SetContainerAcl::new("container").with_policy(
+                ContainerAccessPolicy::new("backwards")
+                    .with_start(timestamp("2030-02-01T00:00:00Z"))
+                    .with_expiry(timestamp("2030-01-01T00:00:00Z")),
+            ),
+            SetContainerAcl::new("Invalid_Container"),
+        ] {
+            let err = acl
+                .build("https://account.blob.core.windows.net")
+                .expect_err("invalid ACL must fail");
+            assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+        }
+
+        let err = SetContainerAcl::new("container")
+            .build("account.blob.core.windows.net")
+            .expect_err("relative endpoint must fail");
+        assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+    }
+
+    #[tokio::test]
+    async fn request_is_signed_with_shared_key() {
+        let req = SetContainerAcl::new("container")
+            .with_policy(
+                ContainerAccessPolicy::new("read-only")
+                    .with_expiry(timestamp("2030-02-01T00:00:00Z"))
+                    .with_permissions(ServiceSasContainerPermissions::READ),
+            )
+            .build("https://account.blob.core.windows.net/")
+            .unwrap();
+        assert_eq!(
+            req.uri(),
+            "https://account.blob.core.windows.net/container?restype=container&comp=acl"
+        );
+        assert_eq!(req.method(), http::Method::PUT);
+
+        let (mut parts, body) = req.into_parts();
+        assert_eq!(
+            parts.headers[header::CONTENT_LENGTH],
+            body.len().to_string()
+        );
+
+        let key = reqsign_core::hash::base64_encode("key".as_bytes());
+        RequestSigner::new()
+            .with_time(timestamp("2022-03-01T08:12:34Z"))
+            .sign_request(
+                &Context::new(),
+                &mut parts,
+                Some(&Credential::with_shared_key("account", &key)),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            parts.headers[header::AUTHORIZATION],
+            "SharedKey account:ZQLCQbsyRTeUZOZqeT8e3HlYmmjfn+UOIKDm5NJYVh4="
+        );
+    }
+}
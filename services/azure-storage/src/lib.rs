@@ -87,7 +87,8 @@
 //!
 //! ### Managed Identity
 //!
-//! When running on Azure services (VMs, App Service, AKS), the crate
+//! When running on Azure services (VMs, App Service, Service Fabric, Azure Arc,
+//! Cloud Shell), the crate
 //! automatically uses managed identity:
 //!
 //! ```no_run
@@ -170,7 +171,9 @@
 //! - [Blob storage operations](examples/blob_storage.rs)
 //! - [SAS token generation](examples/sas_token.rs)
 
+mod account_sas;
 mod constants;
+mod container_acl;
 mod service_sas;
 mod user_delegation;
 mod user_delegation_sas;
@@ -178,17 +181,23 @@ mod user_delegation_sas;
 mod credential;
 pub use credential::Credential;
 
+pub use account_sas::{
+    AccountSasGrant, AccountSasGranter, AccountSasPermissions, AccountSasResourceTypes,
+    AccountSasServices,
+};
+pub use container_acl::{ContainerAccessPolicy, SetContainerAcl};
 pub use service_sas::{
     SasIpRange, ServiceSasBlobPermissions, ServiceSasConfig, ServiceSasContainerPermissions,
-    ServiceSasGrant, ServiceSasGranter, ServiceSasResource, ServiceSasVersion,
-    ServiceSharedAccessSignature,
+    ServiceSasFilePermissions, ServiceSasGrant, ServiceSasGranter, ServiceSasQueuePermissions,
+    ServiceSasResource, ServiceSasSharePermissions, ServiceSasTablePermissions,
+    ServiceSasTableRange, ServiceSasVersion, ServiceSharedAccessSignature,
 };
 pub use user_delegation_sas::{
     SasProtocol, UserDelegationSasGrant, UserDelegationSasGranter, UserDelegationSasPermissions,
 };
 
 mod sign_request;
-pub use sign_request::RequestSigner;
+pub use sign_request::{RequestSigner, SharedKeyMode};
 
 mod provide_credential;
 pub use provide_credential::*;
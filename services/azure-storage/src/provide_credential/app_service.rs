@@ -0,0 +1,215 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
+use crate::provide_credential::managed_identity::{
+    IdentityKind, UserAssignedIdentity, parse_token_response, response_error,
+};
+use crate::provide_credential::scope_to_resource;
+use http::HeaderValue;
+use reqsign_core::{Context, ProvideCredential, Result};
+
+const API_VERSION: &str = "2019-08-01";
+
+/// Load credential from the managed identity endpoint of Azure App Service.
+///
+/// Azure App Service, Azure Functions and Azure Container Apps expose managed
+/// identity through `IDENTITY_ENDPOINT` and `IDENTITY_HEADER` instead of IMDS.
+/// This provider returns `None` when either variable is missing.
+///
+/// A user-assigned identity is selected by client ID, object ID or resource ID,
+/// falling back to `AZURE_CLIENT_ID`, `AZURE_OBJECT_ID` and `AZURE_MSI_RES_ID`.
+///
+/// Reference: <https://learn.microsoft.com/en-us/azure/app-service/overview-managed-identity#rest-endpoint-reference>
+#[derive(Debug, Default, Clone)]
+pub struct AppServiceCredentialProvider {
+    identity: UserAssignedIdentity,
+    scope: Option<String>,
+}
+
+impl AppServiceCredentialProvider {
+    /// Create a new App Service managed identity loader.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select a user-assigned identity by client ID.
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.identity.client_id = Some(client_id.into());
+        self
+    }
+
+    /// Select a user-assigned identity by object ID.
+    pub fn with_object_id(mut self, object_id: impl Into<String>) -> Self {
+        self.identity.object_id = Some(object_id.into());
+        self
+    }
+
+    /// Select a user-assigned identity by Azure resource ID.
+    pub fn with_msi_res_id(mut self, msi_res_id: impl Into<String>) -> Self {
+        self.identity.msi_res_id = Some(msi_res_id.into());
+        self
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// The token is requested for the scope's resource: a trailing `.default`
+    /// is removed, so `https://vault.azure.net/.default` requests
+    /// `https://vault.azure.net/`. Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    pub(crate) fn with_identity(mut self, identity: UserAssignedIdentity) -> Self {
+        self.identity = identity;
+        self
+    }
+}
+
+impl ProvideCredential for AppServiceCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let (Some(endpoint), Some(identity_header)) = (
+            ctx.env_var("IDENTITY_ENDPOINT").filter(|v| !v.is_empty()),
+            ctx.env_var("IDENTITY_HEADER").filter(|v| !v.is_empty()),
+        ) else {
+            return Ok(None);
+        };
+
+        let resource = scope_to_resource(self.scope.as_deref().unwrap_or(STORAGE_SCOPE));
+        let url = {
+            let mut query = form_urlencoded::Serializer::new(String::new());
+            query
+                .append_pair("api-version", API_VERSION)
+                .append_pair("resource", resource);
+            if let Some((kind, value)) = self.identity.resolve(ctx)? {
+                let name = match kind {
+                    IdentityKind::Client => "client_id",
+                    IdentityKind::Object => "object_id",
+                    IdentityKind::Resource => "mi_res_id",
+                };
+                query.append_pair(name, &value);
+            }
+            format!("{endpoint}?{}", query.finish())
+        };
+
+        let mut header_value = HeaderValue::from_str(&identity_header).map_err(|e| {
+            reqsign_core::Error::config_invalid("IDENTITY_HEADER is not a valid header value")
+                .with_source(e)
+        })?;
+        header_value.set_sensitive(true);
+
+        let req = http::Request::builder()
+            .method(http::Method::GET)
+            .uri(&url)
+            .header("X-IDENTITY-HEADER", header_value)
+            .body(bytes::Bytes::new())
+            .map_err(|e| {
+                reqsign_core::Error::unexpected("failed to build App Service identity request")
+                    .with_source(e)
+            })?;
+
+        let resp = ctx.http_send(req).await?;
+
+        if !resp.status().is_success() {
+            return Err(response_error("App Service", &resp));
+        }
+
+        parse_token_response(resp.body(), "App Service").map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provide_credential::test_util::{RecordingHttpSend, context};
+    use reqsign_core::time::Timestamp;
+
+    const TOKEN_RESPONSE: &str = r#"{"access_token":"token","expires_on":"1700000000","resource":"https://storage.azure.com/","token_type":"Bearer"}"#;
+
+    #[tokio::test]
+    async fn test_returns_none_outside_app_service() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = context(http.clone(), &[("IDENTITY_ENDPOINT", "http://localhost")]);
+
+        let cred = AppServiceCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+
+        assert!(cred.is_none());
+        assert!(http.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_requests_token_from_identity_endpoint() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = context(
+            http.clone(),
+            &[
+                ("IDENTITY_ENDPOINT", "http://localhost:42356/msi/token"),
+                ("IDENTITY_HEADER", "secret-header"),
+            ],
+        );
+
+        let cred = AppServiceCredentialProvider::new()
+            .with_client_id("client-id")
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        match cred {
+            Credential::BearerToken { token, expires_in } => {
+                assert_eq!(token, "token");
+                assert_eq!(
+                    expires_in,
+                    Some(Timestamp::from_second(1_700_000_000).unwrap())
+                );
+            }
+            _ => panic!("expected bearer token"),
+        }
+        let requests = http.requests();
+        assert_eq!(
+            requests[0].uri(),
+            "http://localhost:42356/msi/token?api-version=2019-08-01&resource=https%3A%2F%2Fstorage.azure.com%2F&client_id=client-id"
+        );
+        assert_eq!(requests[0].headers()["X-IDENTITY-HEADER"], "secret-header");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_multiple_user_assigned_identities() {
+        let ctx = context(
+            RecordingHttpSend::new(TOKEN_RESPONSE),
+            &[
+                ("IDENTITY_ENDPOINT", "http://localhost:42356/msi/token"),
+                ("IDENTITY_HEADER", "secret-header"),
+            ],
+        );
+
+        let err = AppServiceCredentialProvider::new()
+            .with_client_id("client-id")
+            .with_object_id("object-id")
+            .provide_credential(&ctx)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::ConfigInvalid);
+    }
+}
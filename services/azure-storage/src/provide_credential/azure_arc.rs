@@ -0,0 +1,339 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
+use crate::provide_credential::managed_identity::{
+    UserAssignedIdentity, parse_token_response, response_error,
+};
+use crate::provide_credential::scope_to_resource;
+use http::{HeaderValue, StatusCode, header};
+use reqsign_core::{Context, ProvideCredential, Result};
+
+const API_VERSION: &str = "2019-11-01";
+/// Largest secret file the Azure Arc agent writes.
+const MAX_SECRET_FILE_SIZE: usize = 4096;
+
+/// Load credential from the managed identity endpoint of Azure Arc-enabled servers.
+///
+/// The Azure Connected Machine agent exposes managed identity through
+/// `IDENTITY_ENDPOINT` and `IMDS_ENDPOINT`. This provider returns `None` when
+/// either variable is missing.
+///
+/// The first request is answered with `401` and a `WWW-Authenticate: Basic
+/// realm=<path>` challenge naming a secret file written by the agent. The file
+/// is read through [`Context::file_read`] and its content is sent back as the
+/// `Authorization` header. Only files with a `.key` extension directly inside
+/// the agent's token directory are accepted.
+///
+/// Azure Arc only supports the system-assigned identity.
+///
+/// Reference: <https://learn.microsoft.com/en-us/azure/azure-arc/servers/managed-identity-authentication>
+#[derive(Debug, Default, Clone)]
+pub struct AzureArcCredentialProvider {
+    identity: UserAssignedIdentity,
+    scope: Option<String>,
+}
+
+impl AzureArcCredentialProvider {
+    /// Create a new Azure Arc managed identity loader.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// The token is requested for the scope's resource: a trailing `.default`
+    /// is removed, so `https://vault.azure.net/.default` requests
+    /// `https://vault.azure.net/`. Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    pub(crate) fn with_identity(mut self, identity: UserAssignedIdentity) -> Self {
+        self.identity = identity;
+        self
+    }
+}
+
+impl ProvideCredential for AzureArcCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let (Some(endpoint), Some(_)) = (
+            ctx.env_var("IDENTITY_ENDPOINT").filter(|v| !v.is_empty()),
+            ctx.env_var("IMDS_ENDPOINT").filter(|v| !v.is_empty()),
+        ) else {
+            return Ok(None);
+        };
+        self.identity.ensure_unset("Azure Arc")?;
+
+        let resource = scope_to_resource(self.scope.as_deref().unwrap_or(STORAGE_SCOPE));
+        let url = {
+            let mut query = form_urlencoded::Serializer::new(String::new());
+            query
+                .append_pair("api-version", API_VERSION)
+                .append_pair("resource", resource);
+            format!("{endpoint}?{}", query.finish())
+        };
+
+        let resp = ctx.http_send(build_request(&url, None)?).await?;
+        if resp.status() != StatusCode::UNAUTHORIZED {
+            return Err(response_error("Azure Arc", &resp));
+        }
+
+        let path = resp
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split_once("Basic realm="))
+            .map(|(_, path)| path.trim().to_string())
+            .ok_or_else(|| {
+                reqsign_core::Error::unexpected(
+                    "Azure Arc identity response is missing the WWW-Authenticate challenge",
+                )
+            })?;
+        validate_secret_path(ctx, &path)?;
+
+        let secret = ctx.file_read(&path).await?;
+        if secret.len() > MAX_SECRET_FILE_SIZE {
+            return Err(reqsign_core::Error::unexpected(format!(
+                "Azure Arc secret file {path} is larger than {MAX_SECRET_FILE_SIZE} bytes"
+            )));
+        }
+        let secret = String::from_utf8(secret).map_err(|e| {
+            reqsign_core::Error::unexpected("Azure Arc secret file is not valid UTF-8")
+                .with_source(e)
+        })?;
+
+        let resp = ctx
+            .http_send(build_request(&url, Some(secret.trim()))?)
+            .await?;
+        if !resp.status().is_success() {
+            return Err(response_error("Azure Arc", &resp));
+        }
+
+        parse_token_response(resp.body(), "Azure Arc").map(Some)
+    }
+}
+
+fn build_request(url: &str, secret: Option<&str>) -> Result<http::Request<bytes::Bytes>> {
+    let mut req = http::Request::builder()
+        .method(http::Method::GET)
+        .uri(url)
+        .header("Metadata", "true");
+    if let Some(secret) = secret {
+        let mut value = HeaderValue::from_str(&format!("Basic {secret}")).map_err(|e| {
+            reqsign_core::Error::unexpected("Azure Arc secret is not a valid header value")
+                .with_source(e)
+        })?;
+        value.set_sensitive(true);
+        req = req.header(header::AUTHORIZATION, value);
+    }
+    req.body(bytes::Bytes::new()).map_err(|e| {
+        reqsign_core::Error::unexpected("failed to build Azure Arc identity request").with_source(e)
+    })
+}
+
+/// Check that the challenge points at a key file in the agent's token directory.
+///
+/// The path comes from the HTTP response, so anything else is refused rather
+/// than read and sent back to the endpoint.
+fn validate_secret_path(ctx: &Context, path: &str) -> Result<()> {
+    let (dir, file_name, expected_dir) = if cfg!(windows) {
+        let program_data = ctx.env_var("ProgramData").unwrap_or_default();
+        let (dir, file_name) = path.rsplit_once('\\').unwrap_or(("", path));
+        (
+            dir.to_ascii_lowercase(),
+            file_name,
+            format!("{program_data}\\AzureConnectedMachineAgent\\Tokens").to_ascii_lowercase(),
+        )
+    } else {
+        let (dir, file_name) = path.rsplit_once('/').unwrap_or(("", path));
+        (
+            dir.to_string(),
+            file_name,
+            "/var/opt/azcmagent/tokens".to_string(),
+        )
+    };
+
+    if dir != expected_dir || !file_name.ends_with(".key") || file_name.len() <= ".key".len() {
+        return Err(reqsign_core::Error::unexpected(format!(
+            "Azure Arc challenge names an unexpected secret file: {path}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use reqsign_core::{FileRead, HttpSend, StaticEnv};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Request URI and `Authorization` value.
+    type RecordedRequest = (String, Option<String>);
+
+    #[derive(Clone, Debug)]
+    struct ChallengeHttpSend {
+        realm: String,
+        requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    }
+
+    impl ChallengeHttpSend {
+        fn new(realm: &str) -> Self {
+            Self {
+                realm: realm.to_string(),
+                requests: Arc::default(),
+            }
+        }
+    }
+
+    impl HttpSend for ChallengeHttpSend {
+        async fn http_send(&self, req: http::Request<Bytes>) -> Result<http::Response<Bytes>> {
+            let authorization = req
+                .headers()
+                .get(header::AUTHORIZATION)
+                .map(|v| v.to_str().unwrap().to_string());
+            self.requests
+                .lock()
+                .unwrap()
+                .push((req.uri().to_string(), authorization.clone()));
+
+            if authorization.is_none() {
+                return Ok(http::Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .header(
+                        header::WWW_AUTHENTICATE,
+                        format!("Basic realm={}", self.realm),
+                    )
+                    .body(Bytes::new())
+                    .unwrap());
+            }
+            Ok(http::Response::new(Bytes::from_static(
+                br#"{"access_token":"token","expires_on":"1700000000","token_type":"Bearer"}"#,
+            )))
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct RecordingFileRead {
+        paths: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl FileRead for RecordingFileRead {
+        async fn file_read(&self, path: &str) -> Result<Vec<u8>> {
+            self.paths.lock().unwrap().push(path.to_string());
+            Ok(b"arc-secret\n".to_vec())
+        }
+    }
+
+    fn context(http: ChallengeHttpSend, fs: RecordingFileRead) -> Context {
+        Context::new()
+            .with_http_send(http)
+            .with_file_read(fs)
+            .with_env(StaticEnv {
+                home_dir: None,
+                envs: HashMap::from([
+                    (
+                        "IDENTITY_ENDPOINT".to_string(),
+                        "http://localhost:40342/metadata/identity/oauth2/token".to_string(),
+                    ),
+                    (
+                        "IMDS_ENDPOINT".to_string(),
+                        "http://localhost:40342".to_string(),
+                    ),
+                    ("ProgramData".to_string(), "C:\\ProgramData".to_string()),
+                ]),
+            })
+    }
+
+    fn token_path() -> &'static str {
+        if cfg!(windows) {
+            "C:\\ProgramData\\AzureConnectedMachineAgent\\Tokens\\secret.key"
+        } else {
+            "/var/opt/azcmagent/tokens/secret.key"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_answers_basic_challenge_with_secret_file() {
+        let http = ChallengeHttpSend::new(token_path());
+        let fs = RecordingFileRead::default();
+        let ctx = context(http.clone(), fs.clone());
+
+        let cred = AzureArcCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(matches!(cred, Credential::BearerToken { ref token, .. } if token == "token"));
+        assert_eq!(*fs.paths.lock().unwrap(), vec![token_path().to_string()]);
+        let requests = http.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].0,
+            "http://localhost:40342/metadata/identity/oauth2/token?api-version=2019-11-01&resource=https%3A%2F%2Fstorage.azure.com%2F"
+        );
+        assert_eq!(requests[0].1, None);
+        assert_eq!(requests[1].1.as_deref(), Some("Basic arc-secret"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_secret_outside_token_directory() {
+        for realm in [
+            "/etc/shadow",
+            "/var/opt/azcmagent/tokens/../../../etc/secret.key",
+            "/var/opt/azcmagent/tokens/secret.txt",
+            "C:\\Windows\\secret.key",
+        ] {
+            let fs = RecordingFileRead::default();
+            let ctx = context(ChallengeHttpSend::new(realm), fs.clone());
+
+            let err = AzureArcCredentialProvider::new()
+                .provide_credential(&ctx)
+                .await
+                .unwrap_err();
+
+            assert_eq!(err.kind(), reqsign_core::ErrorKind::Unexpected, "{realm}");
+            assert!(fs.paths.lock().unwrap().is_empty(), "{realm}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_user_assigned_identity() {
+        let ctx = context(
+            ChallengeHttpSend::new(token_path()),
+            RecordingFileRead::default(),
+        );
+
+        let err = AzureArcCredentialProvider::new()
+            .with_identity(UserAssignedIdentity {
+                client_id: Some("client-id".to_string()),
+                ..Default::default()
+            })
+            .provide_credential(&ctx)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::ConfigInvalid);
+    }
+}
@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::constants::STORAGE_SCOPE;
 use crate::credential::Credential;
+use crate::provide_credential::scope_to_resource;
 use reqsign_core::time::Timestamp;
 use reqsign_core::{Context, ProvideCredential};
 use serde::Deserialize;
@@ -25,13 +27,25 @@ use serde::Deserialize;
 /// This provider reads tokens from Azure CLI's local storage or invokes
 /// `az account get-access-token` to retrieve fresh tokens.
 #[derive(Clone, Debug, Default)]
-pub struct AzureCliCredentialProvider {}
+pub struct AzureCliCredentialProvider {
+    scope: Option<String>,
+}
 
 impl AzureCliCredentialProvider {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// The token is requested for the scope's resource: a trailing `.default`
+    /// is removed, so `https://vault.azure.net/.default` requests
+    /// `https://vault.azure.net/`. Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
     /// Execute `az account get-access-token` command
     async fn get_access_token_from_cli(
         &self,
@@ -85,8 +99,7 @@ impl ProvideCredential for AzureCliCredentialProvider {
         &self,
         ctx: &Context,
     ) -> Result<Option<Self::Credential>, reqsign_core::Error> {
-        // For Azure Storage, we need the storage resource
-        let resource = "https://storage.azure.com/";
+        let resource = scope_to_resource(self.scope.as_deref().unwrap_or(STORAGE_SCOPE));
 
         // Try to get access token from Azure CLI
         let token = self.get_access_token_from_cli(ctx, resource).await?;
@@ -0,0 +1,204 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::constants::STORAGE_SCOPE;
+use crate::credential::Credential;
+use crate::provide_credential::{validate_scope, validate_tenant_id};
+use reqsign_core::time::Timestamp;
+use reqsign_core::{Context, ProvideCredential, Result};
+use serde::Deserialize;
+
+/// AzureDeveloperCliCredentialProvider provides credentials from the Azure Developer CLI.
+///
+/// This provider invokes `azd auth token` to retrieve a token for the account
+/// signed in with `azd auth login`.
+///
+/// Reference: <https://learn.microsoft.com/en-us/azure/developer/azure-developer-cli/reference#azd-auth-token>
+#[derive(Clone, Debug, Default)]
+pub struct AzureDeveloperCliCredentialProvider {
+    tenant_id: Option<String>,
+    scope: Option<String>,
+}
+
+impl AzureDeveloperCliCredentialProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the token from a specific tenant instead of the default one.
+    pub fn with_tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureDeveloperCliToken {
+    token: String,
+    expires_on: String,
+}
+
+impl ProvideCredential for AzureDeveloperCliCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let scope = self.scope.as_deref().unwrap_or(STORAGE_SCOPE);
+        validate_scope(scope)?;
+
+        let mut args = vec!["auth", "token", "--output", "json", "--scope", scope];
+        if let Some(tenant_id) = self.tenant_id.as_deref().filter(|v| !v.is_empty()) {
+            validate_tenant_id(tenant_id)?;
+            args.extend(["--tenant-id", tenant_id]);
+        }
+
+        let output = ctx.command_execute("azd", &args).await?;
+        if !output.success() {
+            return Err(reqsign_core::Error::credential_invalid(format!(
+                "Azure Developer CLI command failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let token: AzureDeveloperCliToken =
+            serde_json::from_slice(&output.stdout).map_err(|e| {
+                reqsign_core::Error::unexpected("failed to parse Azure Developer CLI output")
+                    .with_source(e)
+            })?;
+        let expires_on = token.expires_on.parse::<Timestamp>()?;
+
+        Ok(Some(Credential::with_bearer_token(
+            &token.token,
+            Some(expires_on),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqsign_core::{CommandExecute, CommandOutput};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Debug)]
+    struct MockCommandExecute {
+        commands: Arc<Mutex<Vec<Vec<String>>>>,
+        output: CommandOutput,
+    }
+
+    impl MockCommandExecute {
+        fn new(status: i32, stdout: &str, stderr: &str) -> Self {
+            Self {
+                commands: Arc::default(),
+                output: CommandOutput {
+                    status,
+                    stdout: stdout.as_bytes().to_vec(),
+                    stderr: stderr.as_bytes().to_vec(),
+                },
+            }
+        }
+    }
+
+    impl CommandExecute for MockCommandExecute {
+        async fn command_execute(&self, program: &str, args: &[&str]) -> Result<CommandOutput> {
+            let mut command = vec![program.to_string()];
+            command.extend(args.iter().map(|v| v.to_string()));
+            self.commands.lock().unwrap().push(command);
+            Ok(self.output.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_token_from_azd() {
+        let cmd = MockCommandExecute::new(
+            0,
+            r#"{"token":"azd-token","expiresOn":"2024-05-29T22:17:45Z"}"#,
+            "",
+        );
+        let ctx = Context::new().with_command_execute(cmd.clone());
+
+        let cred = AzureDeveloperCliCredentialProvider::new()
+            .with_tenant_id("00000000-0000-0000-0000-000000000000")
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        match cred {
+            Credential::BearerToken { token, expires_in } => {
+                assert_eq!(token, "azd-token");
+                assert_eq!(
+                    expires_in,
+                    Some("2024-05-29T22:17:45Z".parse::<Timestamp>().unwrap())
+                );
+            }
+            _ => panic!("expected bearer token"),
+        }
+        assert_eq!(
+            cmd.commands.lock().unwrap()[0],
+            [
+                "azd",
+                "auth",
+                "token",
+                "--output",
+                "json",
+                "--scope",
+                "https://storage.azure.com/.default",
+                "--tenant-id",
+                "00000000-0000-0000-0000-000000000000",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reports_login_failure() {
+        let cmd =
+            MockCommandExecute::new(1, "", "ERROR: not logged in, run `azd auth login` to login");
+        let ctx = Context::new().with_command_execute(cmd);
+
+        let err = AzureDeveloperCliCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::CredentialInvalid);
+        assert!(err.to_string().contains("azd auth login"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_tenant_id() {
+        let cmd = MockCommandExecute::new(0, "", "");
+        let ctx = Context::new().with_command_execute(cmd.clone());
+
+        let err = AzureDeveloperCliCredentialProvider::new()
+            .with_tenant_id("tenant; rm -rf /")
+            .provide_credential(&ctx)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::ConfigInvalid);
+        assert!(cmd.commands.lock().unwrap().is_empty());
+    }
+}
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::constants::STORAGE_SCOPE;
 use crate::credential::Credential;
 use reqsign_core::time::Timestamp;
 use reqsign_core::{Context, ProvideCredential};
@@ -30,6 +31,7 @@ pub struct AzurePipelinesCredentialProvider {
     tenant_id: Option<String>,
     client_id: Option<String>,
     service_connection_id: Option<String>,
+    scope: Option<String>,
 }
 
 impl AzurePipelinesCredentialProvider {
@@ -55,6 +57,14 @@ impl AzurePipelinesCredentialProvider {
         self
     }
 
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+
     /// Get OIDC token from Azure Pipelines
     async fn get_oidc_token(
         &self,
@@ -112,7 +122,7 @@ impl AzurePipelinesCredentialProvider {
         let url = format!("https://login.microsoftonline.com/{tenant_id}/oauth2/v2.0/token");
 
         let mut params = HashMap::new();
-        params.insert("scope", "https://storage.azure.com/.default");
+        params.insert("scope", self.scope.as_deref().unwrap_or(STORAGE_SCOPE));
         params.insert("client_id", client_id);
         params.insert(
             "client_assertion_type",
@@ -0,0 +1,237 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::constants::STORAGE_SCOPE;
+use crate::credential::Credential;
+use crate::provide_credential::{scope_to_resource, validate_scope, validate_tenant_id};
+use reqsign_core::time::Timestamp;
+use reqsign_core::{Context, ProvideCredential, Result};
+use serde::Deserialize;
+
+/// AzurePowerShellCredentialProvider provides credentials from Azure PowerShell.
+///
+/// This provider invokes `Get-AzAccessToken` through `pwsh` to retrieve a token
+/// for the account signed in with `Connect-AzAccount`. The `Az.Accounts` module
+/// must be installed.
+///
+/// Reference: <https://learn.microsoft.com/en-us/powershell/module/az.accounts/get-azaccesstoken>
+#[derive(Clone, Debug, Default)]
+pub struct AzurePowerShellCredentialProvider {
+    tenant_id: Option<String>,
+    scope: Option<String>,
+}
+
+impl AzurePowerShellCredentialProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the token from a specific tenant instead of the default one.
+    pub fn with_tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// The token is requested for the scope's resource: a trailing `.default`
+    /// is removed, so `https://vault.azure.net/.default` requests
+    /// `https://vault.azure.net/`. Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Build the script run by `pwsh -Command`.
+    ///
+    /// The resource and tenant are validated beforehand, so quoting them in
+    /// single-quoted PowerShell strings is safe.
+    fn script(resource: &str, tenant_id: Option<&str>) -> String {
+        let tenant = match tenant_id {
+            Some(tenant_id) => format!("$params['TenantId'] = '{tenant_id}'\n"),
+            None => String::new(),
+        };
+        format!(
+            "$ErrorActionPreference = 'Stop'
+$params = @{{ ResourceUrl = '{resource}'; WarningAction = 'Ignore' }}
+{tenant}$token = Get-AzAccessToken @params
+$value = $token.Token
+if ($value -is [System.Security.SecureString]) {{
+    $value = ConvertFrom-SecureString -SecureString $value -AsPlainText
+}}
+[pscustomobject]@{{
+    Token = $value
+    ExpiresOn = $token.ExpiresOn.ToUnixTimeSeconds()
+    TenantId = $token.TenantId
+}} | ConvertTo-Json -Compress"
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AzurePowerShellToken {
+    token: String,
+    expires_on: i64,
+    tenant_id: Option<String>,
+}
+
+impl ProvideCredential for AzurePowerShellCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let scope = self.scope.as_deref().unwrap_or(STORAGE_SCOPE);
+        validate_scope(scope)?;
+        let tenant_id = self.tenant_id.as_deref().filter(|v| !v.is_empty());
+        if let Some(tenant_id) = tenant_id {
+            validate_tenant_id(tenant_id)?;
+        }
+
+        let script = Self::script(scope_to_resource(scope), tenant_id);
+        let args = ["-NoProfile", "-NonInteractive", "-Command", &script];
+
+        let output = ctx.command_execute("pwsh", &args).await?;
+        if !output.success() {
+            return Err(reqsign_core::Error::credential_invalid(format!(
+                "Azure PowerShell command failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let token: AzurePowerShellToken = serde_json::from_slice(&output.stdout).map_err(|e| {
+            reqsign_core::Error::unexpected("failed to parse Azure PowerShell output")
+                .with_source(e)
+        })?;
+
+        if let (Some(expected), Some(actual)) = (tenant_id, token.tenant_id.as_deref()) {
+            if !expected.eq_ignore_ascii_case(actual) {
+                return Err(reqsign_core::Error::credential_invalid(format!(
+                    "Azure PowerShell returned a token for tenant {actual}, expected {expected}"
+                )));
+            }
+        }
+
+        let expires_on = Timestamp::from_second(token.expires_on).map_err(|e| {
+            reqsign_core::Error::unexpected(format!("invalid ExpiresOn timestamp: {e}"))
+        })?;
+
+        Ok(Some(Credential::with_bearer_token(
+            &token.token,
+            Some(expires_on),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqsign_core::{CommandExecute, CommandOutput};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Debug)]
+    struct MockCommandExecute {
+        commands: Arc<Mutex<Vec<Vec<String>>>>,
+        output: CommandOutput,
+    }
+
+    impl MockCommandExecute {
+        fn new(stdout: &str) -> Self {
+            Self {
+                commands: Arc::default(),
+                output: CommandOutput {
+                    status: 0,
+                    stdout: stdout.as_bytes().to_vec(),
+                    stderr: Vec::new(),
+                },
+            }
+        }
+    }
+
+    impl CommandExecute for MockCommandExecute {
+        async fn command_execute(&self, program: &str, args: &[&str]) -> Result<CommandOutput> {
+            let mut command = vec![program.to_string()];
+            command.extend(args.iter().map(|v| v.to_string()));
+            self.commands.lock().unwrap().push(command);
+            Ok(self.output.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_token_from_powershell() {
+        let cmd = MockCommandExecute::new(
+            r#"{"Token":"ps-token","ExpiresOn":1700000000,"TenantId":"tenant-a"}"#,
+        );
+        let ctx = Context::new().with_command_execute(cmd.clone());
+
+        let cred = AzurePowerShellCredentialProvider::new()
+            .with_tenant_id("TENANT-A")
+            .with_scope("https://vault.azure.net/.default")
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        match cred {
+            Credential::BearerToken { token, expires_in } => {
+                assert_eq!(token, "ps-token");
+                assert_eq!(
+                    expires_in,
+                    Some(Timestamp::from_second(1_700_000_000).unwrap())
+                );
+            }
+            _ => panic!("expected bearer token"),
+        }
+        let commands = cmd.commands.lock().unwrap();
+        assert_eq!(
+            commands[0][..4],
+            ["pwsh", "-NoProfile", "-NonInteractive", "-Command"]
+        );
+        assert!(commands[0][4].contains("ResourceUrl = 'https://vault.azure.net/'"));
+        assert!(commands[0][4].contains("$params['TenantId'] = 'TENANT-A'"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_token_from_other_tenant() {
+        let cmd = MockCommandExecute::new(
+            r#"{"Token":"ps-token","ExpiresOn":1700000000,"TenantId":"tenant-b"}"#,
+        );
+        let ctx = Context::new().with_command_execute(cmd);
+
+        let err = AzurePowerShellCredentialProvider::new()
+            .with_tenant_id("tenant-a")
+            .provide_credential(&ctx)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::CredentialInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_scope_with_quotes() {
+        let cmd = MockCommandExecute::new("");
+        let ctx = Context::new().with_command_execute(cmd.clone());
+
+        let err = AzurePowerShellCredentialProvider::new()
+            .with_scope("https://storage.azure.com/'; Remove-Item *; '")
+            .provide_credential(&ctx)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::ConfigInvalid);
+        assert!(cmd.commands.lock().unwrap().is_empty());
+    }
+}
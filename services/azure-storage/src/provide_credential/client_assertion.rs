@@ -0,0 +1,233 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
+use crate::provide_credential::client_secret::{
+    ClientAuthentication, authority_host, request_token,
+};
+use reqsign_core::{BoxedFuture, Context, MaybeSend, ProvideCredential, Result};
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+
+/// Supplies the signed JWT a confidential client presents as its
+/// `client_assertion`.
+///
+/// Implement this for assertions signed outside the process, such as by an
+/// HSM or another identity provider. Async closures returning
+/// `Result<String>` implement it as well. The assertion is requested again
+/// every time a token is fetched.
+pub trait ClientAssertion: Send + Sync + 'static {
+    /// Return a fresh client assertion.
+    fn client_assertion(&self, ctx: &Context) -> impl Future<Output = Result<String>> + MaybeSend;
+}
+
+impl<F, Fut> ClientAssertion for F
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<String>> + MaybeSend,
+{
+    fn client_assertion(&self, _: &Context) -> impl Future<Output = Result<String>> + MaybeSend {
+        self()
+    }
+}
+
+/// ClientAssertionDyn is the dyn version of [`ClientAssertion`].
+pub(crate) trait ClientAssertionDyn: Send + Sync + 'static {
+    fn client_assertion_dyn<'a>(&'a self, ctx: &'a Context) -> BoxedFuture<'a, Result<String>>;
+}
+
+impl<T: ClientAssertion> ClientAssertionDyn for T {
+    fn client_assertion_dyn<'a>(&'a self, ctx: &'a Context) -> BoxedFuture<'a, Result<String>> {
+        Box::pin(self.client_assertion(ctx))
+    }
+}
+
+/// Fetch a client assertion and check it is usable.
+pub(crate) async fn fetch_client_assertion(
+    assertion: &dyn ClientAssertionDyn,
+    ctx: &Context,
+) -> Result<String> {
+    let assertion = assertion.client_assertion_dyn(ctx).await?;
+    let assertion = assertion.trim();
+    if assertion.is_empty() {
+        return Err(reqsign_core::Error::credential_invalid(
+            "client assertion callback returned an empty assertion",
+        ));
+    }
+    Ok(assertion.to_string())
+}
+
+/// Load credential with a client assertion from a callback.
+///
+/// The client authenticates to Microsoft Entra ID with a JWT returned by a
+/// [`ClientAssertion`] instead of a secret or a local certificate. Tenant and
+/// client IDs fall back to `AZURE_TENANT_ID` and `AZURE_CLIENT_ID`.
+///
+/// Reference: <https://learn.microsoft.com/en-us/entra/identity-platform/v2-oauth2-client-creds-grant-flow#second-case-access-token-request-with-a-certificate>
+#[derive(Clone)]
+pub struct ClientAssertionCredentialProvider {
+    assertion: Arc<dyn ClientAssertionDyn>,
+    tenant_id: Option<String>,
+    client_id: Option<String>,
+    authority_host: Option<String>,
+    scope: Option<String>,
+}
+
+impl Debug for ClientAssertionCredentialProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientAssertionCredentialProvider")
+            .field("tenant_id", &self.tenant_id)
+            .field("client_id", &self.client_id)
+            .field("authority_host", &self.authority_host)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ClientAssertionCredentialProvider {
+    /// Create a provider that authenticates with assertions from `assertion`.
+    pub fn new(assertion: impl ClientAssertion) -> Self {
+        Self {
+            assertion: Arc::new(assertion),
+            tenant_id: None,
+            client_id: None,
+            authority_host: None,
+            scope: None,
+        }
+    }
+
+    /// Set the tenant ID.
+    pub fn with_tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Set the client ID.
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+
+    /// Set the authority host.
+    pub fn with_authority_host(mut self, authority_host: impl Into<String>) -> Self {
+        self.authority_host = Some(authority_host.into());
+        self
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+}
+
+impl ProvideCredential for ClientAssertionCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let envs = ctx.env_vars();
+
+        let tenant_id = match self
+            .tenant_id
+            .as_ref()
+            .or_else(|| envs.get("AZURE_TENANT_ID"))
+        {
+            Some(id) if !id.is_empty() => id,
+            _ => return Ok(None),
+        };
+
+        let client_id = match self
+            .client_id
+            .as_ref()
+            .or_else(|| envs.get("AZURE_CLIENT_ID"))
+        {
+            Some(id) if !id.is_empty() => id,
+            _ => return Ok(None),
+        };
+
+        let assertion = fetch_client_assertion(self.assertion.as_ref(), ctx).await?;
+        let authority_host = authority_host(self.authority_host.as_deref(), &envs);
+        let scope = self.scope.as_deref().unwrap_or(STORAGE_SCOPE);
+        let credential = request_token(
+            ctx,
+            authority_host,
+            tenant_id,
+            client_id,
+            ClientAuthentication::Assertion(&assertion),
+            &[("scope", scope), ("grant_type", "client_credentials")],
+        )
+        .await?;
+        Ok(Some(credential))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provide_credential::test_util::{RecordingHttpSend, context};
+
+    const TOKEN_RESPONSE: &str =
+        r#"{"access_token":"token","expires_in":3600,"token_type":"Bearer"}"#;
+
+    #[tokio::test]
+    async fn test_callback_assertion_is_sent() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = context(http.clone(), &[("AZURE_TENANT_ID", "tenant")]);
+
+        let provider = ClientAssertionCredentialProvider::new(|| async {
+            Ok("signed.jwt.assertion".to_string())
+        })
+        .with_client_id("client");
+        let cred = provider.provide_credential(&ctx).await.unwrap().unwrap();
+        assert!(matches!(cred, Credential::BearerToken { ref token, .. } if token == "token"));
+
+        assert_eq!(
+            http.uris()[0],
+            "https://login.microsoftonline.com/tenant/oauth2/v2.0/token"
+        );
+        assert_eq!(
+            http.bodies()[0],
+            "client_id=client\
+             &client_assertion_type=urn%3Aietf%3Aparams%3Aoauth%3Aclient-assertion-type%3Ajwt-bearer\
+             &client_assertion=signed.jwt.assertion\
+             &scope=https%3A%2F%2Fstorage.azure.com%2F.default\
+             &grant_type=client_credentials"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_ids_and_empty_assertion() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = Context::new().with_http_send(http.clone());
+
+        let provider = ClientAssertionCredentialProvider::new(|| async { Ok(String::new()) });
+        assert!(provider.provide_credential(&ctx).await.unwrap().is_none());
+
+        let err = provider
+            .with_tenant_id("tenant")
+            .with_client_id("client")
+            .provide_credential(&ctx)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::CredentialInvalid);
+        assert!(http.requests().is_empty());
+    }
+}
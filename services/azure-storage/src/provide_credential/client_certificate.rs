@@ -18,6 +18,7 @@
 use std::collections::HashMap;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::constants::STORAGE_SCOPE;
 use crate::credential::Credential;
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
@@ -49,6 +50,7 @@ pub struct ClientCertificateCredentialProvider {
     client_id: Option<String>,
     certificate_path: Option<String>,
     certificate_password: Option<String>,
+    scope: Option<String>,
 }
 
 impl Default for ClientCertificateCredentialProvider {
@@ -64,6 +66,7 @@ impl ClientCertificateCredentialProvider {
             client_id: None,
             certificate_path: None,
             certificate_password: None,
+            scope: None,
         }
     }
 
@@ -91,6 +94,14 @@ impl ClientCertificateCredentialProvider {
         self
     }
 
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+
     /// Load certificate and private key from file
     async fn load_certificate(
         &self,
@@ -198,7 +209,7 @@ impl ClientCertificateCredentialProvider {
         let url = format!("https://login.microsoftonline.com/{tenant_id}/oauth2/v2.0/token");
 
         let mut params = HashMap::new();
-        params.insert("scope", "https://storage.azure.com/.default");
+        params.insert("scope", self.scope.as_deref().unwrap_or(STORAGE_SCOPE));
         params.insert("client_id", client_id);
         params.insert(
             "client_assertion_type",
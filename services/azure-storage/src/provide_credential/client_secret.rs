@@ -16,8 +16,10 @@
 // under the License.
 
 use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
 use reqsign_core::time::Timestamp;
 use reqsign_core::{Context, ProvideCredential, Result};
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// Load credential from Azure Client Secret.
@@ -33,6 +35,7 @@ pub struct ClientSecretCredentialProvider {
     client_id: Option<String>,
     client_secret: Option<String>,
     authority_host: Option<String>,
+    scope: Option<String>,
 }
 
 impl ClientSecretCredentialProvider {
@@ -64,6 +67,14 @@ impl ClientSecretCredentialProvider {
         self.authority_host = Some(authority_host.into());
         self
     }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
 }
 impl ProvideCredential for ClientSecretCredentialProvider {
     type Credential = Credential;
@@ -99,56 +110,80 @@ impl ProvideCredential for ClientSecretCredentialProvider {
             _ => return Ok(None),
         };
 
-        let authority_host = self
-            .authority_host
-            .as_ref()
-            .or_else(|| envs.get("AZURE_AUTHORITY_HOST"))
-            .filter(|h| !h.is_empty())
-            .map(|s| s.as_str())
-            .unwrap_or("https://login.microsoftonline.com");
-
-        let token =
-            get_client_secret_token(tenant_id, client_id, client_secret, authority_host, ctx)
-                .await?;
-
-        match token {
-            Some(token_response) => {
-                let expires_on = Timestamp::now() + Duration::from_secs(token_response.expires_in);
-                Ok(Some(Credential::with_bearer_token(
-                    &token_response.access_token,
-                    Some(expires_on),
-                )))
-            }
-            None => Ok(None),
-        }
+        let authority_host = authority_host(self.authority_host.as_deref(), &envs);
+        let scope = self.scope.as_deref().unwrap_or(STORAGE_SCOPE);
+        let credential = request_token(
+            ctx,
+            authority_host,
+            tenant_id,
+            client_id,
+            ClientAuthentication::Secret(client_secret),
+            &[("scope", scope), ("grant_type", "client_credentials")],
+        )
+        .await?;
+        Ok(Some(credential))
     }
 }
 
+/// Resolve the Microsoft Entra authority host from config, then
+/// `AZURE_AUTHORITY_HOST`, then the public cloud.
+pub(crate) fn authority_host<'a>(
+    configured: Option<&'a str>,
+    envs: &'a HashMap<String, String>,
+) -> &'a str {
+    configured
+        .or_else(|| envs.get("AZURE_AUTHORITY_HOST").map(|s| s.as_str()))
+        .filter(|h| !h.is_empty())
+        .unwrap_or("https://login.microsoftonline.com")
+}
+
+/// How a confidential client proves its identity to the token endpoint.
+pub(crate) enum ClientAuthentication<'a> {
+    Secret(&'a str),
+    Assertion(&'a str),
+}
+
 #[derive(serde::Deserialize)]
-struct ClientSecretTokenResponse {
+struct TokenResponse {
     access_token: String,
     expires_in: u64,
 }
 
-async fn get_client_secret_token(
+/// Request a token from the Microsoft Entra v2.0 token endpoint.
+///
+/// `params` carries the grant-specific fields, including `grant_type` and
+/// `scope`.
+pub(crate) async fn request_token(
+    ctx: &Context,
+    authority_host: &str,
     tenant_id: &str,
     client_id: &str,
-    client_secret: &str,
-    authority_host: &str,
-    ctx: &Context,
-) -> Result<Option<ClientSecretTokenResponse>> {
+    client_authentication: ClientAuthentication<'_>,
+    params: &[(&str, &str)],
+) -> Result<Credential> {
     let url = format!(
         "{}/{}/oauth2/v2.0/token",
         authority_host.trim_end_matches('/'),
         tenant_id
     );
 
-    let body = form_urlencoded::Serializer::new(String::new())
-        .append_pair("scope", "https://storage.azure.com/.default")
-        .append_pair("client_id", client_id)
-        .append_pair("client_secret", client_secret)
-        .append_pair("grant_type", "client_credentials")
-        .finish();
+    let body = {
+        let mut body = form_urlencoded::Serializer::new(String::new());
+        body.append_pair("client_id", client_id);
+        match client_authentication {
+            ClientAuthentication::Secret(secret) => {
+                body.append_pair("client_secret", secret);
+            }
+            ClientAuthentication::Assertion(assertion) => {
+                body.append_pair(
+                    "client_assertion_type",
+                    "urn:ietf:params:oauth:client-assertion-type:jwt-bearer",
+                )
+                .append_pair("client_assertion", assertion);
+            }
+        }
+        body.extend_pairs(params).finish()
+    };
 
     let req = http::Request::builder()
         .method(http::Method::POST)
@@ -156,7 +191,7 @@ async fn get_client_secret_token(
         .header("Content-Type", "application/x-www-form-urlencoded")
         .body(bytes::Bytes::from(body))
         .map_err(|e| {
-            reqsign_core::Error::unexpected("failed to build client secret request").with_source(e)
+            reqsign_core::Error::unexpected("failed to build token request").with_source(e)
         })?;
 
     let resp = ctx.http_send(req).await?;
@@ -165,12 +200,16 @@ async fn get_client_secret_token(
         let status = resp.status();
         let body = String::from_utf8_lossy(resp.body());
         return Err(reqsign_core::Error::unexpected(format!(
-            "Client secret request failed with status {status}: {body}"
+            "token request failed with status {status}: {body}"
         )));
     }
 
-    let token: ClientSecretTokenResponse = serde_json::from_slice(resp.body()).map_err(|e| {
-        reqsign_core::Error::unexpected("failed to parse client secret response").with_source(e)
+    let token: TokenResponse = serde_json::from_slice(resp.body()).map_err(|e| {
+        reqsign_core::Error::unexpected("failed to parse token response").with_source(e)
     })?;
-    Ok(Some(token))
+    let expires_on = Timestamp::now() + Duration::from_secs(token.expires_in);
+    Ok(Credential::with_bearer_token(
+        &token.access_token,
+        Some(expires_on),
+    ))
 }
@@ -0,0 +1,136 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
+use crate::provide_credential::managed_identity::{
+    UserAssignedIdentity, parse_token_response, response_error,
+};
+use crate::provide_credential::scope_to_resource;
+use http::header::CONTENT_TYPE;
+use reqsign_core::{Context, ProvideCredential, Result};
+
+/// Load credential from the managed identity endpoint of Azure Cloud Shell.
+///
+/// Cloud Shell issues tokens for the signed-in user through the local
+/// endpoint in `MSI_ENDPOINT`. This provider returns `None` when the variable
+/// is missing.
+///
+/// Cloud Shell does not support user-assigned identities.
+///
+/// Reference: <https://learn.microsoft.com/en-us/azure/cloud-shell/msi-authorization>
+#[derive(Debug, Default, Clone)]
+pub struct CloudShellCredentialProvider {
+    identity: UserAssignedIdentity,
+    scope: Option<String>,
+}
+
+impl CloudShellCredentialProvider {
+    /// Create a new Cloud Shell managed identity loader.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// The token is requested for the scope's resource: a trailing `.default`
+    /// is removed, so `https://vault.azure.net/.default` requests
+    /// `https://vault.azure.net/`. Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    pub(crate) fn with_identity(mut self, identity: UserAssignedIdentity) -> Self {
+        self.identity = identity;
+        self
+    }
+}
+
+impl ProvideCredential for CloudShellCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let Some(endpoint) = ctx.env_var("MSI_ENDPOINT").filter(|v| !v.is_empty()) else {
+            return Ok(None);
+        };
+        self.identity.ensure_unset("Cloud Shell")?;
+
+        let resource = scope_to_resource(self.scope.as_deref().unwrap_or(STORAGE_SCOPE));
+        let body = form_urlencoded::Serializer::new(String::new())
+            .append_pair("resource", resource)
+            .finish();
+
+        let req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri(&endpoint)
+            .header("Metadata", "true")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(bytes::Bytes::from(body))
+            .map_err(|e| {
+                reqsign_core::Error::unexpected("failed to build Cloud Shell identity request")
+                    .with_source(e)
+            })?;
+
+        let resp = ctx.http_send(req).await?;
+        if !resp.status().is_success() {
+            return Err(response_error("Cloud Shell", &resp));
+        }
+
+        parse_token_response(resp.body(), "Cloud Shell").map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provide_credential::test_util::RecordingHttpSend;
+    use reqsign_core::StaticEnv;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_posts_resource_to_msi_endpoint() {
+        let http = RecordingHttpSend::new(
+            r#"{"access_token":"token","expires_on":"1700000000","token_type":"Bearer"}"#,
+        );
+        let ctx = Context::new()
+            .with_http_send(http.clone())
+            .with_env(StaticEnv {
+                home_dir: None,
+                envs: HashMap::from([(
+                    "MSI_ENDPOINT".to_string(),
+                    "http://localhost:50342/oauth2/token".to_string(),
+                )]),
+            });
+
+        let cred = CloudShellCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(matches!(cred, Credential::BearerToken { ref token, .. } if token == "token"));
+        let requests = http.requests();
+        assert_eq!(requests[0].method(), http::Method::POST);
+        assert_eq!(requests[0].uri(), "http://localhost:50342/oauth2/token");
+        assert_eq!(requests[0].headers()["Metadata"], "true");
+        assert_eq!(
+            requests[0].body().as_ref(),
+            b"resource=https%3A%2F%2Fstorage.azure.com%2F"
+        );
+    }
+}
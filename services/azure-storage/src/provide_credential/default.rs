@@ -17,10 +17,13 @@
 
 use crate::Credential;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::provide_credential::{AzureCliCredentialProvider, ClientCertificateCredentialProvider};
+use crate::provide_credential::{
+    AzureCliCredentialProvider, AzureDeveloperCliCredentialProvider,
+    AzurePowerShellCredentialProvider, ClientCertificateCredentialProvider,
+};
 use crate::provide_credential::{
     AzurePipelinesCredentialProvider, ClientSecretCredentialProvider, EnvCredentialProvider,
-    ImdsCredentialProvider, WorkloadIdentityCredentialProvider,
+    ImdsCredentialProvider, ManagedIdentityCredentialProvider, WorkloadIdentityCredentialProvider,
 };
 use reqsign_core::{Context, ProvideCredential, ProvideCredentialChain, Result};
 
@@ -33,7 +36,19 @@ use reqsign_core::{Context, ProvideCredential, ProvideCredentialChain, Result};
 /// 4. Client secret (service principal)
 /// 5. Azure Pipelines (workload identity)
 /// 6. Workload identity (federated credentials)
-/// 7. IMDS (Azure VM managed identity)
+/// 7. Managed identity (App Service, Azure Arc or Cloud Shell when detected,
+///    otherwise IMDS)
+/// 8. Azure PowerShell (local development)
+/// 9. Azure Developer CLI (local development)
+///
+/// Azure PowerShell and Azure Developer CLI start a process on every refresh,
+/// so they are tried last and never shadow a configured service principal or
+/// managed identity.
+///
+/// Service Fabric is not used by default: its endpoint needs an HTTP client
+/// that trusts the cluster certificate. Configure the context with such a
+/// client and set the managed identity slot to
+/// `ManagedIdentityCredentialProvider::new().with_service_fabric()`.
 #[derive(Debug)]
 pub struct DefaultCredentialProvider {
     chain: ProvideCredentialChain<Credential>,
@@ -100,16 +115,33 @@ impl DefaultCredentialProvider {
 ///     .no_imds()
 ///     .build();
 /// ```
+///
+/// Use [`DefaultCredentialProviderBuilder::with_scope`] to request bearer
+/// tokens for a resource other than Azure Storage:
+///
+/// ```no_run
+/// use reqsign_azure_storage::DefaultCredentialProvider;
+///
+/// let provider = DefaultCredentialProvider::builder()
+///     .with_scope("https://vault.azure.net/.default")
+///     .build();
+/// ```
 pub struct DefaultCredentialProviderBuilder {
     env: Option<EnvCredentialProvider>,
     #[cfg(not(target_arch = "wasm32"))]
     azure_cli: Option<AzureCliCredentialProvider>,
     #[cfg(not(target_arch = "wasm32"))]
+    azure_powershell: Option<AzurePowerShellCredentialProvider>,
+    #[cfg(not(target_arch = "wasm32"))]
+    azure_developer_cli: Option<AzureDeveloperCliCredentialProvider>,
+    #[cfg(not(target_arch = "wasm32"))]
     client_certificate: Option<ClientCertificateCredentialProvider>,
     client_secret: Option<ClientSecretCredentialProvider>,
     azure_pipelines: Option<AzurePipelinesCredentialProvider>,
     workload_identity: Option<WorkloadIdentityCredentialProvider>,
+    managed_identity: Option<ManagedIdentityCredentialProvider>,
     imds: Option<ImdsCredentialProvider>,
+    scope: Option<String>,
 }
 
 impl Default for DefaultCredentialProviderBuilder {
@@ -119,11 +151,17 @@ impl Default for DefaultCredentialProviderBuilder {
             #[cfg(not(target_arch = "wasm32"))]
             azure_cli: Some(AzureCliCredentialProvider::new()),
             #[cfg(not(target_arch = "wasm32"))]
+            azure_powershell: Some(AzurePowerShellCredentialProvider::new()),
+            #[cfg(not(target_arch = "wasm32"))]
+            azure_developer_cli: Some(AzureDeveloperCliCredentialProvider::new()),
+            #[cfg(not(target_arch = "wasm32"))]
             client_certificate: Some(ClientCertificateCredentialProvider::new()),
             client_secret: Some(ClientSecretCredentialProvider::new()),
             azure_pipelines: Some(AzurePipelinesCredentialProvider::new()),
             workload_identity: Some(WorkloadIdentityCredentialProvider::new()),
+            managed_identity: Some(ManagedIdentityCredentialProvider::new()),
             imds: Some(ImdsCredentialProvider::new()),
+            scope: None,
         }
     }
 }
@@ -133,6 +171,15 @@ impl DefaultCredentialProviderBuilder {
         Self::default()
     }
 
+    /// Set the OAuth 2.0 scope requested by every token provider in the chain.
+    ///
+    /// This replaces any scope set on the individual providers. Defaults to
+    /// `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
     /// Set the environment credential provider slot.
     pub fn env(mut self, provider: EnvCredentialProvider) -> Self {
         self.env = Some(provider);
@@ -159,6 +206,34 @@ impl DefaultCredentialProviderBuilder {
         self
     }
 
+    /// Set the Azure PowerShell credential provider slot.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn azure_powershell(mut self, provider: AzurePowerShellCredentialProvider) -> Self {
+        self.azure_powershell = Some(provider);
+        self
+    }
+
+    /// Remove the Azure PowerShell credential provider slot.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn no_azure_powershell(mut self) -> Self {
+        self.azure_powershell = None;
+        self
+    }
+
+    /// Set the Azure Developer CLI credential provider slot.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn azure_developer_cli(mut self, provider: AzureDeveloperCliCredentialProvider) -> Self {
+        self.azure_developer_cli = Some(provider);
+        self
+    }
+
+    /// Remove the Azure Developer CLI credential provider slot.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn no_azure_developer_cli(mut self) -> Self {
+        self.azure_developer_cli = None;
+        self
+    }
+
     /// Set the client certificate credential provider slot.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn client_certificate(mut self, provider: ClientCertificateCredentialProvider) -> Self {
@@ -209,7 +284,25 @@ impl DefaultCredentialProviderBuilder {
         self
     }
 
+    /// Set the managed identity credential provider slot.
+    ///
+    /// The IMDS slot replaces the provider's own IMDS fallback.
+    pub fn managed_identity(mut self, provider: ManagedIdentityCredentialProvider) -> Self {
+        self.managed_identity = Some(provider);
+        self
+    }
+
+    /// Remove the managed identity credential provider slot.
+    ///
+    /// IMDS is still tried unless its slot is removed as well.
+    pub fn no_managed_identity(mut self) -> Self {
+        self.managed_identity = None;
+        self
+    }
+
     /// Set the Azure IMDS credential provider slot.
+    ///
+    /// IMDS is used when managed identity detects no other hosting platform.
     pub fn imds(mut self, provider: ImdsCredentialProvider) -> Self {
         self.imds = Some(provider);
         self
@@ -222,7 +315,11 @@ impl DefaultCredentialProviderBuilder {
     }
 
     /// Build the `DefaultCredentialProvider` with the configured options.
-    pub fn build(self) -> DefaultCredentialProvider {
+    pub fn build(mut self) -> DefaultCredentialProvider {
+        if let Some(scope) = self.scope.take() {
+            self = self.apply_scope(&scope);
+        }
+
         let mut chain = ProvideCredentialChain::new();
 
         if let Some(p) = self.env {
@@ -252,12 +349,42 @@ impl DefaultCredentialProviderBuilder {
             chain = chain.push(p);
         }
 
-        if let Some(p) = self.imds {
-            chain = chain.push(p);
+        match (self.managed_identity, self.imds) {
+            (Some(p), Some(imds)) => chain = chain.push(p.with_imds(imds)),
+            (Some(p), None) => chain = chain.push(p.without_imds()),
+            (None, Some(imds)) => chain = chain.push(imds),
+            (None, None) => {}
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(p) = self.azure_powershell {
+                chain = chain.push(p);
+            }
+
+            if let Some(p) = self.azure_developer_cli {
+                chain = chain.push(p);
+            }
         }
 
         DefaultCredentialProvider::with_chain(chain)
     }
+
+    fn apply_scope(mut self, scope: &str) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.azure_cli = self.azure_cli.map(|p| p.with_scope(scope));
+            self.azure_powershell = self.azure_powershell.map(|p| p.with_scope(scope));
+            self.azure_developer_cli = self.azure_developer_cli.map(|p| p.with_scope(scope));
+            self.client_certificate = self.client_certificate.map(|p| p.with_scope(scope));
+        }
+        self.client_secret = self.client_secret.map(|p| p.with_scope(scope));
+        self.azure_pipelines = self.azure_pipelines.map(|p| p.with_scope(scope));
+        self.workload_identity = self.workload_identity.map(|p| p.with_scope(scope));
+        self.managed_identity = self.managed_identity.map(|p| p.with_scope(scope));
+        self.imds = self.imds.map(|p| p.with_scope(scope));
+        self
+    }
 }
 impl ProvideCredential for DefaultCredentialProvider {
     type Credential = Credential;
@@ -270,8 +397,10 @@ impl ProvideCredential for DefaultCredentialProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::provide_credential::test_util::RecordingHttpSend;
     use reqsign_core::StaticEnv;
     use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
 
     #[tokio::test]
     async fn test_config_loader_priority() {
@@ -333,6 +462,88 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_builder_scope_applies_to_token_providers() {
+        let env = StaticEnv {
+            home_dir: None,
+            envs: HashMap::from([
+                ("AZURE_TENANT_ID".to_string(), "tenant".to_string()),
+                ("AZURE_CLIENT_ID".to_string(), "client".to_string()),
+                ("AZURE_CLIENT_SECRET".to_string(), "secret".to_string()),
+            ]),
+        };
+        let http = RecordingHttpSend::new(r#"{"access_token":"token","expires_in":3600}"#);
+        let ctx = reqsign_core::Context::new()
+            .with_file_read(MockFileRead)
+            .with_http_send(http.clone())
+            .with_env(env);
+
+        let builder = DefaultCredentialProvider::builder().no_env();
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = builder
+            .no_azure_cli()
+            .no_azure_powershell()
+            .no_azure_developer_cli()
+            .no_client_certificate();
+        let loader = builder
+            .client_secret(
+                ClientSecretCredentialProvider::new().with_scope("https://ignored/.default"),
+            )
+            .with_scope("https://vault.azure.net/.default")
+            .build();
+
+        let cred = loader.provide_credential(&ctx).await.unwrap().unwrap();
+        assert!(matches!(cred, crate::Credential::BearerToken { .. }));
+        let bodies = http.bodies();
+        assert_eq!(bodies.len(), 1);
+        assert!(bodies[0].contains("scope=https%3A%2F%2Fvault.azure.net%2F.default"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn test_developer_tools_do_not_shadow_service_principal() {
+        #[derive(Debug, Clone, Default)]
+        struct RecordingCommandExecute {
+            programs: Arc<Mutex<Vec<String>>>,
+        }
+        impl reqsign_core::CommandExecute for RecordingCommandExecute {
+            async fn command_execute<'a>(
+                &'a self,
+                program: &'a str,
+                _args: &'a [&'a str],
+            ) -> Result<reqsign_core::CommandOutput> {
+                self.programs.lock().unwrap().push(program.to_string());
+                Err(reqsign_core::Error::unexpected("not installed"))
+            }
+        }
+
+        let env = StaticEnv {
+            home_dir: None,
+            envs: HashMap::from([
+                ("AZURE_TENANT_ID".to_string(), "tenant".to_string()),
+                ("AZURE_CLIENT_ID".to_string(), "client".to_string()),
+                ("AZURE_CLIENT_SECRET".to_string(), "secret".to_string()),
+            ]),
+        };
+        let commands = RecordingCommandExecute::default();
+        let ctx = reqsign_core::Context::new()
+            .with_file_read(MockFileRead)
+            .with_http_send(RecordingHttpSend::new(
+                r#"{"access_token":"token","expires_in":3600}"#,
+            ))
+            .with_command_execute(commands.clone())
+            .with_env(env);
+
+        let loader = DefaultCredentialProvider::builder()
+            .no_env()
+            .no_azure_cli()
+            .build();
+
+        let cred = loader.provide_credential(&ctx).await.unwrap().unwrap();
+        assert!(matches!(cred, crate::Credential::BearerToken { .. }));
+        assert!(commands.programs.lock().unwrap().is_empty());
+    }
+
     // Mock implementations for testing
     #[derive(Debug)]
     struct MockFileRead;
@@ -16,6 +16,9 @@
 // under the License.
 
 use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
+use crate::provide_credential::managed_identity::{IdentityKind, UserAssignedIdentity};
+use crate::provide_credential::scope_to_resource;
 use reqsign_core::time::Timestamp;
 use reqsign_core::{Context, ProvideCredential, Result};
 use std::time::Duration;
@@ -25,10 +28,15 @@ use std::time::Duration;
 /// This loader attempts to retrieve an access token from the Azure Instance Metadata Service
 /// which is available on Azure VMs and other Azure compute resources.
 ///
+/// A user-assigned identity is selected by client ID, object ID or resource ID,
+/// falling back to `AZURE_OBJECT_ID`, `AZURE_CLIENT_ID` and `AZURE_MSI_RES_ID`.
+///
 /// Reference: <https://learn.microsoft.com/en-us/azure/app-service/overview-managed-identity?tabs=portal,http#using-the-rest-protocol>
 #[derive(Debug, Default, Clone)]
 pub struct ImdsCredentialProvider {
     endpoint: Option<String>,
+    identity: UserAssignedIdentity,
+    scope: Option<String>,
 }
 
 impl ImdsCredentialProvider {
@@ -42,12 +50,48 @@ impl ImdsCredentialProvider {
         self.endpoint = Some(endpoint.into());
         self
     }
+
+    /// Select a user-assigned identity by client ID.
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.identity.client_id = Some(client_id.into());
+        self
+    }
+
+    /// Select a user-assigned identity by object ID.
+    pub fn with_object_id(mut self, object_id: impl Into<String>) -> Self {
+        self.identity.object_id = Some(object_id.into());
+        self
+    }
+
+    /// Select a user-assigned identity by Azure resource ID.
+    pub fn with_msi_res_id(mut self, msi_res_id: impl Into<String>) -> Self {
+        self.identity.msi_res_id = Some(msi_res_id.into());
+        self
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// The token is requested for the scope's resource: a trailing `.default`
+    /// is removed, so `https://vault.azure.net/.default` requests
+    /// `https://vault.azure.net/`. Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    pub(crate) fn with_identity(mut self, identity: UserAssignedIdentity) -> Self {
+        self.identity = identity;
+        self
+    }
 }
+
 impl ProvideCredential for ImdsCredentialProvider {
     type Credential = Credential;
 
     async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
-        let token = get_access_token("https://storage.azure.com/", ctx).await?;
+        let scope = self.scope.as_deref().unwrap_or(STORAGE_SCOPE);
+        let identity = self.identity.resolve(ctx)?;
+        let token = get_access_token(scope_to_resource(scope), identity, ctx).await?;
 
         let expires_on = if token.expires_on.is_empty() {
             Timestamp::now() + Duration::from_secs(600)
@@ -75,7 +119,11 @@ struct AccessTokenResponse {
     expires_on: String,
 }
 
-async fn get_access_token(resource: &str, ctx: &Context) -> Result<AccessTokenResponse> {
+async fn get_access_token(
+    resource: &str,
+    identity: Option<(IdentityKind, String)>,
+    ctx: &Context,
+) -> Result<AccessTokenResponse> {
     let envs = ctx.env_vars();
 
     let endpoint = envs
@@ -85,16 +133,21 @@ async fn get_access_token(resource: &str, ctx: &Context) -> Result<AccessTokenRe
         .map(|s| s.as_str())
         .unwrap_or("http://169.254.169.254/metadata/identity/oauth2/token");
 
-    let mut url = format!("{endpoint}?api-version=2018-02-01&resource={resource}");
-
-    // Add identity parameters if specified in environment
-    if let Some(object_id) = envs.get("AZURE_OBJECT_ID").filter(|s| !s.is_empty()) {
-        url.push_str(&format!("&object_id={object_id}"));
-    } else if let Some(client_id) = envs.get("AZURE_CLIENT_ID").filter(|s| !s.is_empty()) {
-        url.push_str(&format!("&client_id={client_id}"));
-    } else if let Some(msi_res_id) = envs.get("AZURE_MSI_RES_ID").filter(|s| !s.is_empty()) {
-        url.push_str(&format!("&msi_res_id={msi_res_id}"));
-    }
+    let url = {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        query
+            .append_pair("api-version", "2018-02-01")
+            .append_pair("resource", resource);
+        if let Some((kind, value)) = identity {
+            let name = match kind {
+                IdentityKind::Client => "client_id",
+                IdentityKind::Object => "object_id",
+                IdentityKind::Resource => "msi_res_id",
+            };
+            query.append_pair(name, &value);
+        }
+        format!("{endpoint}?{}", query.finish())
+    };
 
     let mut req = http::Request::builder()
         .method(http::Method::GET)
@@ -129,6 +182,27 @@ async fn get_access_token(resource: &str, ctx: &Context) -> Result<AccessTokenRe
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::provide_credential::test_util::RecordingHttpSend;
+
+    #[tokio::test]
+    async fn test_scope_selects_imds_resource() {
+        let http = RecordingHttpSend::new(r#"{"access_token":"token","expires_on":"1506484173"}"#);
+        let ctx = Context::new().with_http_send(http.clone());
+
+        ImdsCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+        ImdsCredentialProvider::new()
+            .with_scope("https://vault.azure.net/.default")
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+
+        let uris = http.uris();
+        assert!(uris[0].ends_with("&resource=https%3A%2F%2Fstorage.azure.com%2F"));
+        assert!(uris[1].ends_with("&resource=https%3A%2F%2Fvault.azure.net%2F"));
+    }
 
     #[test]
     fn test_parse_standard_imds_response() {
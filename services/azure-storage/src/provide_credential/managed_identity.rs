@@ -0,0 +1,540 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::time::Duration;
+
+use crate::Credential;
+use crate::provide_credential::{
+    AppServiceCredentialProvider, AzureArcCredentialProvider, CloudShellCredentialProvider,
+    ImdsCredentialProvider, ServiceFabricCredentialProvider,
+};
+use log::debug;
+use reqsign_core::time::Timestamp;
+use reqsign_core::{Context, ProvideCredential, Result};
+use serde::Deserialize;
+
+/// The hosting protocol used to obtain a managed identity token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ManagedIdentitySource {
+    /// Azure App Service, Azure Functions and Azure Container Apps.
+    AppService,
+    /// Azure Service Fabric.
+    ServiceFabric,
+    /// Azure Arc-enabled servers.
+    AzureArc,
+    /// Azure Cloud Shell.
+    CloudShell,
+    /// Azure Instance Metadata Service on Azure VMs.
+    Imds,
+}
+
+impl ManagedIdentitySource {
+    /// Detect the managed identity source from the environment.
+    ///
+    /// Hosting platforms announce themselves through environment variables.
+    /// When none of them is set, the source is [`ManagedIdentitySource::Imds`].
+    pub fn detect(ctx: &Context) -> Self {
+        let is_set = |key: &str| ctx.env_var(key).is_some_and(|v| !v.is_empty());
+
+        if is_set("IDENTITY_ENDPOINT") {
+            if is_set("IDENTITY_HEADER") {
+                if is_set("IDENTITY_SERVER_THUMBPRINT") {
+                    return Self::ServiceFabric;
+                }
+                return Self::AppService;
+            }
+            if is_set("IMDS_ENDPOINT") {
+                return Self::AzureArc;
+            }
+        }
+        if is_set("MSI_ENDPOINT") {
+            return Self::CloudShell;
+        }
+        Self::Imds
+    }
+}
+
+/// Load credential from whichever Azure managed identity source is available.
+///
+/// The source is chosen by [`ManagedIdentitySource::detect`]: App Service,
+/// Service Fabric, Azure Arc and Cloud Shell are recognized from their
+/// environment variables, and IMDS is used everywhere else. The IMDS fallback
+/// can be replaced with [`ManagedIdentityCredentialProvider::with_imds`] or
+/// disabled with [`ManagedIdentityCredentialProvider::without_imds`].
+///
+/// A user-assigned identity is only supported by App Service and IMDS. Other
+/// sources reject an explicitly configured identity.
+///
+/// Service Fabric serves its endpoint with a self-signed cluster certificate
+/// that the default HTTP client does not trust. It is therefore skipped, and
+/// this provider returns `None`, unless enabled with
+/// [`ManagedIdentityCredentialProvider::with_service_fabric`].
+///
+/// Reference: <https://learn.microsoft.com/en-us/entra/identity/managed-identities-azure-resources/overview>
+#[derive(Debug, Clone)]
+pub struct ManagedIdentityCredentialProvider {
+    identity: UserAssignedIdentity,
+    scope: Option<String>,
+    imds: Option<ImdsCredentialProvider>,
+    service_fabric: bool,
+}
+
+impl Default for ManagedIdentityCredentialProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManagedIdentityCredentialProvider {
+    /// Create a new managed identity loader.
+    pub fn new() -> Self {
+        Self {
+            identity: UserAssignedIdentity::default(),
+            scope: None,
+            imds: Some(ImdsCredentialProvider::new()),
+            service_fabric: false,
+        }
+    }
+
+    /// Select a user-assigned identity by client ID.
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.identity.client_id = Some(client_id.into());
+        self
+    }
+
+    /// Select a user-assigned identity by object ID.
+    pub fn with_object_id(mut self, object_id: impl Into<String>) -> Self {
+        self.identity.object_id = Some(object_id.into());
+        self
+    }
+
+    /// Select a user-assigned identity by Azure resource ID.
+    pub fn with_msi_res_id(mut self, msi_res_id: impl Into<String>) -> Self {
+        self.identity.msi_res_id = Some(msi_res_id.into());
+        self
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// The token is requested for the scope's resource: a trailing `.default`
+    /// is removed, so `https://vault.azure.net/.default` requests
+    /// `https://vault.azure.net/`. Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Set the IMDS provider used when no hosting platform is detected.
+    pub fn with_imds(mut self, provider: ImdsCredentialProvider) -> Self {
+        self.imds = Some(provider);
+        self
+    }
+
+    /// Return `None` instead of calling IMDS when no hosting platform is detected.
+    pub fn without_imds(mut self) -> Self {
+        self.imds = None;
+        self
+    }
+
+    /// Use the Service Fabric endpoint when it is detected.
+    ///
+    /// Only enable this when the [`reqsign_core::HttpSend`] configured on the
+    /// context trusts the cluster certificate whose thumbprint is
+    /// `IDENTITY_SERVER_THUMBPRINT`.
+    pub fn with_service_fabric(mut self) -> Self {
+        self.service_fabric = true;
+        self
+    }
+}
+
+impl ProvideCredential for ManagedIdentityCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let identity = self.identity.clone();
+        let scope = self.scope.as_deref();
+        match ManagedIdentitySource::detect(ctx) {
+            ManagedIdentitySource::AppService => {
+                with_scope(
+                    AppServiceCredentialProvider::new().with_identity(identity),
+                    scope,
+                )
+                .provide_credential(ctx)
+                .await
+            }
+            ManagedIdentitySource::ServiceFabric if !self.service_fabric => {
+                debug!("skipping Service Fabric managed identity because it is not enabled");
+                Ok(None)
+            }
+            ManagedIdentitySource::ServiceFabric => {
+                with_scope(
+                    ServiceFabricCredentialProvider::new().with_identity(identity),
+                    scope,
+                )
+                .provide_credential(ctx)
+                .await
+            }
+            ManagedIdentitySource::AzureArc => {
+                with_scope(
+                    AzureArcCredentialProvider::new().with_identity(identity),
+                    scope,
+                )
+                .provide_credential(ctx)
+                .await
+            }
+            ManagedIdentitySource::CloudShell => {
+                with_scope(
+                    CloudShellCredentialProvider::new().with_identity(identity),
+                    scope,
+                )
+                .provide_credential(ctx)
+                .await
+            }
+            ManagedIdentitySource::Imds => {
+                let Some(imds) = &self.imds else {
+                    return Ok(None);
+                };
+                // Keep the identity configured on the IMDS provider unless one
+                // was set here.
+                let imds = if identity.is_set() {
+                    imds.clone().with_identity(identity)
+                } else {
+                    imds.clone()
+                };
+                with_scope(imds, scope).provide_credential(ctx).await
+            }
+        }
+    }
+}
+
+/// Apply the scope configured on [`ManagedIdentityCredentialProvider`], if any.
+fn with_scope<P: ScopedProvider>(provider: P, scope: Option<&str>) -> P {
+    match scope {
+        Some(scope) => provider.scope(scope),
+        None => provider,
+    }
+}
+
+trait ScopedProvider {
+    fn scope(self, scope: &str) -> Self;
+}
+
+macro_rules! impl_scoped_provider {
+    ($($provider:ty),*) => {
+        $(
+            impl ScopedProvider for $provider {
+                fn scope(self, scope: &str) -> Self {
+                    self.with_scope(scope)
+                }
+            }
+        )*
+    };
+}
+
+impl_scoped_provider!(
+    AppServiceCredentialProvider,
+    ServiceFabricCredentialProvider,
+    AzureArcCredentialProvider,
+    CloudShellCredentialProvider,
+    ImdsCredentialProvider
+);
+
+/// Which identifier selects a user-assigned identity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum IdentityKind {
+    Client,
+    Object,
+    Resource,
+}
+
+/// User-assigned identity selection shared by managed identity sources.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct UserAssignedIdentity {
+    pub(crate) client_id: Option<String>,
+    pub(crate) object_id: Option<String>,
+    pub(crate) msi_res_id: Option<String>,
+}
+
+impl UserAssignedIdentity {
+    /// Whether any identifier was configured explicitly.
+    pub(crate) fn is_set(&self) -> bool {
+        [&self.client_id, &self.object_id, &self.msi_res_id]
+            .into_iter()
+            .any(|v| v.as_ref().is_some_and(|v| !v.is_empty()))
+    }
+
+    /// Resolve the configured identity, falling back to `AZURE_OBJECT_ID`,
+    /// `AZURE_CLIENT_ID` and `AZURE_MSI_RES_ID`.
+    pub(crate) fn resolve(&self, ctx: &Context) -> Result<Option<(IdentityKind, String)>> {
+        let configured = [
+            (IdentityKind::Client, &self.client_id),
+            (IdentityKind::Object, &self.object_id),
+            (IdentityKind::Resource, &self.msi_res_id),
+        ]
+        .into_iter()
+        .filter_map(|(kind, value)| {
+            value
+                .as_ref()
+                .filter(|v| !v.is_empty())
+                .map(|v| (kind, v.clone()))
+        })
+        .collect::<Vec<_>>();
+        if configured.len() > 1 {
+            return Err(reqsign_core::Error::config_invalid(
+                "managed identity accepts only one of client_id, object_id and msi_res_id",
+            ));
+        }
+        if let Some(identity) = configured.into_iter().next() {
+            return Ok(Some(identity));
+        }
+
+        let envs = ctx.env_vars();
+        Ok([
+            (IdentityKind::Object, "AZURE_OBJECT_ID"),
+            (IdentityKind::Client, "AZURE_CLIENT_ID"),
+            (IdentityKind::Resource, "AZURE_MSI_RES_ID"),
+        ]
+        .into_iter()
+        .find_map(|(kind, key)| {
+            envs.get(key)
+                .filter(|v| !v.is_empty())
+                .map(|v| (kind, v.clone()))
+        }))
+    }
+
+    /// Reject an explicitly configured identity for a source that only
+    /// supports its system-assigned identity.
+    pub(crate) fn ensure_unset(&self, source: &str) -> Result<()> {
+        if self.is_set() {
+            return Err(reqsign_core::Error::config_invalid(format!(
+                "{source} managed identity does not support user-assigned identities"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct ManagedIdentityTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_on: Option<ExpiresOn>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExpiresOn {
+    Seconds(i64),
+    Text(String),
+}
+
+/// Parse a managed identity token response into a bearer credential.
+///
+/// `expires_on` may be Unix seconds as a number or a string. A missing value
+/// is treated as ten minutes from now.
+pub(crate) fn parse_token_response(body: &[u8], source: &str) -> Result<Credential> {
+    let token: ManagedIdentityTokenResponse = serde_json::from_slice(body).map_err(|e| {
+        reqsign_core::Error::unexpected(format!("failed to parse {source} identity response"))
+            .with_source(e)
+    })?;
+
+    let seconds = match token.expires_on {
+        Some(ExpiresOn::Seconds(seconds)) => Some(seconds),
+        Some(ExpiresOn::Text(text)) if !text.is_empty() => Some(text.parse().map_err(|e| {
+            reqsign_core::Error::unexpected("failed to parse expires_on timestamp").with_source(e)
+        })?),
+        _ => None,
+    };
+    let expires_on = match seconds {
+        Some(seconds) => Timestamp::from_second(seconds).map_err(|e| {
+            reqsign_core::Error::unexpected(format!("invalid expires_on timestamp: {e}"))
+        })?,
+        None => Timestamp::now() + Duration::from_secs(600),
+    };
+
+    Ok(Credential::with_bearer_token(
+        &token.access_token,
+        Some(expires_on),
+    ))
+}
+
+/// Build an error for a non-success managed identity response.
+pub(crate) fn response_error(
+    source: &str,
+    resp: &http::Response<bytes::Bytes>,
+) -> reqsign_core::Error {
+    let status = resp.status();
+    let body = String::from_utf8_lossy(resp.body());
+    reqsign_core::Error::unexpected(format!(
+        "{source} identity request failed with status {status}: {body}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provide_credential::test_util::{RecordingHttpSend, context};
+
+    const TOKEN_RESPONSE: &str = r#"{"access_token":"token","expires_on":"1700000000"}"#;
+
+    #[test]
+    fn test_detect_source() {
+        let cases: &[(&[(&str, &str)], ManagedIdentitySource)] = &[
+            (&[], ManagedIdentitySource::Imds),
+            (
+                &[("IDENTITY_ENDPOINT", "http://e"), ("IDENTITY_HEADER", "h")],
+                ManagedIdentitySource::AppService,
+            ),
+            (
+                &[
+                    ("IDENTITY_ENDPOINT", "https://e"),
+                    ("IDENTITY_HEADER", "h"),
+                    ("IDENTITY_SERVER_THUMBPRINT", "t"),
+                ],
+                ManagedIdentitySource::ServiceFabric,
+            ),
+            (
+                &[
+                    ("IDENTITY_ENDPOINT", "http://e"),
+                    ("IMDS_ENDPOINT", "http://i"),
+                ],
+                ManagedIdentitySource::AzureArc,
+            ),
+            (
+                &[("MSI_ENDPOINT", "http://e")],
+                ManagedIdentitySource::CloudShell,
+            ),
+            (
+                &[("IDENTITY_ENDPOINT", "http://e")],
+                ManagedIdentitySource::Imds,
+            ),
+        ];
+
+        for (envs, expected) in cases {
+            let ctx = context(RecordingHttpSend::new(TOKEN_RESPONSE), envs);
+            assert_eq!(ManagedIdentitySource::detect(&ctx), *expected, "{envs:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatches_to_detected_source() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = context(
+            http.clone(),
+            &[
+                ("IDENTITY_ENDPOINT", "http://localhost:42356/msi/token"),
+                ("IDENTITY_HEADER", "secret-header"),
+            ],
+        );
+
+        let cred = ManagedIdentityCredentialProvider::new()
+            .with_object_id("object-id")
+            .with_scope("https://vault.azure.net/.default")
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+
+        assert!(cred.is_some());
+        assert_eq!(
+            http.uris()[0],
+            "http://localhost:42356/msi/token?api-version=2019-08-01&resource=https%3A%2F%2Fvault.azure.net%2F&object_id=object-id"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_imds_fallback() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = context(http.clone(), &[]);
+
+        let cred = ManagedIdentityCredentialProvider::new()
+            .without_imds()
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+        assert!(cred.is_none());
+        assert!(http.uris().is_empty());
+
+        let cred = ManagedIdentityCredentialProvider::new()
+            .with_imds(ImdsCredentialProvider::new().with_client_id("imds-client"))
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+        assert!(cred.is_some());
+        assert!(http.uris()[0].starts_with(
+            "http://169.254.169.254/metadata/identity/oauth2/token?api-version=2018-02-01&"
+        ));
+        assert!(http.uris()[0].ends_with("&client_id=imds-client"));
+    }
+
+    #[tokio::test]
+    async fn test_service_fabric_requires_opt_in() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = context(
+            http.clone(),
+            &[
+                (
+                    "IDENTITY_ENDPOINT",
+                    "https://localhost:2377/metadata/identity/oauth2/token",
+                ),
+                ("IDENTITY_HEADER", "secret-header"),
+                ("IDENTITY_SERVER_THUMBPRINT", "thumbprint"),
+            ],
+        );
+
+        let cred = ManagedIdentityCredentialProvider::new()
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+        assert!(cred.is_none());
+        assert!(http.uris().is_empty());
+
+        let cred = ManagedIdentityCredentialProvider::new()
+            .with_service_fabric()
+            .provide_credential(&ctx)
+            .await
+            .unwrap();
+        assert!(cred.is_some());
+        assert!(http.uris()[0].starts_with("https://localhost:2377/"));
+    }
+
+    #[test]
+    fn test_parse_token_response_expires_on() {
+        let numeric =
+            parse_token_response(br#"{"access_token":"a","expires_on":1700000000}"#, "test")
+                .unwrap();
+        let text =
+            parse_token_response(br#"{"access_token":"a","expires_on":"1700000000"}"#, "test")
+                .unwrap();
+        for cred in [numeric, text] {
+            match cred {
+                Credential::BearerToken { expires_in, .. } => assert_eq!(
+                    expires_in,
+                    Some(Timestamp::from_second(1_700_000_000).unwrap())
+                ),
+                _ => panic!("expected bearer token"),
+            }
+        }
+
+        let missing = parse_token_response(br#"{"access_token":"a"}"#, "test").unwrap();
+        match missing {
+            Credential::BearerToken { expires_in, .. } => {
+                assert!(expires_in.unwrap() > Timestamp::now())
+            }
+            _ => panic!("expected bearer token"),
+        }
+    }
+}
@@ -18,12 +18,69 @@
 mod env;
 pub use env::EnvCredentialProvider;
 
+#[cfg(test)]
+mod test_util;
+
+/// Convert a `.default` scope into the resource URI used by resource-based
+/// token endpoints, keeping the trailing slash.
+pub(crate) fn scope_to_resource(scope: &str) -> &str {
+    match scope.strip_suffix(".default") {
+        Some(resource) if resource.ends_with('/') => resource,
+        _ => scope,
+    }
+}
+
+/// Check a tenant ID before passing it to a developer tool.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn validate_tenant_id(tenant_id: &str) -> reqsign_core::Result<()> {
+    if tenant_id.is_empty()
+        || !tenant_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+    {
+        return Err(reqsign_core::Error::config_invalid(format!(
+            "invalid tenant id: {tenant_id}"
+        )));
+    }
+    Ok(())
+}
+
+/// Check a scope before passing it to a developer tool.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn validate_scope(scope: &str) -> reqsign_core::Result<()> {
+    if scope.is_empty()
+        || !scope
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/'))
+    {
+        return Err(reqsign_core::Error::config_invalid(format!(
+            "invalid scope: {scope}"
+        )));
+    }
+    Ok(())
+}
+
 mod static_provider;
 pub use static_provider::StaticCredentialProvider;
 
 mod default;
 pub use default::{DefaultCredentialProvider, DefaultCredentialProviderBuilder};
 
+mod managed_identity;
+pub use managed_identity::{ManagedIdentityCredentialProvider, ManagedIdentitySource};
+
+mod app_service;
+pub use app_service::AppServiceCredentialProvider;
+
+mod service_fabric;
+pub use service_fabric::ServiceFabricCredentialProvider;
+
+mod azure_arc;
+pub use azure_arc::AzureArcCredentialProvider;
+
+mod cloud_shell;
+pub use cloud_shell::CloudShellCredentialProvider;
+
 mod imds;
 pub use imds::ImdsCredentialProvider;
 
@@ -33,11 +90,27 @@ pub use workload_identity::WorkloadIdentityCredentialProvider;
 mod client_secret;
 pub use client_secret::ClientSecretCredentialProvider;
 
+mod client_assertion;
+pub use client_assertion::{ClientAssertion, ClientAssertionCredentialProvider};
+
+mod on_behalf_of;
+pub use on_behalf_of::OnBehalfOfCredentialProvider;
+
 #[cfg(not(target_arch = "wasm32"))]
 mod azure_cli;
 #[cfg(not(target_arch = "wasm32"))]
 pub use azure_cli::AzureCliCredentialProvider;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod azure_developer_cli;
+#[cfg(not(target_arch = "wasm32"))]
+pub use azure_developer_cli::AzureDeveloperCliCredentialProvider;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod azure_powershell;
+#[cfg(not(target_arch = "wasm32"))]
+pub use azure_powershell::AzurePowerShellCredentialProvider;
+
 #[cfg(not(target_arch = "wasm32"))]
 mod client_certificate;
 #[cfg(not(target_arch = "wasm32"))]
@@ -0,0 +1,259 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
+use crate::provide_credential::client_assertion::{
+    ClientAssertion, ClientAssertionDyn, fetch_client_assertion,
+};
+use crate::provide_credential::client_secret::{
+    ClientAuthentication, authority_host, request_token,
+};
+use reqsign_core::{Context, ProvideCredential, Result};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+#[derive(Clone)]
+enum ClientCredential {
+    Secret(String),
+    Assertion(Arc<dyn ClientAssertionDyn>),
+}
+
+/// Load credential with the OAuth 2.0 on-behalf-of flow.
+///
+/// A middle-tier API exchanges the access token it received from a caller
+/// for a storage token issued to the same user, so requests run with that
+/// user's permissions. The API authenticates with a client secret or a
+/// [`ClientAssertion`]; tenant and client IDs and the secret fall back to
+/// `AZURE_TENANT_ID`, `AZURE_CLIENT_ID` and `AZURE_CLIENT_SECRET`.
+///
+/// The user assertion is fixed per provider, so build one provider and signer
+/// per incoming user token.
+///
+/// Reference: <https://learn.microsoft.com/en-us/entra/identity-platform/v2-oauth2-on-behalf-of-flow>
+#[derive(Clone)]
+pub struct OnBehalfOfCredentialProvider {
+    user_assertion: String,
+    client_credential: Option<ClientCredential>,
+    tenant_id: Option<String>,
+    client_id: Option<String>,
+    authority_host: Option<String>,
+    scope: Option<String>,
+}
+
+impl Debug for OnBehalfOfCredentialProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OnBehalfOfCredentialProvider")
+            .field("tenant_id", &self.tenant_id)
+            .field("client_id", &self.client_id)
+            .field("authority_host", &self.authority_host)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OnBehalfOfCredentialProvider {
+    /// Create a provider that exchanges `user_assertion`, the access token
+    /// the caller presented to this API.
+    pub fn new(user_assertion: impl Into<String>) -> Self {
+        Self {
+            user_assertion: user_assertion.into(),
+            client_credential: None,
+            tenant_id: None,
+            client_id: None,
+            authority_host: None,
+            scope: None,
+        }
+    }
+
+    /// Authenticate the API with a client secret.
+    pub fn with_client_secret(mut self, client_secret: impl Into<String>) -> Self {
+        self.client_credential = Some(ClientCredential::Secret(client_secret.into()));
+        self
+    }
+
+    /// Authenticate the API with assertions from `assertion`.
+    pub fn with_client_assertion(mut self, assertion: impl ClientAssertion) -> Self {
+        self.client_credential = Some(ClientCredential::Assertion(Arc::new(assertion)));
+        self
+    }
+
+    /// Set the tenant ID.
+    pub fn with_tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Set the client ID.
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+
+    /// Set the authority host.
+    pub fn with_authority_host(mut self, authority_host: impl Into<String>) -> Self {
+        self.authority_host = Some(authority_host.into());
+        self
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+}
+
+impl ProvideCredential for OnBehalfOfCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        if self.user_assertion.is_empty() {
+            return Err(reqsign_core::Error::config_invalid(
+                "on-behalf-of user assertion must not be empty",
+            ));
+        }
+
+        let envs = ctx.env_vars();
+
+        let tenant_id = match self
+            .tenant_id
+            .as_ref()
+            .or_else(|| envs.get("AZURE_TENANT_ID"))
+        {
+            Some(id) if !id.is_empty() => id,
+            _ => return Ok(None),
+        };
+
+        let client_id = match self
+            .client_id
+            .as_ref()
+            .or_else(|| envs.get("AZURE_CLIENT_ID"))
+        {
+            Some(id) if !id.is_empty() => id,
+            _ => return Ok(None),
+        };
+
+        let client_assertion;
+        let client_authentication = match &self.client_credential {
+            Some(ClientCredential::Secret(secret)) if !secret.is_empty() => {
+                ClientAuthentication::Secret(secret)
+            }
+            Some(ClientCredential::Assertion(assertion)) => {
+                client_assertion = fetch_client_assertion(assertion.as_ref(), ctx).await?;
+                ClientAuthentication::Assertion(&client_assertion)
+            }
+            _ => match envs.get("AZURE_CLIENT_SECRET") {
+                Some(secret) if !secret.is_empty() => ClientAuthentication::Secret(secret),
+                _ => return Ok(None),
+            },
+        };
+
+        let authority_host = authority_host(self.authority_host.as_deref(), &envs);
+        let scope = self.scope.as_deref().unwrap_or(STORAGE_SCOPE);
+        let credential = request_token(
+            ctx,
+            authority_host,
+            tenant_id,
+            client_id,
+            client_authentication,
+            &[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", &self.user_assertion),
+                ("scope", scope),
+                ("requested_token_use", "on_behalf_of"),
+            ],
+        )
+        .await?;
+        Ok(Some(credential))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provide_credential::test_util::RecordingHttpSend;
+
+    const TOKEN_RESPONSE: &str =
+        r#"{"access_token":"user-token","expires_in":3600,"token_type":"Bearer"}"#;
+
+    #[tokio::test]
+    async fn test_exchanges_user_assertion() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = Context::new().with_http_send(http.clone());
+
+        let cred = OnBehalfOfCredentialProvider::new("incoming.user.token")
+            .with_tenant_id("tenant")
+            .with_client_id("api")
+            .with_client_secret("secret")
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(cred, Credential::BearerToken { ref token, .. } if token == "user-token"));
+
+        OnBehalfOfCredentialProvider::new("incoming.user.token")
+            .with_tenant_id("tenant")
+            .with_client_id("api")
+            .with_client_assertion(|| async { Ok("api.assertion".to_string()) })
+            .with_scope("https://vault.azure.net/.default")
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let bodies = http.bodies();
+        assert_eq!(
+            bodies[0],
+            "client_id=api&client_secret=secret\
+             &grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Ajwt-bearer\
+             &assertion=incoming.user.token\
+             &scope=https%3A%2F%2Fstorage.azure.com%2F.default\
+             &requested_token_use=on_behalf_of"
+        );
+        assert_eq!(
+            bodies[1],
+            "client_id=api\
+             &client_assertion_type=urn%3Aietf%3Aparams%3Aoauth%3Aclient-assertion-type%3Ajwt-bearer\
+             &client_assertion=api.assertion\
+             &grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Ajwt-bearer\
+             &assertion=incoming.user.token\
+             &scope=https%3A%2F%2Fvault.azure.net%2F.default\
+             &requested_token_use=on_behalf_of"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_requires_client_credential_and_user_assertion() {
+        let http = RecordingHttpSend::new(TOKEN_RESPONSE);
+        let ctx = Context::new().with_http_send(http.clone());
+
+        let provider = OnBehalfOfCredentialProvider::new("incoming.user.token")
+            .with_tenant_id("tenant")
+            .with_client_id("api");
+        assert!(provider.provide_credential(&ctx).await.unwrap().is_none());
+
+        let err = OnBehalfOfCredentialProvider::new("")
+            .with_client_secret("secret")
+            .provide_credential(&ctx)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::ConfigInvalid);
+        assert!(http.requests().is_empty());
+    }
+}
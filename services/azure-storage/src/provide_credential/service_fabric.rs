@@ -0,0 +1,173 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
+use crate::provide_credential::managed_identity::{
+    UserAssignedIdentity, parse_token_response, response_error,
+};
+use crate::provide_credential::scope_to_resource;
+use http::HeaderValue;
+use reqsign_core::{Context, ProvideCredential, Result};
+
+const API_VERSION: &str = "2019-07-01-preview";
+
+/// Load credential from the managed identity endpoint of Azure Service Fabric.
+///
+/// Service Fabric applications get managed identity through
+/// `IDENTITY_ENDPOINT`, `IDENTITY_HEADER` and `IDENTITY_SERVER_THUMBPRINT`.
+/// This provider returns `None` when any of them is missing.
+///
+/// The endpoint is served over HTTPS with a self-signed cluster certificate
+/// whose thumbprint is `IDENTITY_SERVER_THUMBPRINT`. The [`reqsign_core::HttpSend`]
+/// configured on the context must be set up to trust that certificate, which
+/// is why [`crate::ManagedIdentityCredentialProvider`] only uses this provider
+/// after `with_service_fabric()`.
+///
+/// Service Fabric only supports the identity assigned to the application.
+///
+/// Reference: <https://learn.microsoft.com/en-us/azure/service-fabric/how-to-managed-identity-service-fabric-app-code>
+#[derive(Debug, Default, Clone)]
+pub struct ServiceFabricCredentialProvider {
+    identity: UserAssignedIdentity,
+    scope: Option<String>,
+}
+
+impl ServiceFabricCredentialProvider {
+    /// Create a new Service Fabric managed identity loader.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// The token is requested for the scope's resource: a trailing `.default`
+    /// is removed, so `https://vault.azure.net/.default` requests
+    /// `https://vault.azure.net/`. Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    pub(crate) fn with_identity(mut self, identity: UserAssignedIdentity) -> Self {
+        self.identity = identity;
+        self
+    }
+}
+
+impl ProvideCredential for ServiceFabricCredentialProvider {
+    type Credential = Credential;
+
+    async fn provide_credential(&self, ctx: &Context) -> Result<Option<Self::Credential>> {
+        let (Some(endpoint), Some(identity_header), Some(_)) = (
+            ctx.env_var("IDENTITY_ENDPOINT").filter(|v| !v.is_empty()),
+            ctx.env_var("IDENTITY_HEADER").filter(|v| !v.is_empty()),
+            ctx.env_var("IDENTITY_SERVER_THUMBPRINT")
+                .filter(|v| !v.is_empty()),
+        ) else {
+            return Ok(None);
+        };
+        self.identity.ensure_unset("Service Fabric")?;
+
+        let resource = scope_to_resource(self.scope.as_deref().unwrap_or(STORAGE_SCOPE));
+        let url = {
+            let mut query = form_urlencoded::Serializer::new(String::new());
+            query
+                .append_pair("api-version", API_VERSION)
+                .append_pair("resource", resource);
+            format!("{endpoint}?{}", query.finish())
+        };
+
+        let mut header_value = HeaderValue::from_str(&identity_header).map_err(|e| {
+            reqsign_core::Error::config_invalid("IDENTITY_HEADER is not a valid header value")
+                .with_source(e)
+        })?;
+        header_value.set_sensitive(true);
+
+        let req = http::Request::builder()
+            .method(http::Method::GET)
+            .uri(&url)
+            .header("secret", header_value)
+            .body(bytes::Bytes::new())
+            .map_err(|e| {
+                reqsign_core::Error::unexpected("failed to build Service Fabric identity request")
+                    .with_source(e)
+            })?;
+
+        let resp = ctx.http_send(req).await?;
+        if !resp.status().is_success() {
+            return Err(response_error("Service Fabric", &resp));
+        }
+
+        parse_token_response(resp.body(), "Service Fabric").map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provide_credential::test_util::RecordingHttpSend;
+    use reqsign_core::StaticEnv;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_sends_secret_header() {
+        let http = RecordingHttpSend::new(
+            r#"{"token_type":"Bearer","access_token":"token","expires_on":1700000000,"resource":"https://storage.azure.com/"}"#,
+        );
+        let ctx = Context::new()
+            .with_http_send(http.clone())
+            .with_env(StaticEnv {
+                home_dir: None,
+                envs: HashMap::from([
+                    (
+                        "IDENTITY_ENDPOINT".to_string(),
+                        "https://10.0.0.4:2377/metadata/identity/oauth2/token".to_string(),
+                    ),
+                    ("IDENTITY_HEADER".to_string(), "sf-secret".to_string()),
+                    (
+                        "IDENTITY_SERVER_THUMBPRINT".to_string(),
+                        "0123456789ABCDEF".to_string(),
+                    ),
+                ]),
+            });
+
+        let cred = ServiceFabricCredentialProvider::new()
+            .with_scope("https://vault.azure.net/.default")
+            .provide_credential(&ctx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        match cred {
+            Credential::BearerToken { token, expires_in } => {
+                assert_eq!(token, "token");
+                assert_eq!(
+                    expires_in,
+                    Some(reqsign_core::time::Timestamp::from_second(1_700_000_000).unwrap())
+                );
+            }
+            _ => panic!("expected bearer token"),
+        }
+        let requests = http.requests();
+        assert_eq!(
+            requests[0].uri(),
+            "https://10.0.0.4:2377/metadata/identity/oauth2/token?api-version=2019-07-01-preview&resource=https%3A%2F%2Fvault.azure.net%2F"
+        );
+        assert_eq!(requests[0].headers()["secret"], "sf-secret");
+    }
+}
@@ -0,0 +1,80 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Shared fixtures for credential provider tests.
+
+use bytes::Bytes;
+use reqsign_core::{Context, HttpSend, Result, StaticEnv};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// An [`HttpSend`] that records every request and answers with a fixed body.
+#[derive(Clone, Debug)]
+pub(crate) struct RecordingHttpSend {
+    body: &'static str,
+    requests: Arc<Mutex<Vec<http::Request<Bytes>>>>,
+}
+
+impl RecordingHttpSend {
+    /// Answer every request with `body` and status `200 OK`.
+    pub(crate) fn new(body: &'static str) -> Self {
+        Self {
+            body,
+            requests: Arc::default(),
+        }
+    }
+
+    /// All requests sent so far.
+    pub(crate) fn requests(&self) -> MutexGuard<'_, Vec<http::Request<Bytes>>> {
+        self.requests.lock().unwrap()
+    }
+
+    /// The URIs of all requests sent so far.
+    pub(crate) fn uris(&self) -> Vec<String> {
+        self.requests()
+            .iter()
+            .map(|req| req.uri().to_string())
+            .collect()
+    }
+
+    /// The bodies of all requests sent so far.
+    pub(crate) fn bodies(&self) -> Vec<String> {
+        self.requests()
+            .iter()
+            .map(|req| String::from_utf8_lossy(req.body()).into_owned())
+            .collect()
+    }
+}
+
+impl HttpSend for RecordingHttpSend {
+    async fn http_send(&self, req: http::Request<Bytes>) -> Result<http::Response<Bytes>> {
+        self.requests.lock().unwrap().push(req);
+        Ok(http::Response::new(Bytes::from_static(
+            self.body.as_bytes(),
+        )))
+    }
+}
+
+/// A context that sends through `http` and reads the given environment variables.
+pub(crate) fn context(http: RecordingHttpSend, envs: &[(&str, &str)]) -> Context {
+    Context::new().with_http_send(http).with_env(StaticEnv {
+        home_dir: None,
+        envs: envs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    })
+}
@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::Credential;
+use crate::constants::STORAGE_SCOPE;
 use reqsign_core::time::Timestamp;
 use reqsign_core::{Context, ProvideCredential, Result};
 use std::time::Duration;
@@ -33,6 +34,7 @@ pub struct WorkloadIdentityCredentialProvider {
     client_id: Option<String>,
     federated_token_file: Option<String>,
     authority_host: Option<String>,
+    scope: Option<String>,
 }
 
 impl WorkloadIdentityCredentialProvider {
@@ -64,6 +66,14 @@ impl WorkloadIdentityCredentialProvider {
         self.authority_host = Some(authority_host.into());
         self
     }
+
+    /// Set the OAuth 2.0 scope to request.
+    ///
+    /// Defaults to `https://storage.azure.com/.default`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
 }
 impl ProvideCredential for WorkloadIdentityCredentialProvider {
     type Credential = Credential;
@@ -112,6 +122,7 @@ impl ProvideCredential for WorkloadIdentityCredentialProvider {
             client_id,
             federated_token_file,
             authority_host,
+            self.scope.as_deref().unwrap_or(STORAGE_SCOPE),
             ctx,
         )
         .await?;
@@ -147,6 +158,7 @@ async fn get_workload_identity_token(
     client_id: &str,
     federated_token_file: &str,
     authority_host: &str,
+    scope: &str,
     ctx: &Context,
 ) -> Result<Option<WorkloadIdentityTokenResponse>> {
     // Read the federated token from file
@@ -169,7 +181,7 @@ async fn get_workload_identity_token(
     );
 
     let body = form_urlencoded::Serializer::new(String::new())
-        .append_pair("scope", "https://storage.azure.com/.default")
+        .append_pair("scope", scope)
         .append_pair("client_id", client_id)
         .append_pair(
             "client_assertion_type",
@@ -29,7 +29,10 @@ use std::time::Duration;
 
 const SERVICE_SAS_VERSION: &str = "2020-12-06";
 const BLOB_SERVICE: &str = "blob";
-const STORAGE_ACCOUNT_KEY_LENGTH: usize = 64;
+const FILE_SERVICE: &str = "file";
+const QUEUE_SERVICE: &str = "queue";
+const TABLE_SERVICE: &str = "table";
+pub(crate) const STORAGE_ACCOUNT_KEY_LENGTH: usize = 64;
 const SUPPORTED_SERVICE_SAS_VERSIONS: &[&str] = &[
     "2020-12-06",
     "2021-02-12",
@@ -57,13 +60,28 @@ const SUPPORTED_SERVICE_SAS_VERSIONS: &[&str] = &[
     "2026-10-06",
 ];
 
-/// Resource level for Azure Blob Storage Service SAS.
+/// Resource level for an Azure Storage Service SAS.
+///
+/// New resource levels may be added in minor releases, so matches need a
+/// wildcard arm.
 #[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ServiceSasResource {
     /// A container resource.
     Container { container: String },
     /// A blob resource.
     Blob { container: String, blob: String },
+    /// An Azure Files share.
+    Share { share: String },
+    /// A file in an Azure Files share, given as a `/`-separated path.
+    File { share: String, path: String },
+    /// A queue and its messages.
+    Queue { queue: String },
+    /// A table, optionally limited to a range of entities.
+    Table {
+        table: String,
+        range: ServiceSasTableRange,
+    },
 }
 
 impl Debug for ServiceSasResource {
@@ -96,10 +114,14 @@ impl ServiceSasResource {
         }
     }
 
-    pub(crate) fn signed_resource(&self) -> &'static str {
+    /// Return the `sr` value, which Queue and Table SAS do not carry.
+    pub(crate) fn signed_resource(&self) -> Option<&'static str> {
         match self {
-            ServiceSasResource::Container { .. } => "c",
-            ServiceSasResource::Blob { .. } => "b",
+            ServiceSasResource::Container { .. } => Some("c"),
+            ServiceSasResource::Blob { .. } => Some("b"),
+            ServiceSasResource::Share { .. } => Some("s"),
+            ServiceSasResource::File { .. } => Some("f"),
+            ServiceSasResource::Queue { .. } | ServiceSasResource::Table { .. } => None,
         }
     }
 
@@ -111,10 +133,104 @@ impl ServiceSasResource {
             ServiceSasResource::Blob { container, blob } => {
                 format!("/{BLOB_SERVICE}/{account}/{container}/{blob}")
             }
+            ServiceSasResource::Share { share } => format!("/{FILE_SERVICE}/{account}/{share}"),
+            ServiceSasResource::File { share, path } => {
+                format!("/{FILE_SERVICE}/{account}/{share}/{path}")
+            }
+            ServiceSasResource::Queue { queue } => format!("/{QUEUE_SERVICE}/{account}/{queue}"),
+            ServiceSasResource::Table { table, .. } => {
+                format!("/{TABLE_SERVICE}/{account}/{}", table.to_ascii_lowercase())
+            }
         }
     }
 }
 
+/// Inclusive range of entities reachable with a Table Storage Service SAS.
+///
+/// Unset bounds leave that side of the range open. A row key bound is only
+/// meaningful together with the partition key bound on the same side.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ServiceSasTableRange {
+    start_partition_key: Option<String>,
+    start_row_key: Option<String>,
+    end_partition_key: Option<String>,
+    end_row_key: Option<String>,
+}
+
+impl ServiceSasTableRange {
+    /// Create an unbounded range covering the whole table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the smallest partition key (`spk`).
+    pub fn with_start_partition_key(mut self, partition_key: impl Into<String>) -> Self {
+        self.start_partition_key = Some(partition_key.into());
+        self
+    }
+
+    /// Set the smallest row key within the starting partition (`srk`).
+    pub fn with_start_row_key(mut self, row_key: impl Into<String>) -> Self {
+        self.start_row_key = Some(row_key.into());
+        self
+    }
+
+    /// Set the largest partition key (`epk`).
+    pub fn with_end_partition_key(mut self, partition_key: impl Into<String>) -> Self {
+        self.end_partition_key = Some(partition_key.into());
+        self
+    }
+
+    /// Set the largest row key within the ending partition (`erk`).
+    pub fn with_end_row_key(mut self, row_key: impl Into<String>) -> Self {
+        self.end_row_key = Some(row_key.into());
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.start_row_key.is_some() && self.start_partition_key.is_none() {
+            return Err(Error::request_invalid(
+                "Azure Table SAS start row key requires a start partition key",
+            ));
+        }
+        if self.end_row_key.is_some() && self.end_partition_key.is_none() {
+            return Err(Error::request_invalid(
+                "Azure Table SAS end row key requires an end partition key",
+            ));
+        }
+        for key in [
+            &self.start_partition_key,
+            &self.start_row_key,
+            &self.end_partition_key,
+            &self.end_row_key,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            validate_table_key(key)?;
+        }
+        Ok(())
+    }
+
+    fn query_pairs(&self) -> Vec<(String, String)> {
+        [
+            ("spk", &self.start_partition_key),
+            ("srk", &self.start_row_key),
+            ("epk", &self.end_partition_key),
+            ("erk", &self.end_row_key),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.clone().map(|value| (name.to_string(), value)))
+        .collect()
+    }
+}
+
+impl Debug for ServiceSasTableRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ServiceSasTableRange(REDACTED)")
+    }
+}
+
 /// Permissions for a container-scoped Blob Storage Service SAS.
 ///
 /// Combining values with `|` produces Azure's canonical permission order.
@@ -163,7 +279,7 @@ impl ServiceSasContainerPermissions {
         self.0 & other.0 == other.0
     }
 
-    fn as_canonical_string(self) -> String {
+    pub(crate) fn as_canonical_string(self) -> String {
         [
             (Self::READ, 'r'),
             (Self::ADD, 'a'),
@@ -293,6 +409,245 @@ impl BitOrAssign for ServiceSasBlobPermissions {
     }
 }
 
+/// Permissions for a share-scoped Azure Files Service SAS.
+///
+/// Combining values with `|` produces Azure's canonical permission order.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServiceSasSharePermissions(u16);
+
+impl ServiceSasSharePermissions {
+    /// Read files in the share.
+    pub const READ: Self = Self(1 << 0);
+    /// Create files in the share.
+    pub const CREATE: Self = Self(1 << 1);
+    /// Write file content, properties and metadata.
+    pub const WRITE: Self = Self(1 << 2);
+    /// Delete files in the share.
+    pub const DELETE: Self = Self(1 << 3);
+    /// List files and directories in the share.
+    pub const LIST: Self = Self(1 << 4);
+
+    /// Return whether no permission is selected.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return whether all permissions in `other` are selected.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn as_canonical_string(self) -> String {
+        [
+            (Self::READ, 'r'),
+            (Self::CREATE, 'c'),
+            (Self::WRITE, 'w'),
+            (Self::DELETE, 'd'),
+            (Self::LIST, 'l'),
+        ]
+        .into_iter()
+        .filter_map(|(permission, symbol)| self.contains(permission).then_some(symbol))
+        .collect()
+    }
+}
+
+impl Debug for ServiceSasSharePermissions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ServiceSasSharePermissions(REDACTED)")
+    }
+}
+
+impl BitOr for ServiceSasSharePermissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ServiceSasSharePermissions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Permissions for a file-scoped Azure Files Service SAS.
+///
+/// Combining values with `|` produces Azure's canonical permission order.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServiceSasFilePermissions(u16);
+
+impl ServiceSasFilePermissions {
+    /// Read the file.
+    pub const READ: Self = Self(1 << 0);
+    /// Create the file.
+    pub const CREATE: Self = Self(1 << 1);
+    /// Write file content, properties and metadata.
+    pub const WRITE: Self = Self(1 << 2);
+    /// Delete the file.
+    pub const DELETE: Self = Self(1 << 3);
+
+    /// Return whether no permission is selected.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return whether all permissions in `other` are selected.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn as_canonical_string(self) -> String {
+        [
+            (Self::READ, 'r'),
+            (Self::CREATE, 'c'),
+            (Self::WRITE, 'w'),
+            (Self::DELETE, 'd'),
+        ]
+        .into_iter()
+        .filter_map(|(permission, symbol)| self.contains(permission).then_some(symbol))
+        .collect()
+    }
+}
+
+impl Debug for ServiceSasFilePermissions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ServiceSasFilePermissions(REDACTED)")
+    }
+}
+
+impl BitOr for ServiceSasFilePermissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ServiceSasFilePermissions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Permissions for a Queue Storage Service SAS.
+///
+/// Combining values with `|` produces Azure's canonical permission order.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServiceSasQueuePermissions(u16);
+
+impl ServiceSasQueuePermissions {
+    /// Read and peek messages and queue metadata.
+    pub const READ: Self = Self(1 << 0);
+    /// Add messages to the queue.
+    pub const ADD: Self = Self(1 << 1);
+    /// Update messages in the queue.
+    pub const UPDATE: Self = Self(1 << 2);
+    /// Get and delete messages from the queue.
+    pub const PROCESS: Self = Self(1 << 3);
+
+    /// Return whether no permission is selected.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return whether all permissions in `other` are selected.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn as_canonical_string(self) -> String {
+        [
+            (Self::READ, 'r'),
+            (Self::ADD, 'a'),
+            (Self::UPDATE, 'u'),
+            (Self::PROCESS, 'p'),
+        ]
+        .into_iter()
+        .filter_map(|(permission, symbol)| self.contains(permission).then_some(symbol))
+        .collect()
+    }
+}
+
+impl Debug for ServiceSasQueuePermissions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ServiceSasQueuePermissions(REDACTED)")
+    }
+}
+
+impl BitOr for ServiceSasQueuePermissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ServiceSasQueuePermissions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Permissions for a Table Storage Service SAS.
+///
+/// Combining values with `|` produces Azure's canonical permission order.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServiceSasTablePermissions(u16);
+
+impl ServiceSasTablePermissions {
+    /// Query entities.
+    pub const READ: Self = Self(1 << 0);
+    /// Insert entities.
+    pub const ADD: Self = Self(1 << 1);
+    /// Update and merge entities.
+    pub const UPDATE: Self = Self(1 << 2);
+    /// Delete entities.
+    pub const DELETE: Self = Self(1 << 3);
+
+    /// Return whether no permission is selected.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return whether all permissions in `other` are selected.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn as_canonical_string(self) -> String {
+        [
+            (Self::READ, 'r'),
+            (Self::ADD, 'a'),
+            (Self::UPDATE, 'u'),
+            (Self::DELETE, 'd'),
+        ]
+        .into_iter()
+        .filter_map(|(permission, symbol)| self.contains(permission).then_some(symbol))
+        .collect()
+    }
+}
+
+impl Debug for ServiceSasTablePermissions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ServiceSasTablePermissions(REDACTED)")
+    }
+}
+
+impl BitOr for ServiceSasTablePermissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ServiceSasTablePermissions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
 #[derive(Clone)]
 enum ServiceSasGrantResource {
     Container {
@@ -304,12 +659,35 @@ enum ServiceSasGrantResource {
         blob: String,
         permissions: ServiceSasBlobPermissions,
     },
+    Share {
+        share: String,
+        permissions: ServiceSasSharePermissions,
+    },
+    File {
+        share: String,
+        path: String,
+        permissions: ServiceSasFilePermissions,
+    },
+    Queue {
+        queue: String,
+        permissions: ServiceSasQueuePermissions,
+    },
+    Table {
+        table: String,
+        range: ServiceSasTableRange,
+        permissions: ServiceSasTablePermissions,
+    },
 }
 
-/// One complete, typed Blob Storage Service SAS grant.
+/// One complete, typed Service SAS grant for Blob, Files, Queue or Table Storage.
+///
+/// A grant can reference a stored access policy with
+/// [`ServiceSasGrant::with_signed_identifier`], so the SAS can be revoked by
+/// changing or removing the policy on the service.
 #[derive(Clone)]
 pub struct ServiceSasGrant {
     resource: ServiceSasGrantResource,
+    signed_identifier: Option<String>,
 }
 
 impl Debug for ServiceSasGrant {
@@ -329,6 +707,7 @@ impl ServiceSasGrant {
                 container: container.into(),
                 permissions,
             },
+            signed_identifier: None,
         }
     }
 
@@ -344,46 +723,198 @@ impl ServiceSasGrant {
                 blob: blob.into(),
                 permissions,
             },
+            signed_identifier: None,
+        }
+    }
+
+    /// Grant permissions over one Azure Files share and its files.
+    pub fn for_share(share: impl Into<String>, permissions: ServiceSasSharePermissions) -> Self {
+        Self {
+            resource: ServiceSasGrantResource::Share {
+                share: share.into(),
+                permissions,
+            },
+            signed_identifier: None,
+        }
+    }
+
+    /// Grant permissions over one file in an Azure Files share.
+    ///
+    /// The path is percent-decoded and uses `/` between directories.
+    pub fn for_file(
+        share: impl Into<String>,
+        path: impl Into<String>,
+        permissions: ServiceSasFilePermissions,
+    ) -> Self {
+        Self {
+            resource: ServiceSasGrantResource::File {
+                share: share.into(),
+                path: path.into(),
+                permissions,
+            },
+            signed_identifier: None,
+        }
+    }
+
+    /// Grant permissions over one queue and its messages.
+    pub fn for_queue(queue: impl Into<String>, permissions: ServiceSasQueuePermissions) -> Self {
+        Self {
+            resource: ServiceSasGrantResource::Queue {
+                queue: queue.into(),
+                permissions,
+            },
+            signed_identifier: None,
+        }
+    }
+
+    /// Grant permissions over every entity in one table.
+    pub fn for_table(table: impl Into<String>, permissions: ServiceSasTablePermissions) -> Self {
+        Self::for_table_range(table, ServiceSasTableRange::new(), permissions)
+    }
+
+    /// Grant permissions over a range of entities in one table.
+    pub fn for_table_range(
+        table: impl Into<String>,
+        range: ServiceSasTableRange,
+        permissions: ServiceSasTablePermissions,
+    ) -> Self {
+        Self {
+            resource: ServiceSasGrantResource::Table {
+                table: table.into(),
+                range,
+                permissions,
+            },
+            signed_identifier: None,
         }
     }
 
+    /// Reference a stored access policy (`si`) on the container, share, queue
+    /// or table.
+    ///
+    /// Fields defined by the policy must be omitted from the SAS: permissions
+    /// may be empty when the policy supplies them, and [`ServiceSasGranter`]
+    /// leaves out the expiry when no lifetime is requested.
+    pub fn with_signed_identifier(mut self, identifier: impl Into<String>) -> Self {
+        self.signed_identifier = Some(identifier.into());
+        self
+    }
+
     fn validate(&self) -> Result<ValidatedGrant> {
+        if let Some(identifier) = &self.signed_identifier {
+            validate_signed_identifier(identifier)?;
+        }
+        // A stored access policy may supply the permissions instead.
+        let policy_permissions = self.signed_identifier.is_some();
         match &self.resource {
             ServiceSasGrantResource::Container {
                 container,
                 permissions,
             } => {
-                validate_container(container)?;
-                if permissions.is_empty() {
+                validate_container(container)?;
+                if permissions.is_empty() && !policy_permissions {
+                    return Err(Error::request_invalid(
+                        "Azure Service SAS container permissions must not be empty",
+                    ));
+                }
+                Ok(ValidatedGrant {
+                    resource: ServiceSasResource::Container {
+                        container: container.clone(),
+                    },
+                    permissions: permissions.as_canonical_string(),
+                    signed_identifier: self.signed_identifier.clone(),
+                })
+            }
+            ServiceSasGrantResource::Blob {
+                container,
+                blob,
+                permissions,
+            } => {
+                validate_container(container)?;
+                validate_blob(blob)?;
+                if permissions.is_empty() && !policy_permissions {
+                    return Err(Error::request_invalid(
+                        "Azure Service SAS blob permissions must not be empty",
+                    ));
+                }
+                Ok(ValidatedGrant {
+                    resource: ServiceSasResource::Blob {
+                        container: container.clone(),
+                        blob: blob.clone(),
+                    },
+                    permissions: permissions.as_canonical_string(),
+                    signed_identifier: self.signed_identifier.clone(),
+                })
+            }
+            ServiceSasGrantResource::Share { share, permissions } => {
+                validate_share_or_queue(share, "Azure Files share")?;
+                if permissions.is_empty() && !policy_permissions {
+                    return Err(Error::request_invalid(
+                        "Azure Service SAS share permissions must not be empty",
+                    ));
+                }
+                Ok(ValidatedGrant {
+                    resource: ServiceSasResource::Share {
+                        share: share.clone(),
+                    },
+                    permissions: permissions.as_canonical_string(),
+                    signed_identifier: self.signed_identifier.clone(),
+                })
+            }
+            ServiceSasGrantResource::File {
+                share,
+                path,
+                permissions,
+            } => {
+                validate_share_or_queue(share, "Azure Files share")?;
+                validate_file_path(path)?;
+                if permissions.is_empty() && !policy_permissions {
                     return Err(Error::request_invalid(
-                        "Azure Service SAS container permissions must not be empty",
+                        "Azure Service SAS file permissions must not be empty",
                     ));
                 }
                 Ok(ValidatedGrant {
-                    resource: ServiceSasResource::Container {
-                        container: container.clone(),
+                    resource: ServiceSasResource::File {
+                        share: share.clone(),
+                        path: path.clone(),
                     },
                     permissions: permissions.as_canonical_string(),
+                    signed_identifier: self.signed_identifier.clone(),
                 })
             }
-            ServiceSasGrantResource::Blob {
-                container,
-                blob,
+            ServiceSasGrantResource::Queue { queue, permissions } => {
+                validate_share_or_queue(queue, "Azure Queue Storage queue")?;
+                if permissions.is_empty() && !policy_permissions {
+                    return Err(Error::request_invalid(
+                        "Azure Service SAS queue permissions must not be empty",
+                    ));
+                }
+                Ok(ValidatedGrant {
+                    resource: ServiceSasResource::Queue {
+                        queue: queue.clone(),
+                    },
+                    permissions: permissions.as_canonical_string(),
+                    signed_identifier: self.signed_identifier.clone(),
+                })
+            }
+            ServiceSasGrantResource::Table {
+                table,
+                range,
                 permissions,
             } => {
-                validate_container(container)?;
-                validate_blob(blob)?;
-                if permissions.is_empty() {
+                validate_table(table)?;
+                range.validate()?;
+                if permissions.is_empty() && !policy_permissions {
                     return Err(Error::request_invalid(
-                        "Azure Service SAS blob permissions must not be empty",
+                        "Azure Service SAS table permissions must not be empty",
                     ));
                 }
                 Ok(ValidatedGrant {
-                    resource: ServiceSasResource::Blob {
-                        container: container.clone(),
-                        blob: blob.clone(),
+                    resource: ServiceSasResource::Table {
+                        table: table.clone(),
+                        range: range.clone(),
                     },
                     permissions: permissions.as_canonical_string(),
+                    signed_identifier: self.signed_identifier.clone(),
                 })
             }
         }
@@ -393,6 +924,7 @@ impl ServiceSasGrant {
 struct ValidatedGrant {
     resource: ServiceSasResource,
     permissions: String,
+    signed_identifier: Option<String>,
 }
 
 impl Debug for ValidatedGrant {
@@ -489,7 +1021,7 @@ impl Debug for ServiceSasVersion {
     }
 }
 
-/// Stable configuration for locally generating one Blob Storage Service SAS.
+/// Stable configuration for locally generating one Service SAS or account SAS.
 #[derive(Clone)]
 pub struct ServiceSasConfig {
     account: String,
@@ -544,7 +1076,7 @@ impl ServiceSasConfig {
         self
     }
 
-    fn validate(&self) -> Result<ValidatedConfig> {
+    pub(crate) fn validate(&self) -> Result<ValidatedConfig> {
         validate_account(&self.account)?;
         Ok(ValidatedConfig {
             account: self.account.clone(),
@@ -556,12 +1088,12 @@ impl ServiceSasConfig {
     }
 }
 
-struct ValidatedConfig {
-    account: String,
-    start: Option<Timestamp>,
-    ip: Option<String>,
-    protocol: SasProtocol,
-    version: ServiceSasVersion,
+pub(crate) struct ValidatedConfig {
+    pub(crate) account: String,
+    pub(crate) start: Option<Timestamp>,
+    pub(crate) ip: Option<String>,
+    pub(crate) protocol: SasProtocol,
+    pub(crate) version: ServiceSasVersion,
 }
 
 fn validate_account(account: &str) -> Result<()> {
@@ -577,7 +1109,7 @@ fn validate_account(account: &str) -> Result<()> {
     Ok(())
 }
 
-fn validate_container(container: &str) -> Result<()> {
+pub(crate) fn validate_container(container: &str) -> Result<()> {
     if matches!(container, "$root" | "$web" | "$logs") {
         return Ok(());
     }
@@ -616,8 +1148,85 @@ fn validate_blob(blob: &str) -> Result<()> {
     Ok(())
 }
 
+fn validate_share_or_queue(name: &str, kind: &str) -> Result<()> {
+    if !(3..=63).contains(&name.len())
+        || !name
+            .bytes()
+            .all(|value| value.is_ascii_lowercase() || value.is_ascii_digit() || value == b'-')
+        || !name
+            .as_bytes()
+            .first()
+            .is_some_and(u8::is_ascii_alphanumeric)
+        || !name
+            .as_bytes()
+            .last()
+            .is_some_and(u8::is_ascii_alphanumeric)
+        || name.contains("--")
+    {
+        return Err(Error::request_invalid(format!("invalid {kind} name")));
+    }
+    Ok(())
+}
+
+fn validate_file_path(path: &str) -> Result<()> {
+    if path.is_empty()
+        || path.len() > 2048
+        || path.starts_with('/')
+        || path.split('/').any(|segment| segment.is_empty())
+        || path.chars().any(|value| {
+            value.is_control() || matches!(value, '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+        })
+    {
+        return Err(Error::request_invalid(
+            "file path must be a non-empty percent-decoded relative path without empty segments or reserved characters",
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_signed_identifier(identifier: &str) -> Result<()> {
+    if identifier.is_empty()
+        || identifier.chars().count() > 64
+        || identifier.chars().any(char::is_control)
+    {
+        return Err(Error::request_invalid(
+            "stored access policy identifier must be 1-64 characters without control characters",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_table(table: &str) -> Result<()> {
+    if !(3..=63).contains(&table.len())
+        || !table.bytes().all(|value| value.is_ascii_alphanumeric())
+        || !table.as_bytes()[0].is_ascii_alphabetic()
+        || table.eq_ignore_ascii_case("tables")
+    {
+        return Err(Error::request_invalid(
+            "invalid Azure Table Storage table name",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_table_key(key: &str) -> Result<()> {
+    if key.len() > 1024
+        || key
+            .chars()
+            .any(|value| value.is_control() || matches!(value, '/' | '\\' | '#' | '?'))
+    {
+        return Err(Error::request_invalid(
+            "Azure Table SAS partition and row keys must not contain '/', '\\', '#', '?' or control characters",
+        ));
+    }
+    Ok(())
+}
+
 /// Service SAS generator using Shared Key.
 ///
+/// The string-to-sign follows the layout of the resource's service: Blob,
+/// Files, Queue or Table Storage.
+///
 /// Reference: <https://learn.microsoft.com/en-us/rest/api/storageservices/create-service-sas>
 pub struct ServiceSharedAccessSignature {
     account: String,
@@ -625,8 +1234,9 @@ pub struct ServiceSharedAccessSignature {
 
     resource: ServiceSasResource,
     permissions: String,
-    expiry: Timestamp,
+    expiry: Option<Timestamp>,
     start: Option<Timestamp>,
+    identifier: Option<String>,
     ip: Option<String>,
     protocol: Option<String>,
     version: String,
@@ -640,6 +1250,18 @@ impl ServiceSharedAccessSignature {
         resource: ServiceSasResource,
         permissions: String,
         expiry: Timestamp,
+    ) -> Self {
+        Self::with_optional_expiry(account, key, resource, permissions, Some(expiry))
+    }
+
+    /// Create a Service SAS signer; `None` leaves the expiry to the stored
+    /// access policy.
+    pub(crate) fn with_optional_expiry(
+        account: String,
+        key: String,
+        resource: ServiceSasResource,
+        permissions: String,
+        expiry: Option<Timestamp>,
     ) -> Self {
         Self {
             account,
@@ -648,6 +1270,7 @@ impl ServiceSharedAccessSignature {
             permissions,
             expiry,
             start: None,
+            identifier: None,
             ip: None,
             protocol: None,
             version: SERVICE_SAS_VERSION.to_string(),
@@ -678,27 +1301,57 @@ impl ServiceSharedAccessSignature {
         self
     }
 
+    /// Reference a stored access policy (`si`).
+    ///
+    /// Empty permissions are left out of the token so the policy can supply
+    /// them.
+    pub fn with_identifier(mut self, identifier: impl Into<String>) -> Self {
+        self.identifier = Some(identifier.into());
+        self
+    }
+
     fn string_to_sign(&self) -> String {
-        [
+        let mut fields = vec![
             self.permissions.clone(),
             self.start
                 .map_or_else(String::new, Timestamp::format_rfc3339_zulu),
-            self.expiry.format_rfc3339_zulu(),
+            self.expiry
+                .map_or_else(String::new, Timestamp::format_rfc3339_zulu),
             self.resource.canonicalized_resource(&self.account),
-            String::new(), // signed identifier
+            self.identifier.clone().unwrap_or_default(),
             self.ip.clone().unwrap_or_default(),
             self.protocol.clone().unwrap_or_default(),
             self.version.clone(),
-            self.resource.signed_resource().to_string(),
-            String::new(), // snapshot time
-            String::new(), // encryption scope
-            String::new(), // rscc
-            String::new(), // rscd
-            String::new(), // rsce
-            String::new(), // rscl
-            String::new(), // rsct
-        ]
-        .join("\n")
+        ];
+        match &self.resource {
+            ServiceSasResource::Container { .. } | ServiceSasResource::Blob { .. } => {
+                fields.extend([
+                    self.resource
+                        .signed_resource()
+                        .unwrap_or_default()
+                        .to_string(),
+                    String::new(), // snapshot time
+                    String::new(), // encryption scope
+                ]);
+                fields.extend(std::iter::repeat_n(String::new(), 5)); // rscc, rscd, rsce, rscl, rsct
+            }
+            ServiceSasResource::Share { .. } | ServiceSasResource::File { .. } => {
+                fields.extend(std::iter::repeat_n(String::new(), 5)); // rscc, rscd, rsce, rscl, rsct
+            }
+            ServiceSasResource::Queue { .. } => {}
+            ServiceSasResource::Table { range, .. } => {
+                fields.extend(
+                    [
+                        &range.start_partition_key,
+                        &range.start_row_key,
+                        &range.end_partition_key,
+                        &range.end_row_key,
+                    ]
+                    .map(|value| value.clone().unwrap_or_default()),
+                );
+            }
+        }
+        fields.join("\n")
     }
 
     fn signature_with_decoded_key(&self, decoded_key: &[u8]) -> String {
@@ -706,15 +1359,24 @@ impl ServiceSharedAccessSignature {
     }
 
     fn token_with_decoded_key(&self, decoded_key: &[u8]) -> Vec<(String, String)> {
-        let mut elements: Vec<(String, String)> = vec![
-            ("sv".to_string(), self.version.to_string()),
-            ("se".to_string(), self.expiry.format_rfc3339_zulu()),
-            ("sp".to_string(), self.permissions.to_string()),
-            (
-                "sr".to_string(),
-                self.resource.signed_resource().to_string(),
-            ),
-        ];
+        let mut elements: Vec<(String, String)> =
+            vec![("sv".to_string(), self.version.to_string())];
+        if let Some(expiry) = &self.expiry {
+            elements.push(("se".to_string(), expiry.format_rfc3339_zulu()));
+        }
+        if !self.permissions.is_empty() {
+            elements.push(("sp".to_string(), self.permissions.to_string()));
+        }
+        if let Some(signed_resource) = self.resource.signed_resource() {
+            elements.push(("sr".to_string(), signed_resource.to_string()));
+        }
+        if let ServiceSasResource::Table { table, range } = &self.resource {
+            elements.push(("tn".to_string(), table.clone()));
+            elements.extend(range.query_pairs());
+        }
+        if let Some(identifier) = &self.identifier {
+            elements.push(("si".to_string(), identifier.to_string()));
+        }
 
         if let Some(start) = &self.start {
             elements.push(("st".to_string(), start.format_rfc3339_zulu()))
@@ -758,10 +1420,10 @@ pub(crate) fn encode_query_pairs(pairs: &[(String, String)]) -> String {
 struct OperationTimes {
     now: Timestamp,
     start: Option<Timestamp>,
-    expiry: Timestamp,
+    expiry: Option<Timestamp>,
 }
 
-/// Grants expiration-aware Azure Blob Storage Service SAS credentials.
+/// Grants expiration-aware Azure Storage Service SAS credentials.
 ///
 /// The source credential must be [`Credential::SharedKey`] for exactly the
 /// account bound in [`ServiceSasConfig`], with a Base64-encoded 512-bit Azure
@@ -770,6 +1432,10 @@ struct OperationTimes {
 /// whole-second precision: explicit start times are rounded forward,
 /// expirations are rounded backward, and the returned credential expiration
 /// exactly matches the signed `se` value.
+///
+/// When the grant references a stored access policy, `expires_in` may be
+/// `None` to leave the expiry to the policy. The returned credential then has
+/// no expiration, because only the service knows the policy.
 #[derive(Clone)]
 pub struct ServiceSasGranter {
     config: ServiceSasConfig,
@@ -841,30 +1507,41 @@ impl ServiceSasGranter {
         &self,
         start: Option<Timestamp>,
         expires_in: Option<Duration>,
+        policy_expiry: bool,
     ) -> Result<OperationTimes> {
-        let expires_in = expires_in.ok_or_else(|| {
-            Error::request_invalid("Azure Service SAS requires an explicit lifetime")
-        })?;
-        if expires_in.is_zero() {
-            return Err(Error::request_invalid(
-                "Azure Service SAS lifetime must be greater than zero",
-            ));
-        }
-
         let now = self.now();
-        let expiry = expiry_at_wire_second(now, expires_in)?;
-        if expiry <= now {
-            return Err(Error::request_invalid(
-                "Azure Service SAS lifetime does not reach a future wire timestamp",
-            ));
-        }
+        let expiry = match expires_in {
+            None if policy_expiry => None,
+            None => {
+                return Err(Error::request_invalid(
+                    "Azure Service SAS requires an explicit lifetime",
+                ));
+            }
+            Some(expires_in) => {
+                if expires_in.is_zero() {
+                    return Err(Error::request_invalid(
+                        "Azure Service SAS lifetime must be greater than zero",
+                    ));
+                }
+                let expiry = expiry_at_wire_second(now, expires_in)?;
+                if expiry <= now {
+                    return Err(Error::request_invalid(
+                        "Azure Service SAS lifetime does not reach a future wire timestamp",
+                    ));
+                }
+                Some(expiry)
+            }
+        };
         let start = start.map(ceil_to_wire_second).transpose()?;
         if start.is_some_and(|start| start > now) {
             return Err(Error::request_invalid(
                 "Azure Service SAS start time must not be in the future",
             ));
         }
-        if start.is_some_and(|start| start >= expiry) {
+        if start
+            .zip(expiry)
+            .is_some_and(|(start, expiry)| start >= expiry)
+        {
             return Err(Error::request_invalid(
                 "Azure Service SAS start time must be before its expiration",
             ));
@@ -922,14 +1599,15 @@ impl GrantCredential for ServiceSasGranter {
         }
 
         let grant = self.grant.validate()?;
-        let times = self.operation_times(config.start, expires_in)?;
+        let times =
+            self.operation_times(config.start, expires_in, grant.signed_identifier.is_some())?;
         if !credential.is_valid_at(times.now) {
             return Err(Error::credential_invalid(
                 "Azure Service SAS source credential is not currently usable",
             ));
         }
 
-        let mut signer = ServiceSharedAccessSignature::new(
+        let mut signer = ServiceSharedAccessSignature::with_optional_expiry(
             config.account,
             String::new(),
             grant.resource,
@@ -941,6 +1619,9 @@ impl GrantCredential for ServiceSasGranter {
         if let Some(start) = times.start {
             signer = signer.with_start(start);
         }
+        if let Some(identifier) = grant.signed_identifier {
+            signer = signer.with_identifier(identifier);
+        }
         if let Some(ip) = config.ip {
             signer = signer.with_ip(ip);
         }
@@ -948,13 +1629,19 @@ impl GrantCredential for ServiceSasGranter {
         let pairs = signer.token_with_decoded_key(&decoded_key);
         let token = encode_query_pairs(&pairs);
         let after_generation = self.now_after_generation();
-        if times.expiry <= after_generation {
+        if times
+            .expiry
+            .is_some_and(|expiry| expiry <= after_generation)
+        {
             return Err(Error::request_invalid(
                 "Azure Service SAS expired before granting completed",
             ));
         }
 
-        let output = Credential::with_sas_token_expires_at(&token, times.expiry);
+        let output = match times.expiry {
+            Some(expiry) => Credential::with_sas_token_expires_at(&token, expiry),
+            None => Credential::with_sas_token(&token),
+        };
         if !output.is_valid_at(after_generation) {
             return Err(Error::credential_invalid(
                 "granted Azure Service SAS is not currently usable",
@@ -964,7 +1651,7 @@ impl GrantCredential for ServiceSasGranter {
     }
 }
 
-fn expiry_at_wire_second(now: Timestamp, expires_in: Duration) -> Result<Timestamp> {
+pub(crate) fn expiry_at_wire_second(now: Timestamp, expires_in: Duration) -> Result<Timestamp> {
     let duration_seconds = i64::try_from(expires_in.as_secs()).map_err(|_| {
         Error::request_invalid("Azure Service SAS lifetime exceeds the wire time range")
     })?;
@@ -1061,6 +1748,228 @@ mod tests {
         );
     }
 
+    fn signed_query(resource: ServiceSasResource, permissions: &str) -> String {
+        let sign = ServiceSharedAccessSignature::new(
+            "myaccount".to_string(),
+            hash::base64_encode("key".as_bytes()),
+            resource,
+            permissions.to_string(),
+            Timestamp::from_str("2015-04-30T02:23:26Z").unwrap(),
+        )
+        .with_start(Timestamp::from_str("2015-04-29T22:18:26Z").unwrap())
+        .with_ip("168.1.5.60-168.1.5.70")
+        .with_protocol("https")
+        .with_version("2015-04-05");
+
+        assert_eq!(
+            sign.token().unwrap().last().unwrap().1,
+            hash::base64_hmac_sha256("key".as_bytes(), sign.string_to_sign().as_bytes())
+        );
+        encode_query_pairs(&sign.token().unwrap())
+    }
+
+    #[test]
+    fn test_queue_sas_string_to_sign_layout() {
+        let resource = ServiceSasResource::Queue {
+            queue: "myqueue".to_string(),
+        };
+        let sign = ServiceSharedAccessSignature::new(
+            "myaccount".to_string(),
+            String::new(),
+            resource.clone(),
+            "raup".to_string(),
+            Timestamp::from_str("2015-04-30T02:23:26Z").unwrap(),
+        )
+        .with_start(Timestamp::from_str("2015-04-29T22:18:26Z").unwrap())
+        .with_ip("168.1.5.60-168.1.5.70")
+        .with_protocol("https")
+        .with_version("2015-04-05");
+        assert_eq!(
+            sign.string_to_sign(),
+            "raup\n2015-04-29T22:18:26Z\n2015-04-30T02:23:26Z\n/queue/myaccount/myqueue\n\n168.1.5.60-168.1.5.70\nhttps\n2015-04-05"
+        );
+
+        assert_eq!(
+            signed_query(resource, "raup"),
+            "sv=2015-04-05&se=2015-04-30T02%3A23%3A26Z&sp=raup&st=2015-04-29T22%3A18%3A26Z&sip=168.1.5.60-168.1.5.70&spr=https&sig=4ZQsKNGbEOb5F24MDbE5Mr5rdH65YaqaO7IzqrYJV2w%3D"
+        );
+    }
+
+    #[test]
+    fn test_table_sas_string_to_sign_layout() {
+        let resource = ServiceSasResource::Table {
+            table: "MyTable".to_string(),
+            range: ServiceSasTableRange::new()
+                .with_start_partition_key("Jeff")
+                .with_start_row_key("William")
+                .with_end_partition_key("Jeff")
+                .with_end_row_key("William"),
+        };
+        let sign = ServiceSharedAccessSignature::new(
+            "myaccount".to_string(),
+            String::new(),
+            resource.clone(),
+            "raud".to_string(),
+            Timestamp::from_str("2015-04-30T02:23:26Z").unwrap(),
+        )
+        .with_start(Timestamp::from_str("2015-04-29T22:18:26Z").unwrap())
+        .with_ip("168.1.5.60-168.1.5.70")
+        .with_protocol("https")
+        .with_version("2015-04-05");
+        assert_eq!(
+            sign.string_to_sign(),
+            "raud\n2015-04-29T22:18:26Z\n2015-04-30T02:23:26Z\n/table/myaccount/mytable\n\n168.1.5.60-168.1.5.70\nhttps\n2015-04-05\nJeff\nWilliam\nJeff\nWilliam"
+        );
+
+        assert_eq!(
+            signed_query(resource, "raud"),
+            "sv=2015-04-05&se=2015-04-30T02%3A23%3A26Z&sp=raud&tn=MyTable&spk=Jeff&srk=William&epk=Jeff&erk=William&st=2015-04-29T22%3A18%3A26Z&sip=168.1.5.60-168.1.5.70&spr=https&sig=Sg%2BKZwMyRT7uPtgWPgD9yzcFvO4%2BhMdLAel0uCSz4/4%3D"
+        );
+    }
+
+    #[test]
+    fn test_file_sas_string_to_sign_layout() {
+        let resource = ServiceSasResource::File {
+            share: "myshare".to_string(),
+            path: "photos/photo.jpg".to_string(),
+        };
+        let sign = ServiceSharedAccessSignature::new(
+            "myaccount".to_string(),
+            String::new(),
+            resource.clone(),
+            "r".to_string(),
+            Timestamp::from_str("2015-04-30T02:23:26Z").unwrap(),
+        )
+        .with_start(Timestamp::from_str("2015-04-29T22:18:26Z").unwrap())
+        .with_ip("168.1.5.60-168.1.5.70")
+        .with_protocol("https")
+        .with_version("2015-04-05");
+        assert_eq!(
+            sign.string_to_sign(),
+            "r\n2015-04-29T22:18:26Z\n2015-04-30T02:23:26Z\n/file/myaccount/myshare/photos/photo.jpg\n\n168.1.5.60-168.1.5.70\nhttps\n2015-04-05\n\n\n\n\n"
+        );
+
+        assert_eq!(
+            signed_query(resource, "r"),
+            "sv=2015-04-05&se=2015-04-30T02%3A23%3A26Z&sp=r&sr=f&st=2015-04-29T22%3A18%3A26Z&sip=168.1.5.60-168.1.5.70&spr=https&sig=bJmn%2B%2B6eiA%2BQA7O7ARDLI1UI%2BjOUo27OVGnjpi/G61Y%3D"
+        );
+    }
+
+    #[tokio::test]
+    async fn grants_files_queue_and_table_service_sas() {
+        let now = timestamp("2030-01-01T00:00:00Z");
+        let source = shared_key("account", VALID_ACCOUNT_KEY);
+        for (grant, expected) in [
+            (
+                ServiceSasGrant::for_share(
+                    "myshare",
+                    ServiceSasSharePermissions::LIST | ServiceSasSharePermissions::READ,
+                ),
+                [("sr", Some("s")), ("sp", Some("rl")), ("tn", None)],
+            ),
+            (
+                ServiceSasGrant::for_file(
+                    "myshare",
+                    "dir/file.txt",
+                    ServiceSasFilePermissions::WRITE | ServiceSasFilePermissions::CREATE,
+                ),
+                [("sr", Some("f")), ("sp", Some("cw")), ("tn", None)],
+            ),
+            (
+                ServiceSasGrant::for_queue(
+                    "myqueue",
+                    ServiceSasQueuePermissions::PROCESS | ServiceSasQueuePermissions::ADD,
+                ),
+                [("sr", None), ("sp", Some("ap")), ("tn", None)],
+            ),
+            (
+                ServiceSasGrant::for_table(
+                    "mytable",
+                    ServiceSasTablePermissions::DELETE | ServiceSasTablePermissions::READ,
+                ),
+                [("sr", None), ("sp", Some("rd")), ("tn", Some("mytable"))],
+            ),
+        ] {
+            let output = ServiceSasGranter::new(ServiceSasConfig::new("account"), grant)
+                .with_time(now)
+                .grant_credential(&Context::new(), &source, Some(Duration::from_secs(60)))
+                .await
+                .expect("service SAS must be granted");
+            let (token, _) = output_parts(&output);
+            for (key, value) in expected {
+                assert_eq!(query_value(token, key).as_deref(), value, "{key}");
+            }
+            assert!(query_value(token, "spk").is_none());
+        }
+    }
+
+    #[test]
+    fn validates_files_queue_and_table_grants() {
+        for grant in [
+            ServiceSasGrant::for_share("My_Share", ServiceSasSharePermissions::READ),
+            ServiceSasGrant::for_share("myshare", ServiceSasSharePermissions::default()),
+            ServiceSasGrant::for_file("myshare", "", ServiceSasFilePermissions::READ),
+            ServiceSasGrant::for_file("myshare", "/abs", ServiceSasFilePermissions::READ),
+            ServiceSasGrant::for_file("myshare", "a//b", ServiceSasFilePermissions::READ),
+            ServiceSasGrant::for_file("myshare", "a|b", ServiceSasFilePermissions::READ),
+            ServiceSasGrant::for_queue("my--queue", ServiceSasQueuePermissions::READ),
+            ServiceSasGrant::for_queue("myqueue", ServiceSasQueuePermissions::default()),
+            ServiceSasGrant::for_table("1table", ServiceSasTablePermissions::READ),
+            ServiceSasGrant::for_table("Tables", ServiceSasTablePermissions::READ),
+            ServiceSasGrant::for_table("my-table", ServiceSasTablePermissions::READ),
+            ServiceSasGrant::for_table_range(
+                "mytable",
+                ServiceSasTableRange::new().with_start_row_key("row"),
+                ServiceSasTablePermissions::READ,
+            ),
+            ServiceSasGrant::for_table_range(
+                "mytable",
+                ServiceSasTableRange::new().with_end_partition_key("a/b"),
+                ServiceSasTablePermissions::READ,
+            ),
+        ] {
+            assert_eq!(
+                grant
+                    .validate()
+                    .expect_err("invalid grant must fail")
+                    .kind(),
+                ErrorKind::RequestInvalid
+            );
+        }
+
+        let share = ServiceSasGrant::for_share(
+            "myshare",
+            ServiceSasSharePermissions::LIST
+                | ServiceSasSharePermissions::DELETE
+                | ServiceSasSharePermissions::WRITE
+                | ServiceSasSharePermissions::CREATE
+                | ServiceSasSharePermissions::READ,
+        )
+        .validate()
+        .unwrap();
+        assert_eq!(share.permissions, "rcwdl");
+        let queue = ServiceSasGrant::for_queue(
+            "myqueue",
+            ServiceSasQueuePermissions::PROCESS
+                | ServiceSasQueuePermissions::UPDATE
+                | ServiceSasQueuePermissions::ADD
+                | ServiceSasQueuePermissions::READ,
+        )
+        .validate()
+        .unwrap();
+        assert_eq!(queue.permissions, "raup");
+        let table = ServiceSasGrant::for_table(
+            "mytable",
+            ServiceSasTablePermissions::DELETE
+                | ServiceSasTablePermissions::UPDATE
+                | ServiceSasTablePermissions::ADD
+                | ServiceSasTablePermissions::READ,
+        )
+        .validate()
+        .unwrap();
+        assert_eq!(table.permissions, "raud");
+    }
+
     fn timestamp(value: &str) -> Timestamp {
         value.parse().expect("timestamp must parse")
     }
@@ -1325,6 +2234,79 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn grants_stored_access_policy_reference() {
+        let now = timestamp("2030-01-01T00:00:00Z");
+        let source = shared_key("account", VALID_ACCOUNT_KEY);
+
+        let policy_only = ServiceSasGranter::new(
+            ServiceSasConfig::new("account"),
+            ServiceSasGrant::for_blob(
+                "container",
+                "reports/current.csv",
+                ServiceSasBlobPermissions::default(),
+            )
+            .with_signed_identifier("read-only"),
+        )
+        .with_time(now);
+        let output = policy_only
+            .grant_credential(&Context::new(), &source, None)
+            .await
+            .expect("policy-only Service SAS grant must succeed");
+        let Credential::SasToken { token, expires_at } = &output else {
+            panic!("expected SAS token, got {output:?}");
+        };
+        assert_eq!(*expires_at, None);
+        assert_eq!(
+            token,
+            "sv=2020-12-06&sr=b&si=read-only&spr=https&sig=tptLNwJMKq7myGiS8n8tbP7r2LFEjQvdQIqnMq94mOg%3D"
+        );
+
+        let inline_fields = ServiceSasGranter::new(
+            ServiceSasConfig::new("account"),
+            ServiceSasGrant::for_container(
+                "container",
+                ServiceSasContainerPermissions::LIST | ServiceSasContainerPermissions::READ,
+            )
+            .with_signed_identifier("list-policy"),
+        )
+        .with_time(now);
+        let output = inline_fields
+            .grant_credential(&Context::new(), &source, Some(Duration::from_secs(60)))
+            .await
+            .expect("Service SAS grant with inline fields must succeed");
+        let (token, expires_at) = output_parts(&output);
+        assert_eq!(expires_at, timestamp("2030-01-01T00:01:00Z"));
+        assert_eq!(
+            token,
+            "sv=2020-12-06&se=2030-01-01T00%3A01%3A00Z&sp=rl&sr=c&si=list-policy&spr=https&sig=LnBOyKZecNUaFswmVOPxR2sqSV91ihAQP3YGOAF/hp0%3D"
+        );
+
+        for grant in [
+            ServiceSasGrant::for_queue("queue", ServiceSasQueuePermissions::READ)
+                .with_signed_identifier(""),
+            ServiceSasGrant::for_queue("queue", ServiceSasQueuePermissions::READ)
+                .with_signed_identifier("x".repeat(65)),
+        ] {
+            let err = ServiceSasGranter::new(ServiceSasConfig::new("account"), grant)
+                .with_time(now)
+                .grant_credential(&Context::new(), &source, Some(Duration::from_secs(60)))
+                .await
+                .expect_err("invalid signed identifier must fail");
+            assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+        }
+
+        let err = ServiceSasGranter::new(
+            ServiceSasConfig::new("account"),
+            ServiceSasGrant::for_queue("queue", ServiceSasQueuePermissions::READ),
+        )
+        .with_time(now)
+        .grant_credential(&Context::new(), &source, None)
+        .await
+        .expect_err("a lifetime is required without a stored access policy");
+        assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+    }
+
     #[tokio::test]
     async fn rejects_source_variants_account_mismatch_and_invalid_key() {
         let now = timestamp("2030-01-01T00:00:00Z");
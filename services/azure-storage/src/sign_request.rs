@@ -28,7 +28,8 @@ use percent_encoding::percent_decode_str;
 use reqsign_core::hash::{base64_decode, base64_hmac_sha256};
 use reqsign_core::time::Timestamp;
 use reqsign_core::{
-    Context, Result, SignRequest, SigningCredential, SigningMethod, SigningRequest,
+    Context, Result, SignRequest, SigningCredential, SigningMethod, SigningOverrideField,
+    SigningOverrides, SigningRequest,
 };
 use std::fmt::Write;
 use std::fmt::{Debug, Formatter};
@@ -46,11 +47,66 @@ pub enum SasResourceKind {
     Blob,
 }
 
+/// Shared Key string-to-sign and authorization scheme used by [`RequestSigner`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SharedKeyMode {
+    /// Blob, Queue and File `SharedKey`.
+    SharedKey,
+    /// Blob, Queue and File `SharedKeyLite`.
+    SharedKeyLite,
+    /// Table `SharedKey`.
+    Table,
+    /// Table `SharedKeyLite`.
+    TableLite,
+}
+
+/// Table service endpoint suffixes of the public and sovereign Azure clouds.
+const TABLE_ENDPOINT_SUFFIXES: [&str; 3] = [
+    ".table.core.windows.net",
+    ".table.core.chinacloudapi.cn",
+    ".table.core.usgovcloudapi.net",
+];
+
+impl SharedKeyMode {
+    /// Table for Table service endpoints of the Azure clouds, SharedKey otherwise.
+    fn infer(authority: &str) -> Self {
+        let host = authority.split('@').next_back().unwrap_or(authority);
+        let host = host.split(':').next().unwrap_or(host).to_ascii_lowercase();
+        if TABLE_ENDPOINT_SUFFIXES
+            .iter()
+            .any(|suffix| host.ends_with(suffix))
+        {
+            Self::Table
+        } else {
+            Self::SharedKey
+        }
+    }
+
+    fn scheme(self) -> &'static str {
+        match self {
+            Self::SharedKey | Self::Table => "SharedKey",
+            Self::SharedKeyLite | Self::TableLite => "SharedKeyLite",
+        }
+    }
+}
+
 /// RequestSigner that implement Azure Storage Shared Key Authorization.
 ///
 /// - [Authorize with Shared Key](https://docs.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key)
+///
+/// Header signing uses the Blob, Queue and File `SharedKey` format unless the
+/// host ends with `.table.core.windows.net` or, in the sovereign clouds,
+/// `.table.core.chinacloudapi.cn` or `.table.core.usgovcloudapi.net`, which
+/// selects the Table format. Use
+/// [`RequestSigner::with_shared_key_mode`] to pick `SharedKeyLite` or to sign
+/// Table requests sent to other endpoints, such as Azurite or custom domains.
+///
+/// Supports the signing time and `expires_in` [`SigningOverrides`]. Azure
+/// Storage has no region or service in its signatures.
 pub struct RequestSigner {
     time: Option<Timestamp>,
+    shared_key_mode: Option<SharedKeyMode>,
     service_sas_permissions: Option<String>,
     service_sas_start: Option<Timestamp>,
     service_sas_ip: Option<String>,
@@ -90,6 +146,7 @@ impl RequestSigner {
     pub fn new() -> Self {
         Self {
             time: None,
+            shared_key_mode: None,
             service_sas_permissions: None,
             service_sas_start: None,
             service_sas_ip: None,
@@ -101,6 +158,15 @@ impl RequestSigner {
         }
     }
 
+    /// Configure the Shared Key mode for header signing.
+    ///
+    /// Defaults to [`SharedKeyMode::Table`] for `*.table.core.*` hosts and
+    /// [`SharedKeyMode::SharedKey`] otherwise.
+    pub fn with_shared_key_mode(mut self, mode: SharedKeyMode) -> Self {
+        self.shared_key_mode = Some(mode);
+        self
+    }
+
     /// Configure Service SAS presign permissions for Shared Key query signing.
     ///
     /// This setting is required when `expires_in` is provided and credential is `SharedKey`.
@@ -229,6 +295,16 @@ impl SignRequest for RequestSigner {
         self.required_valid_until_at(credential, self.get_time(), expires_in)
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &Parts,
+        credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        let signing_time = SigningOverrides::signing_time_or(req, || self.get_time());
+        self.required_valid_until_at(credential, signing_time, expires_in)
+    }
+
     async fn sign_request(
         &self,
         context: &Context,
@@ -240,7 +316,15 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let signing_time = self.get_time();
+        let (expires_in, signing_time) = SigningOverrides::resolve(
+            req,
+            &[
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::ExpiresIn,
+            ],
+            expires_in,
+            || self.get_time(),
+        )?;
         let required_until = self.required_valid_until_at(cred, signing_time, expires_in);
         if !cred.is_valid_at(required_until) {
             return Err(reqsign_core::Error::credential_invalid(
@@ -387,7 +471,7 @@ impl SignRequest for RequestSigner {
                             crate::user_delegation::UserDelegationSharedAccessSignature::new(
                                 account,
                                 key,
-                                resource.into(),
+                                resource.try_into()?,
                                 cfg.permissions.to_string(),
                                 expiry,
                             );
@@ -476,7 +560,23 @@ impl SignRequest for RequestSigner {
                         final_uri = Some(append_query_pairs(&original_uri, &signer_token)?);
                     }
                     SigningMethod::Header => {
-                        let string_to_sign = string_to_sign(&mut sctx, account_name, signing_time)?;
+                        let mode = self
+                            .shared_key_mode
+                            .unwrap_or_else(|| SharedKeyMode::infer(sctx.authority.as_str()));
+                        let string_to_sign = match mode {
+                            SharedKeyMode::SharedKey => {
+                                string_to_sign(&mut sctx, account_name, signing_time)?
+                            }
+                            SharedKeyMode::SharedKeyLite => {
+                                string_to_sign_lite(&mut sctx, account_name, signing_time)?
+                            }
+                            SharedKeyMode::Table => {
+                                string_to_sign_table(&mut sctx, account_name, signing_time)?
+                            }
+                            SharedKeyMode::TableLite => {
+                                string_to_sign_table_lite(&mut sctx, account_name, signing_time)?
+                            }
+                        };
                         let decode_content = base64_decode(account_key).map_err(|e| {
                             reqsign_core::Error::unexpected("failed to decode account key")
                                 .with_source(e)
@@ -486,7 +586,7 @@ impl SignRequest for RequestSigner {
 
                         sctx.headers.insert(header::AUTHORIZATION, {
                             let mut value: HeaderValue =
-                                format!("SharedKey {account_name}:{signature}")
+                                format!("{} {account_name}:{signature}", mode.scheme())
                                     .parse()
                                     .map_err(|e| {
                                         reqsign_core::Error::unexpected(
@@ -760,12 +860,7 @@ fn string_to_sign(
 ///
 /// - [Constructing the canonicalized headers string](https://docs.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key#constructing-the-canonicalized-headers-string)
 fn canonicalize_header(ctx: &mut SigningRequest, now_time: Timestamp) -> Result<String> {
-    ctx.headers.insert(
-        X_MS_DATE,
-        now_time.format_http_date().parse().map_err(|e| {
-            reqsign_core::Error::unexpected("failed to parse x-ms-date header").with_source(e)
-        })?,
-    );
+    insert_x_ms_date(ctx, now_time)?;
 
     Ok(SigningRequest::header_to_string(
         ctx.header_to_vec_with_prefix("x-ms-"),
@@ -796,6 +891,145 @@ fn canonicalize_resource(ctx: &SigningRequest, account_name: &str) -> String {
     )
 }
 
+/// Construct Shared Key Lite string to sign for Blob, Queue and File
+///
+/// ## Format
+///
+/// ```text
+/// VERB + "\n" +
+/// Content-MD5 + "\n" +
+/// Content-Type + "\n" +
+/// Date + "\n" +
+/// CanonicalizedHeaders +
+/// CanonicalizedResource;
+/// ```
+///
+/// ## Reference
+///
+/// - [Blob, Queue, and File Services (Shared Key Lite authorization)](https://learn.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key#blob-queue-and-file-services-shared-key-lite-authorization)
+fn string_to_sign_lite(
+    ctx: &mut SigningRequest,
+    account_name: &str,
+    now_time: Timestamp,
+) -> Result<String> {
+    let canonicalized_headers = canonicalize_header(ctx, now_time)?;
+    let s = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        ctx.method.as_str(),
+        lite_header(ctx, CONTENT_MD5)?,
+        lite_header(ctx, header::CONTENT_TYPE.as_str())?,
+        lite_header(ctx, header::DATE.as_str())?,
+        canonicalized_headers,
+        canonicalize_resource_lite(ctx, account_name),
+    );
+
+    debug!("string to sign: {}", s);
+
+    Ok(s)
+}
+
+/// Construct Table service Shared Key string to sign
+///
+/// ## Format
+///
+/// ```text
+/// VERB + "\n" +
+/// Content-MD5 + "\n" +
+/// Content-Type + "\n" +
+/// Date + "\n" +
+/// CanonicalizedResource;
+/// ```
+///
+/// `Date` is the `x-ms-date` value set by the signer.
+///
+/// ## Reference
+///
+/// - [Table service (Shared Key authorization)](https://learn.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key#table-service-shared-key-authorization)
+fn string_to_sign_table(
+    ctx: &mut SigningRequest,
+    account_name: &str,
+    now_time: Timestamp,
+) -> Result<String> {
+    let date = insert_x_ms_date(ctx, now_time)?;
+    let s = format!(
+        "{}\n{}\n{}\n{}\n{}",
+        ctx.method.as_str(),
+        lite_header(ctx, CONTENT_MD5)?,
+        lite_header(ctx, header::CONTENT_TYPE.as_str())?,
+        date,
+        canonicalize_resource_lite(ctx, account_name),
+    );
+
+    debug!("string to sign: {}", s);
+
+    Ok(s)
+}
+
+/// Construct Table service Shared Key Lite string to sign
+///
+/// ## Format
+///
+/// ```text
+/// Date + "\n" +
+/// CanonicalizedResource;
+/// ```
+///
+/// ## Reference
+///
+/// - [Table service (Shared Key Lite authorization)](https://learn.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key#table-service-shared-key-lite-authorization)
+fn string_to_sign_table_lite(
+    ctx: &mut SigningRequest,
+    account_name: &str,
+    now_time: Timestamp,
+) -> Result<String> {
+    let date = insert_x_ms_date(ctx, now_time)?;
+    let s = format!(
+        "{}\n{}",
+        date,
+        canonicalize_resource_lite(ctx, account_name)
+    );
+
+    debug!("string to sign: {}", s);
+
+    Ok(s)
+}
+
+fn lite_header<'a>(ctx: &'a SigningRequest, name: &str) -> Result<&'a str> {
+    let name = http::HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+        reqsign_core::Error::unexpected("failed to parse header name").with_source(e)
+    })?;
+    ctx.header_get_or_default(&name).map_err(|e| {
+        reqsign_core::Error::unexpected(format!("failed to get {name} header")).with_source(e)
+    })
+}
+
+fn insert_x_ms_date(ctx: &mut SigningRequest, now_time: Timestamp) -> Result<String> {
+    let date = now_time.format_http_date();
+    ctx.headers.insert(
+        X_MS_DATE,
+        date.parse().map_err(|e| {
+            reqsign_core::Error::unexpected("failed to parse x-ms-date header").with_source(e)
+        })?,
+    );
+    Ok(date)
+}
+
+/// Shared Key Lite and Table services keep only the `comp` query parameter.
+///
+/// ## Reference
+///
+/// - [Shared Key Lite and Table service format for 2009-09-19 and later](https://learn.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key#shared-key-lite-and-table-service-format-for-2009-09-19-and-later)
+fn canonicalize_resource_lite(ctx: &SigningRequest, account_name: &str) -> String {
+    match ctx
+        .query
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("comp"))
+    {
+        Some((_, comp)) => format!("/{}{}?comp={}", account_name, ctx.path, comp),
+        None => format!("/{}{}", account_name, ctx.path),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -860,6 +1094,121 @@ mod tests {
         );
     }
 
+    #[test]
+    fn table_and_lite_string_to_sign() {
+        let now = Timestamp::from_str("2022-03-01T08:12:34Z").unwrap();
+        let build = |method: &str, uri: &str, headers: &[(&str, &str)]| {
+            let mut req = Request::builder().method(method).uri(uri);
+            for (name, value) in headers {
+                req = req.header(*name, *value);
+            }
+            let mut parts = req.body(()).unwrap().into_parts().0;
+            SigningRequest::build(&mut parts).unwrap()
+        };
+
+        let mut sctx = build(
+            "PUT",
+            "https://account.table.core.windows.net/mytable?comp=acl&timeout=30",
+            &[("content-type", "application/xml"), ("content-md5", "md5")],
+        );
+        assert_eq!(
+            string_to_sign_table(&mut sctx, "account", now).unwrap(),
+            "PUT\nmd5\napplication/xml\nTue, 01 Mar 2022 08:12:34 GMT\n/account/mytable?comp=acl"
+        );
+        assert_eq!(
+            sctx.headers.get(X_MS_DATE).unwrap(),
+            "Tue, 01 Mar 2022 08:12:34 GMT"
+        );
+
+        let mut sctx = build(
+            "GET",
+            "https://account.table.core.windows.net/mytable()?$filter=x",
+            &[("content-type", "application/json")],
+        );
+        assert_eq!(
+            string_to_sign_table_lite(&mut sctx, "account", now).unwrap(),
+            "Tue, 01 Mar 2022 08:12:34 GMT\n/account/mytable()"
+        );
+
+        let mut sctx = build(
+            "PUT",
+            "https://account.blob.core.windows.net/container/blob?blockid=YQ%3D%3D&comp=block",
+            &[
+                ("content-type", "text/plain"),
+                ("content-length", "5"),
+                ("x-ms-version", "2020-12-06"),
+            ],
+        );
+        assert_eq!(
+            string_to_sign_lite(&mut sctx, "account", now).unwrap(),
+            "PUT\n\ntext/plain\n\nx-ms-date:Tue, 01 Mar 2022 08:12:34 GMT\nx-ms-version:2020-12-06\n/account/container/blob?comp=block"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_key_mode() {
+        let now = Timestamp::from_str("2022-03-01T08:12:34Z").unwrap();
+        let key = reqsign_core::hash::base64_encode("key".as_bytes());
+        let cred = Credential::with_shared_key("account", &key);
+        let ctx = Context::new();
+
+        // Table authority selects the Table format.
+        let mut parts = Request::get(
+            "https://account.table.core.windows.net/mytable(PartitionKey='a',RowKey='b')",
+        )
+        .header("content-type", "application/json")
+        .body(())
+        .unwrap()
+        .into_parts()
+        .0;
+        RequestSigner::new()
+            .with_time(now)
+            .sign_request(&ctx, &mut parts, Some(&cred), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            parts.headers.get(header::AUTHORIZATION).unwrap(),
+            "SharedKey account:aQOf/o0YpQSJrg+KnDz7oOFhleQRAFa5RpEYynfD5fo="
+        );
+
+        let mut parts =
+            Request::put("https://account.blob.core.windows.net/container/blob?comp=block")
+                .header("content-type", "text/plain")
+                .header("x-ms-version", "2020-12-06")
+                .body(())
+                .unwrap()
+                .into_parts()
+                .0;
+        RequestSigner::new()
+            .with_time(now)
+            .with_shared_key_mode(SharedKeyMode::SharedKeyLite)
+            .sign_request(&ctx, &mut parts, Some(&cred), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            parts.headers.get(header::AUTHORIZATION).unwrap(),
+            "SharedKeyLite account:XP5nA7sjgi9rvRr1tUojv7GgDuglNjuBTSES1WSG7Kg="
+        );
+
+        for authority in [
+            "account.table.core.windows.net",
+            "account.TABLE.core.windows.net:443",
+            "account.table.core.chinacloudapi.cn",
+            "user@account.table.core.usgovcloudapi.net:443",
+        ] {
+            assert_eq!(SharedKeyMode::infer(authority), SharedKeyMode::Table);
+        }
+        for authority in [
+            "account.blob.core.windows.net",
+            "127.0.0.1:10002",
+            "table.example.com",
+            "account.table.core.evil.example",
+            "account.table.core.windows.net.evil.example",
+        ] {
+            assert_eq!(SharedKeyMode::infer(authority), SharedKeyMode::SharedKey);
+        }
+    }
+
     #[tokio::test]
     async fn test_sas_token() {
         let _ = env_logger::builder().is_test(true).try_init();
@@ -978,6 +1327,53 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_signing_overrides_presign_service_sas() {
+        let now = Timestamp::from_str("2022-03-01T08:12:34Z").unwrap();
+        let key = reqsign_core::hash::base64_encode("key".as_bytes());
+        let cred = Credential::with_shared_key("account", &key);
+        let signer = RequestSigner::new().with_service_sas_permissions("r");
+
+        let original_uri =
+            format!("https://account.blob.core.windows.net/container/path/to/blob.txt?{RAW_QUERY}");
+        let (mut parts, _) = Request::builder()
+            .uri(&original_uri)
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts.extensions.insert(
+            SigningOverrides::new()
+                .with_signing_time(now)
+                .with_expires_in(Duration::from_secs(300)),
+        );
+        signer
+            .sign_request(&Context::new(), &mut parts, Some(&cred), None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            parts.uri.to_string(),
+            format!(
+                "{original_uri}sv=2020-12-06&se=2022-03-01T08%3A17%3A34Z&sp=r&sr=b&sig=CP9a2LIrR9zeG4I4jZjqPetJSXWJ77QeUA7c3GMypyM%3D"
+            )
+        );
+
+        let (mut parts, _) = Request::builder()
+            .uri(&original_uri)
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts
+            .extensions
+            .insert(SigningOverrides::new().with_region("westeurope"));
+        let err = signer
+            .sign_request(&Context::new(), &mut parts, Some(&cred), None)
+            .await
+            .expect_err("region override must be rejected");
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::RequestInvalid);
+        assert_eq!(parts.uri.to_string(), original_uri);
+    }
+
     #[derive(Clone, Debug, Default)]
     struct MockUserDelegationHttpSend {
         calls: Arc<AtomicUsize>,
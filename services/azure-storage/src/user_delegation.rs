@@ -349,11 +349,16 @@ impl UserDelegationSasResource {
     }
 }
 
-impl From<ServiceSasResource> for UserDelegationSasResource {
-    fn from(value: ServiceSasResource) -> Self {
+impl TryFrom<ServiceSasResource> for UserDelegationSasResource {
+    type Error = reqsign_core::Error;
+
+    fn try_from(value: ServiceSasResource) -> Result<Self> {
         match value {
-            ServiceSasResource::Container { container } => Self::Container { container },
-            ServiceSasResource::Blob { container, blob } => Self::Blob { container, blob },
+            ServiceSasResource::Container { container } => Ok(Self::Container { container }),
+            ServiceSasResource::Blob { container, blob } => Ok(Self::Blob { container, blob }),
+            _ => Err(reqsign_core::Error::request_invalid(
+                "Azure user delegation SAS only supports Blob Storage resources",
+            )),
         }
     }
 }
@@ -369,6 +374,9 @@ pub(crate) struct UserDelegationSharedAccessSignature {
     ip: Option<String>,
     protocol: Option<String>,
     version: String,
+    authorized_object_id: Option<String>,
+    unauthorized_object_id: Option<String>,
+    correlation_id: Option<String>,
 }
 
 impl UserDelegationSharedAccessSignature {
@@ -389,6 +397,9 @@ impl UserDelegationSharedAccessSignature {
             ip: None,
             protocol: None,
             version: DEFAULT_USER_DELEGATION_SAS_VERSION.to_string(),
+            authorized_object_id: None,
+            unauthorized_object_id: None,
+            correlation_id: None,
         }
     }
 
@@ -412,6 +423,21 @@ impl UserDelegationSharedAccessSignature {
         self
     }
 
+    pub(crate) fn with_authorized_object_id(mut self, object_id: impl Into<String>) -> Self {
+        self.authorized_object_id = Some(object_id.into());
+        self
+    }
+
+    pub(crate) fn with_unauthorized_object_id(mut self, object_id: impl Into<String>) -> Self {
+        self.unauthorized_object_id = Some(object_id.into());
+        self
+    }
+
+    pub(crate) fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
     fn signature(&self) -> Result<String> {
         let canonicalized_resource = self.resource.canonicalized_resource(&self.account);
 
@@ -439,7 +465,18 @@ impl UserDelegationSharedAccessSignature {
         ];
 
         if self.version.as_str() >= "2020-02-10" {
-            fields.extend([String::new(), String::new(), String::new()]);
+            fields.extend([
+                self.authorized_object_id.clone().unwrap_or_default(),
+                self.unauthorized_object_id.clone().unwrap_or_default(),
+                self.correlation_id.clone().unwrap_or_default(),
+            ]);
+        } else if self.authorized_object_id.is_some()
+            || self.unauthorized_object_id.is_some()
+            || self.correlation_id.is_some()
+        {
+            return Err(reqsign_core::Error::request_invalid(
+                "saoid, suoid and scid require user delegation SAS version 2020-02-10 or later",
+            ));
         }
 
         fields.extend([
@@ -494,6 +531,16 @@ impl UserDelegationSharedAccessSignature {
             ("skv".to_string(), self.key.signed_version.to_string()),
         ];
 
+        if let Some(object_id) = &self.authorized_object_id {
+            elements.push(("saoid".to_string(), object_id.to_string()));
+        }
+        if let Some(object_id) = &self.unauthorized_object_id {
+            elements.push(("suoid".to_string(), object_id.to_string()));
+        }
+        if let Some(correlation_id) = &self.correlation_id {
+            elements.push(("scid".to_string(), correlation_id.to_string()));
+        }
+
         if let Some(depth) = self.resource.directory_depth() {
             elements.push(("sdd".to_string(), depth.to_string()));
         }
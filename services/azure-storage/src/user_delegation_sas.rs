@@ -172,10 +172,17 @@ enum UserDelegationSasGrantResource {
 /// Resource names are logical, percent-decoded Azure names. Path prefixes map
 /// to Azure's directory/prefix SAS resource (`sr=d`) and therefore require a
 /// hierarchical-namespace or otherwise prefix-capable storage account.
+///
+/// On hierarchical-namespace accounts a grant can also name the end user it is
+/// issued for (`saoid` or `suoid`) and carry a correlation ID (`scid`) that
+/// Azure records in its storage logs.
 #[derive(Clone)]
 pub struct UserDelegationSasGrant {
     resource: UserDelegationSasGrantResource,
     permissions: UserDelegationSasPermissions,
+    authorized_object_id: Option<String>,
+    unauthorized_object_id: Option<String>,
+    correlation_id: Option<String>,
 }
 
 impl Debug for UserDelegationSasGrant {
@@ -196,6 +203,9 @@ impl UserDelegationSasGrant {
                 container: container.into(),
             },
             permissions,
+            authorized_object_id: None,
+            unauthorized_object_id: None,
+            correlation_id: None,
         }
     }
 
@@ -214,6 +224,9 @@ impl UserDelegationSasGrant {
                 blob: blob.into(),
             },
             permissions,
+            authorized_object_id: None,
+            unauthorized_object_id: None,
+            correlation_id: None,
         }
     }
 
@@ -223,6 +236,7 @@ impl UserDelegationSasGrant {
     /// slash, trailing slash, empty segment, `.` segment, or `..` segment.
     /// It may contain at most 61 segments after accounting for the storage
     /// account and container segments in Azure's hierarchical-namespace limit.
+    /// The number of segments is signed as the directory depth (`sdd`).
     pub fn for_path_prefix(
         container: impl Into<String>,
         path_prefix: impl Into<String>,
@@ -234,9 +248,39 @@ impl UserDelegationSasGrant {
                 path_prefix: path_prefix.into(),
             },
             permissions,
+            authorized_object_id: None,
+            unauthorized_object_id: None,
+            correlation_id: None,
         }
     }
 
+    /// Issue the SAS for a Microsoft Entra object ID that the key owner has
+    /// already authorized (`saoid`).
+    ///
+    /// Azure performs no additional POSIX ACL check for this user. The object
+    /// ID is recorded in the storage logs.
+    pub fn with_authorized_object_id(mut self, object_id: impl Into<String>) -> Self {
+        self.authorized_object_id = Some(object_id.into());
+        self
+    }
+
+    /// Issue the SAS for a Microsoft Entra object ID that Azure must authorize
+    /// itself (`suoid`).
+    ///
+    /// On hierarchical-namespace accounts Azure checks the POSIX ACLs of the
+    /// target path against this object ID before allowing the operation. It
+    /// cannot be combined with [`Self::with_authorized_object_id`].
+    pub fn with_unauthorized_object_id(mut self, object_id: impl Into<String>) -> Self {
+        self.unauthorized_object_id = Some(object_id.into());
+        self
+    }
+
+    /// Attach a correlation ID (`scid`) that Azure records in its storage logs.
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
     fn validate(&self) -> Result<ValidatedGrant> {
         if self.permissions.is_empty() {
             return Err(Error::request_invalid(
@@ -314,9 +358,33 @@ impl UserDelegationSasGrant {
             }
         };
 
+        if self.authorized_object_id.is_some() && self.unauthorized_object_id.is_some() {
+            return Err(Error::request_invalid(
+                "user delegation SAS must not set both authorized and unauthorized object IDs",
+            ));
+        }
+        for (value, message) in [
+            (
+                &self.authorized_object_id,
+                "authorized object ID must be a GUID",
+            ),
+            (
+                &self.unauthorized_object_id,
+                "unauthorized object ID must be a GUID",
+            ),
+            (&self.correlation_id, "correlation ID must be a GUID"),
+        ] {
+            if value.as_deref().is_some_and(|value| !is_guid(value)) {
+                return Err(Error::request_invalid(message));
+            }
+        }
+
         Ok(ValidatedGrant {
             resource,
             permissions: self.permissions.as_canonical_string(),
+            authorized_object_id: self.authorized_object_id.clone(),
+            unauthorized_object_id: self.unauthorized_object_id.clone(),
+            correlation_id: self.correlation_id.clone(),
         })
     }
 }
@@ -324,6 +392,9 @@ impl UserDelegationSasGrant {
 struct ValidatedGrant {
     resource: UserDelegationSasResource,
     permissions: String,
+    authorized_object_id: Option<String>,
+    unauthorized_object_id: Option<String>,
+    correlation_id: Option<String>,
 }
 
 fn validate_permissions(
@@ -338,6 +409,14 @@ fn validate_permissions(
     Ok(())
 }
 
+fn is_guid(value: &str) -> bool {
+    let groups = value.split('-').collect::<Vec<_>>();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, len)| {
+            group.len() == len && group.bytes().all(|value| value.is_ascii_hexdigit())
+        })
+}
+
 fn validate_account(account: &str) -> Result<()> {
     if !(3..=24).contains(&account.len())
         || !account
@@ -801,6 +880,15 @@ impl GrantCredential for UserDelegationSasGranter {
         if let Some(ip) = &self.ip {
             signer = signer.with_ip(ip);
         }
+        if let Some(object_id) = grant.authorized_object_id {
+            signer = signer.with_authorized_object_id(object_id);
+        }
+        if let Some(object_id) = grant.unauthorized_object_id {
+            signer = signer.with_unauthorized_object_id(object_id);
+        }
+        if let Some(correlation_id) = grant.correlation_id {
+            signer = signer.with_correlation_id(correlation_id);
+        }
 
         let pairs = signer.token().map_err(|e| {
             Error::unexpected("failed to generate Azure user delegation SAS").with_source(e)
@@ -1453,6 +1541,73 @@ mod tests {
         assert_eq!(query_value(token, "spr").as_deref(), Some("https,http"));
     }
 
+    #[tokio::test]
+    async fn signs_hierarchical_namespace_object_and_correlation_ids() {
+        let now = timestamp("2030-01-01T00:00:00Z");
+        let key_expiry = now + MAX_USER_DELEGATION_LIFETIME;
+        let http = MockUserDelegationHttpSend::new([delegation_key_response(
+            now, key_expiry, "oid", "tid", "a2V5",
+        )]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let grant = UserDelegationSasGrant::for_path_prefix(
+            "container",
+            "tenants/contoso",
+            UserDelegationSasPermissions::LIST | UserDelegationSasPermissions::READ,
+        )
+        .with_unauthorized_object_id("7c1f2d8e-3b4a-4f6e-9d0c-2a5b8e1f4c7d")
+        .with_correlation_id("0f8fad5b-d9cb-469f-a165-70867728950e");
+
+        let output = UserDelegationSasGranter::new("account", grant)
+            .with_time(now)
+            .grant_credential(&ctx, &bearer("source"), Some(Duration::from_secs(300)))
+            .await
+            .expect("grant must succeed");
+        let token = output_parts(&output).0;
+        assert_eq!(query_value(token, "sdd").as_deref(), Some("2"));
+        assert_eq!(query_value(token, "saoid"), None);
+        assert_eq!(
+            query_value(token, "suoid").as_deref(),
+            Some("7c1f2d8e-3b4a-4f6e-9d0c-2a5b8e1f4c7d")
+        );
+        assert_eq!(
+            query_value(token, "scid").as_deref(),
+            Some("0f8fad5b-d9cb-469f-a165-70867728950e")
+        );
+        assert_eq!(
+            query_value(token, "sig").as_deref(),
+            Some("3YQtxol54VLjROA/vFhFd7CJl2BdwSHB7FUVhxNJtMY=")
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_object_and_correlation_ids_before_io() {
+        let now = timestamp("2030-01-01T00:00:00Z");
+        let http = MockUserDelegationHttpSend::new([]);
+        let ctx = Context::new().with_http_send(http.clone());
+        let guid = "7c1f2d8e-3b4a-4f6e-9d0c-2a5b8e1f4c7d";
+        let grant = blob_grant("blob", UserDelegationSasPermissions::READ);
+
+        for grant in [
+            grant
+                .clone()
+                .with_authorized_object_id(guid)
+                .with_unauthorized_object_id(guid),
+            grant.clone().with_authorized_object_id("user@example.com"),
+            grant
+                .clone()
+                .with_unauthorized_object_id("7c1f2d8e3b4a4f6e9d0c2a5b8e1f4c7d"),
+            grant.with_correlation_id("{0f8fad5b-d9cb-469f-a165-70867728950e}"),
+        ] {
+            let err = UserDelegationSasGranter::new("account", grant)
+                .with_time(now)
+                .grant_credential(&ctx, &bearer("source"), Some(Duration::from_secs(60)))
+                .await
+                .expect_err("invalid object or correlation ID must fail");
+            assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+        }
+        assert_eq!(http.calls.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn rejects_scope_widening_and_invalid_permissions_before_io() {
         let now = timestamp("2030-01-01T00:00:00Z");
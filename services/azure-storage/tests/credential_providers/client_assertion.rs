@@ -0,0 +1,64 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use reqsign_azure_storage::{ClientAssertionCredentialProvider, Credential};
+use reqsign_core::{Context, OsEnv, ProvideCredential};
+use reqsign_file_read_tokio::TokioFileRead;
+use reqsign_http_send_reqwest::ReqwestHttpSend;
+
+fn is_test_enabled() -> bool {
+    std::env::var("REQSIGN_AZURE_STORAGE_TEST_CLIENT_ASSERTION").unwrap_or_default() == "on"
+}
+
+#[tokio::test]
+async fn test_client_assertion_provider() {
+    if !is_test_enabled() {
+        eprintln!("Skipping test: REQSIGN_AZURE_STORAGE_TEST_CLIENT_ASSERTION is not enabled");
+        return;
+    }
+
+    // Any federated token trusted by the app registration works as an assertion.
+    let token_file = std::env::var("AZURE_FEDERATED_TOKEN_FILE")
+        .expect("AZURE_FEDERATED_TOKEN_FILE must be set for client assertion test");
+
+    let ctx = Context::new()
+        .with_file_read(TokioFileRead)
+        .with_http_send(ReqwestHttpSend::default())
+        .with_env(OsEnv);
+
+    let loader = ClientAssertionCredentialProvider::new(move || {
+        let token_file = token_file.clone();
+        async move {
+            tokio::fs::read_to_string(&token_file).await.map_err(|e| {
+                reqsign_core::Error::credential_invalid("failed to read assertion").with_source(e)
+            })
+        }
+    });
+    let cred = match loader.provide_credential(&ctx).await {
+        Ok(Some(cred)) => cred,
+        Ok(None) => panic!("Client assertion provider returned None when test is enabled"),
+        Err(e) => panic!("Client assertion provider failed with error: {e:?}"),
+    };
+
+    match cred {
+        Credential::BearerToken { token, .. } => {
+            assert!(!token.is_empty());
+            eprintln!("Successfully obtained bearer token using client assertion");
+        }
+        _ => panic!("Expected BearerToken credential from client assertion provider"),
+    }
+}
@@ -22,10 +22,20 @@ pub mod static_provider;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod azure_cli;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod azure_developer_cli;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod azure_powershell;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod client_certificate;
 
+pub mod app_service;
 pub mod azure_pipelines;
+pub mod client_assertion;
 pub mod client_secret;
 pub mod imds;
+pub mod managed_identity;
+pub mod on_behalf_of;
 pub mod workload_identity;
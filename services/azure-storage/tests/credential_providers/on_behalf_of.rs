@@ -0,0 +1,57 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use reqsign_azure_storage::{Credential, OnBehalfOfCredentialProvider};
+use reqsign_core::{Context, OsEnv, ProvideCredential};
+use reqsign_file_read_tokio::TokioFileRead;
+use reqsign_http_send_reqwest::ReqwestHttpSend;
+
+fn is_test_enabled() -> bool {
+    std::env::var("REQSIGN_AZURE_STORAGE_TEST_ON_BEHALF_OF").unwrap_or_default() == "on"
+}
+
+#[tokio::test]
+async fn test_on_behalf_of_provider() {
+    if !is_test_enabled() {
+        eprintln!("Skipping test: REQSIGN_AZURE_STORAGE_TEST_ON_BEHALF_OF is not enabled");
+        return;
+    }
+
+    // An access token issued to a user for this app registration's API.
+    let user_assertion = std::env::var("REQSIGN_AZURE_STORAGE_USER_ASSERTION")
+        .expect("REQSIGN_AZURE_STORAGE_USER_ASSERTION must be set for on-behalf-of test");
+
+    let ctx = Context::new()
+        .with_file_read(TokioFileRead)
+        .with_http_send(ReqwestHttpSend::default())
+        .with_env(OsEnv);
+
+    let loader = OnBehalfOfCredentialProvider::new(user_assertion);
+    let cred = match loader.provide_credential(&ctx).await {
+        Ok(Some(cred)) => cred,
+        Ok(None) => panic!("On-behalf-of provider returned None when test is enabled"),
+        Err(e) => panic!("On-behalf-of provider failed with error: {e:?}"),
+    };
+
+    match cred {
+        Credential::BearerToken { token, .. } => {
+            assert!(!token.is_empty());
+            eprintln!("Successfully obtained bearer token using on-behalf-of flow");
+        }
+        _ => panic!("Expected BearerToken credential from on-behalf-of provider"),
+    }
+}
@@ -26,7 +26,8 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use reqsign_core::{
-    Context, Result, SignRequest, SigningCredential, SigningRequest, hash::hex_sha256, time::*,
+    Context, Result, SignRequest, SigningCredential, SigningOverrideField, SigningOverrides,
+    SigningRequest, hash::hex_sha256, time::*,
 };
 
 use crate::constants::{
@@ -84,6 +85,10 @@ struct TokenResponse {
 }
 
 /// RequestSigner for Google service requests.
+///
+/// Signed URLs support every [`SigningOverrides`] field; `host` and `x-goog-*`
+/// headers are always signed. Bearer token requests only support the
+/// `expires_in` override.
 #[derive(Debug)]
 pub struct RequestSigner {
     service: String,
@@ -219,28 +224,32 @@ impl RequestSigner {
         client_email: &str,
         now: Timestamp,
         expires_in: Duration,
+        overrides: Option<&SigningOverrides>,
     ) -> Result<(String, Vec<(String, String)>)> {
         canonicalize_header(req)?;
 
+        let service = overrides
+            .and_then(SigningOverrides::service)
+            .unwrap_or(&self.service);
+        let region = overrides
+            .and_then(SigningOverrides::region)
+            .unwrap_or(&self.region);
+        let signed_headers = signed_header_names(req, overrides);
+
         let authentication_query = authentication_query(
-            req,
+            &signed_headers.join(";"),
             client_email,
             now,
             expires_in,
-            &self.service,
-            &self.region,
+            service,
+            region,
         );
         let canonical_query = canonicalize_query(req, &authentication_query);
 
-        let creq = canonical_request_string(req, &canonical_query)?;
+        let creq = canonical_request_string(req, &canonical_query, &signed_headers)?;
         let encoded_req = hex_sha256(creq.as_bytes());
 
-        let scope = format!(
-            "{}/{}/{}/goog4_request",
-            now.format_date(),
-            self.region,
-            self.service
-        );
+        let scope = format!("{}/{}/{}/goog4_request", now.format_date(), region, service);
         debug!("calculated scope: {scope}");
 
         let string_to_sign = {
@@ -274,14 +283,20 @@ impl RequestSigner {
         &self,
         parts: &mut http::request::Parts,
         service_account: &ServiceAccount,
+        now: Timestamp,
         expires_in: Duration,
     ) -> Result<(SigningRequest, Uri)> {
         let original_uri = parts.uri.clone();
         let mut req = SigningRequest::build(parts)?;
-        let now = Timestamp::now();
+        let overrides = SigningOverrides::from_request(parts);
 
-        let (string_to_sign, authentication_query) =
-            self.build_string_to_sign(&mut req, &service_account.client_email, now, expires_in)?;
+        let (string_to_sign, authentication_query) = self.build_string_to_sign(
+            &mut req,
+            &service_account.client_email,
+            now,
+            expires_in,
+            overrides,
+        )?;
         let signature =
             Self::sign_with_service_account(&service_account.private_key, &string_to_sign)?;
 
@@ -363,14 +378,15 @@ impl RequestSigner {
         parts: &mut http::request::Parts,
         token: &Token,
         signer_email: &str,
+        now: Timestamp,
         expires_in: Duration,
     ) -> Result<(SigningRequest, Uri)> {
         let original_uri = parts.uri.clone();
         let mut req = SigningRequest::build(parts)?;
-        let now = Timestamp::now();
+        let overrides = SigningOverrides::from_request(parts);
 
         let (string_to_sign, authentication_query) =
-            self.build_string_to_sign(&mut req, signer_email, now, expires_in)?;
+            self.build_string_to_sign(&mut req, signer_email, now, expires_in, overrides)?;
         let signature = self
             .sign_via_iamcredentials(ctx, token, signer_email, string_to_sign.as_bytes())
             .await?;
@@ -401,6 +417,23 @@ impl SignRequest for RequestSigner {
         }
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &http::request::Parts,
+        credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        if credential
+            .service_account
+            .as_ref()
+            .is_some_and(ServiceAccount::is_valid)
+        {
+            SigningOverrides::signing_time_or(req, Timestamp::now)
+        } else {
+            self.required_valid_until(credential, expires_in)
+        }
+    }
+
     async fn sign_request(
         &self,
         ctx: &Context,
@@ -412,7 +445,25 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let required_until = self.required_valid_until(cred, expires_in);
+        let (expires_in, now) = SigningOverrides::resolve(
+            req,
+            &[
+                SigningOverrideField::Region,
+                SigningOverrideField::Service,
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::SignedHeaders,
+                SigningOverrideField::ExpiresIn,
+            ],
+            expires_in,
+            Timestamp::now,
+        )?;
+        if expires_in.is_none()
+            && let Some(overrides) = SigningOverrides::from_request(req)
+        {
+            overrides.ensure_supported(&[SigningOverrideField::ExpiresIn])?;
+        }
+
+        let required_until = self.required_valid_until_for(req, cred, expires_in);
         if !cred.is_valid_at(required_until) {
             return Err(reqsign_core::Error::credential_invalid(
                 "credential expires before the requested signing operation deadline",
@@ -428,7 +479,7 @@ impl SignRequest for RequestSigner {
                     .filter(|service_account| service_account.is_valid())
                 {
                     let (signing_req, uri) =
-                        self.build_signed_query_with_service_account(req, sa, expires)?;
+                        self.build_signed_query_with_service_account(req, sa, now, expires)?;
                     (signing_req, Some(uri))
                 } else if let (Some(token), Some(signer_email)) =
                     (cred.token.as_ref(), self.signer_email.as_deref())
@@ -445,6 +496,7 @@ impl SignRequest for RequestSigner {
                             req,
                             token,
                             signer_email,
+                            now,
                             expires,
                         )
                         .await?;
@@ -525,6 +577,7 @@ fn hex_encode_upper(bytes: &[u8]) -> String {
 fn canonical_request_string(
     req: &SigningRequest,
     canonical_query: &[(String, String)],
+    signed_headers: &[&str],
 ) -> Result<String> {
     // 256 is specially chosen to avoid reallocation for most requests.
     let mut f = String::with_capacity(256);
@@ -548,8 +601,7 @@ fn canonical_request_string(
     f.push('\n');
 
     // Insert signed headers
-    let signed_headers = req.header_name_to_vec_sorted();
-    for header in signed_headers.iter() {
+    for header in signed_headers {
         let mut value = req.headers[*header].clone();
         SigningRequest::header_value_normalize(&mut value);
         f.push_str(header);
@@ -582,8 +634,22 @@ fn canonicalize_header(req: &mut SigningRequest) -> Result<()> {
     Ok(())
 }
 
+fn signed_header_names<'a>(
+    req: &'a SigningRequest,
+    overrides: Option<&SigningOverrides>,
+) -> Vec<&'a str> {
+    req.header_name_to_vec_sorted()
+        .into_iter()
+        .filter(|name| {
+            *name == header::HOST.as_str()
+                || name.starts_with("x-goog-")
+                || overrides.is_none_or(|o| o.signs_header(name))
+        })
+        .collect()
+}
+
 fn authentication_query(
-    req: &SigningRequest,
+    signed_headers: &str,
     client_email: &str,
     now: Timestamp,
     expires_in: Duration,
@@ -604,10 +670,7 @@ fn authentication_query(
         ),
         ("X-Goog-Date".into(), now.format_iso8601()),
         ("X-Goog-Expires".into(), expires_in.as_secs().to_string()),
-        (
-            "X-Goog-SignedHeaders".into(),
-            req.header_name_to_vec_sorted().join(";"),
-        ),
+        ("X-Goog-SignedHeaders".into(), signed_headers.to_string()),
     ]
 }
 
@@ -830,6 +893,7 @@ mod tests {
             "test-signer@example.com",
             now,
             expires_in,
+            None,
         )?;
         let canonical_query = canonicalize_query(&signing_req, &authentication_query);
         assert_eq!(
@@ -853,6 +917,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn signed_url_honours_signing_overrides() -> Result<()> {
+        let ctx = Context::new().with_http_send(MockHttpSend::default());
+        let signer = RequestSigner::new("storage").with_signer_email("test-signer@example.com");
+        let cred = Credential::with_token(Token {
+            access_token: "test-access-token".to_string(),
+            expires_at: None,
+        });
+        let now: Timestamp = "2026-07-22T00:00:00Z".parse()?;
+
+        let mut parts = http::Request::get("https://storage.googleapis.com/test-bucket/object")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .header("x-goog-meta-owner", "team")
+            .body(())?
+            .into_parts()
+            .0;
+        parts.extensions.insert(
+            SigningOverrides::new()
+                .with_region("europe-west1")
+                .with_service("s3")
+                .with_signing_time(now)
+                .with_signed_headers([])
+                .with_expires_in(Duration::from_secs(600)),
+        );
+        signer
+            .sign_request(&ctx, &mut parts, Some(&cred), None)
+            .await?;
+
+        let query = parts.uri.query().expect("signed url must have query");
+        assert_eq!(
+            query_get(query, "X-Goog-Credential"),
+            Some("test-signer%40example.com%2F20260722%2Feurope-west1%2Fs3%2Fgoog4_request")
+        );
+        assert_eq!(query_get(query, "X-Goog-Date"), Some("20260722T000000Z"));
+        assert_eq!(query_get(query, "X-Goog-Expires"), Some("600"));
+        assert_eq!(
+            query_get(query, "X-Goog-SignedHeaders"),
+            Some("host%3Bx-goog-meta-owner")
+        );
+
+        let mut parts = http::Request::get("https://storage.googleapis.com/test-bucket/object")
+            .body(())?
+            .into_parts()
+            .0;
+        parts
+            .extensions
+            .insert(SigningOverrides::new().with_region("europe-west1"));
+        let err = signer
+            .sign_request(&ctx, &mut parts, Some(&cred), None)
+            .await
+            .expect_err("bearer requests must reject region overrides");
+        assert_eq!(err.kind(), ErrorKind::RequestInvalid);
+        assert!(parts.headers.get(header::AUTHORIZATION).is_none());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn signer_refreshes_near_expiry_token_without_binding_it_to_signed_url_lifetime()
     -> Result<()> {
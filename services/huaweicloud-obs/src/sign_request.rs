@@ -33,7 +33,10 @@ use super::constants::*;
 use super::credential::Credential;
 use reqsign_core::hash::base64_hmac_sha1;
 use reqsign_core::time::Timestamp;
-use reqsign_core::{SignRequest, SigningCredential, SigningMethod, SigningRequest};
+use reqsign_core::{
+    SignRequest, SigningCredential, SigningMethod, SigningOverrideField, SigningOverrides,
+    SigningRequest,
+};
 
 static OBS_QUERY_ENCODE_SET: AsciiSet = NON_ALPHANUMERIC
     .remove(b'-')
@@ -44,6 +47,8 @@ static OBS_QUERY_ENCODE_SET: AsciiSet = NON_ALPHANUMERIC
 /// RequestSigner that implement Huawei Cloud Object Storage Service Authorization.
 ///
 /// - [User Signature Authentication](https://support.huaweicloud.com/intl/en-us/api-obs/obs_04_0009.html)
+///
+/// Supports the signing time and `expires_in` [`SigningOverrides`].
 #[derive(Debug)]
 pub struct RequestSigner {
     bucket: String,
@@ -94,6 +99,16 @@ impl SignRequest for RequestSigner {
         self.required_valid_until_at(self.get_time(), expires_in)
     }
 
+    fn required_valid_until_for(
+        &self,
+        parts: &http::request::Parts,
+        _credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        let now = SigningOverrides::signing_time_or(parts, || self.get_time());
+        self.required_valid_until_at(now, expires_in)
+    }
+
     async fn sign_request(
         &self,
         _ctx: &reqsign_core::Context,
@@ -105,7 +120,15 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let now = self.get_time();
+        let (expires_in, now) = SigningOverrides::resolve(
+            parts,
+            &[
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::ExpiresIn,
+            ],
+            expires_in,
+            || self.get_time(),
+        )?;
         let required_until = self.required_valid_until_at(now, expires_in);
         if !cred.is_valid_at(required_until) {
             return Err(reqsign_core::Error::credential_invalid(
@@ -393,6 +416,45 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sign_with_signing_time_override() -> Result<()> {
+        let loader = StaticCredentialProvider::new("access_key", "123456");
+        let signer = Signer::new(Context::new(), loader, RequestSigner::new("bucket"));
+
+        let get_req = "http://bucket.obs.cn-north-4.myhuaweicloud.com/object.txt";
+        let mut req = http::Request::get(Uri::from_str(get_req)?)
+            .header("Content-MD5", "abc")
+            .header("Content-Type", "text/plain")
+            .body(())?;
+        req.extensions_mut().insert(
+            SigningOverrides::new()
+                .with_signing_time(Timestamp::parse_rfc2822("Mon, 15 Aug 2022 16:50:12 GMT")?),
+        );
+
+        let (mut parts, _) = req.into_parts();
+        signer.sign(&mut parts, None).await?;
+
+        assert_eq!(
+            "OBS access_key:9gUZ4ol2W19LyYcc92Bu3U0V09E=",
+            parts.headers.get("Authorization").unwrap().to_str()?,
+        );
+
+        let mut parts = http::Request::get(Uri::from_str(get_req)?)
+            .body(())?
+            .into_parts()
+            .0;
+        parts
+            .extensions
+            .insert(SigningOverrides::new().with_region("cn-north-4"));
+        let err = signer
+            .sign(&mut parts, None)
+            .await
+            .expect_err("region override must be rejected");
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::RequestInvalid);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_sign_with_subresource() -> Result<()> {
         let loader = StaticCredentialProvider::new("access_key", "123456");
@@ -22,7 +22,9 @@ use http::request::Parts;
 use log::debug;
 use reqsign_core::Result;
 use reqsign_core::time::Timestamp;
-use reqsign_core::{Context, SignRequest, SigningCredential, SigningRequest};
+use reqsign_core::{
+    Context, SignRequest, SigningCredential, SigningOverrideField, SigningOverrides, SigningRequest,
+};
 use rsa::pkcs1v15::SigningKey;
 use rsa::sha2::Sha256;
 use rsa::signature::{SignatureEncoding, Signer};
@@ -33,6 +35,9 @@ use std::time::Duration;
 /// RequestSigner that implements Oracle Cloud Infrastructure API signing.
 ///
 /// - [Oracle Cloud Infrastructure API Signing](https://docs.oracle.com/en-us/iaas/Content/API/Concepts/signingrequests.htm)
+///
+/// Supports the signing time [`SigningOverrides`] and `without_expiry()`. OCI
+/// has no presigned requests, so an `expires_in` override is rejected.
 #[derive(Debug)]
 pub struct RequestSigner {}
 
@@ -59,6 +64,15 @@ impl SignRequest for RequestSigner {
         Timestamp::now()
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &Parts,
+        _credential: &Self::Credential,
+        _expires_in: Option<Duration>,
+    ) -> Timestamp {
+        SigningOverrides::signing_time_or(req, Timestamp::now)
+    }
+
     async fn sign_request(
         &self,
         ctx: &Context,
@@ -70,7 +84,20 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let now = Timestamp::now();
+        let (expires_in, now) = SigningOverrides::resolve(
+            req,
+            &[
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::ExpiresIn,
+            ],
+            None,
+            Timestamp::now,
+        )?;
+        if expires_in.is_some() {
+            return Err(reqsign_core::Error::request_invalid(
+                "OCI request signing does not support an expires_in override",
+            ));
+        }
         if !cred.is_valid_at(now) {
             return Err(reqsign_core::Error::credential_invalid(
                 "credential expires before the requested signing operation deadline",
@@ -206,6 +233,61 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn signing_time_override_sets_date_header() -> Result<()> {
+        let private_key = RsaPrivateKey::new(&mut OsRng, 1024).expect("key generation must work");
+        let private_key = private_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .expect("private key must encode")
+            .as_bytes()
+            .to_vec();
+        let ctx = Context::new().with_file_read(StaticFileRead(private_key));
+        let now: Timestamp = "2026-07-22T00:00:00Z".parse()?;
+
+        let mut parts = http::Request::get("https://example.com/object")
+            .body(())?
+            .into_parts()
+            .0;
+        parts
+            .extensions
+            .insert(SigningOverrides::new().with_signing_time(now));
+        RequestSigner::new()
+            .sign_request(&ctx, &mut parts, Some(&credential()), None)
+            .await?;
+        assert_eq!(parts.headers[DATE], now.format_http_date());
+
+        let mut parts = http::Request::get("https://example.com/object")
+            .body(())?
+            .into_parts()
+            .0;
+        parts.extensions.insert(
+            SigningOverrides::new()
+                .with_signing_time(now)
+                .with_expires_in(Duration::from_secs(60)),
+        );
+        let err = RequestSigner::new()
+            .sign_request(&ctx, &mut parts, Some(&credential()), None)
+            .await
+            .expect_err("expires_in override must be rejected");
+        assert_eq!(err.kind(), reqsign_core::ErrorKind::RequestInvalid);
+        assert!(!parts.headers.contains_key(AUTHORIZATION));
+
+        let mut parts = http::Request::get("https://example.com/object")
+            .body(())?
+            .into_parts()
+            .0;
+        parts.extensions.insert(
+            SigningOverrides::new()
+                .with_signing_time(now)
+                .without_expiry(),
+        );
+        RequestSigner::new()
+            .sign_request(&ctx, &mut parts, Some(&credential()), None)
+            .await?;
+        assert_eq!(parts.headers[DATE], now.format_http_date());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn invalid_private_key_leaves_request_unchanged() -> Result<()> {
         let original_uri = format!("https://example.com/object?{RAW_QUERY}");
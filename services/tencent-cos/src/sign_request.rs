@@ -24,12 +24,18 @@ use log::debug;
 use percent_encoding::{percent_decode_str, utf8_percent_encode};
 use reqsign_core::hash::{hex_hmac_sha1, hex_sha1};
 use reqsign_core::time::Timestamp;
-use reqsign_core::{Context, Result, SignRequest, SigningCredential, SigningRequest};
+use reqsign_core::{
+    Context, Result, SignRequest, SigningCredential, SigningOverrideField, SigningOverrides,
+    SigningRequest,
+};
 use std::time::Duration;
 
 /// RequestSigner that implements Tencent COS signing.
 ///
 /// - [Tencent COS Signature](https://cloud.tencent.com/document/product/436/7778)
+///
+/// Supports the signing time, signed headers, and `expires_in`
+/// [`SigningOverrides`].
 #[derive(Debug, Default)]
 pub struct RequestSigner {
     time: Option<Timestamp>,
@@ -77,6 +83,16 @@ impl SignRequest for RequestSigner {
         self.required_valid_until_at(self.get_time(), expires_in)
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &Parts,
+        _credential: &Self::Credential,
+        expires_in: Option<Duration>,
+    ) -> Timestamp {
+        let now = SigningOverrides::signing_time_or(req, || self.get_time());
+        self.required_valid_until_at(now, expires_in)
+    }
+
     async fn sign_request(
         &self,
         _ctx: &Context,
@@ -88,7 +104,16 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let now = self.get_time();
+        let (expires_in, now) = SigningOverrides::resolve(
+            req,
+            &[
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::SignedHeaders,
+                SigningOverrideField::ExpiresIn,
+            ],
+            expires_in,
+            || self.get_time(),
+        )?;
         let required_until = self.required_valid_until_at(now, expires_in);
         if !cred.is_valid_at(required_until) {
             return Err(reqsign_core::Error::credential_invalid(
@@ -98,10 +123,11 @@ impl SignRequest for RequestSigner {
 
         let original_uri = req.uri.clone();
         let mut signing_req = SigningRequest::build(req)?;
+        let overrides = SigningOverrides::from_request(req);
 
         let final_uri = if let Some(expires) = expires_in {
             // Query signing
-            let signature = build_signature(&signing_req, cred, now, expires);
+            let signature = build_signature(&signing_req, cred, now, expires, overrides);
 
             signing_req
                 .headers
@@ -117,7 +143,13 @@ impl SignRequest for RequestSigner {
             Some(append_query_fragment(&original_uri, &authentication)?)
         } else {
             // Header signing (default 3600s expiration)
-            let signature = build_signature(&signing_req, cred, now, Duration::from_secs(3600));
+            let signature = build_signature(
+                &signing_req,
+                cred,
+                now,
+                Duration::from_secs(3600),
+                overrides,
+            );
 
             signing_req
                 .headers
@@ -151,6 +183,7 @@ fn build_signature(
     cred: &Credential,
     now: Timestamp,
     expires: Duration,
+    overrides: Option<&SigningOverrides>,
 ) -> String {
     let key_time = format!("{};{}", now.as_second(), (now + expires).as_second());
     let sign_key = hex_hmac_sha1(cred.secret_key.as_bytes(), key_time.as_bytes());
@@ -168,6 +201,7 @@ fn build_signature(
                 utf8_percent_encode(v, &TENCENT_URI_ENCODE_SET).to_string(),
             )
         })
+        .filter(|(k, _)| overrides.is_none_or(|o| o.signs_header(k)))
         .collect::<Vec<_>>();
     headers.sort();
 
@@ -321,4 +355,39 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn signing_overrides_control_key_time_and_header_list() -> Result<()> {
+        let now: Timestamp = "2026-07-22T00:00:00Z".parse()?;
+        let credential = Credential {
+            secret_id: "secret_id".to_string(),
+            secret_key: "secret_key".to_string(),
+            ..Default::default()
+        };
+        let mut parts = http::Request::put("https://bucket.cos.ap-beijing.myqcloud.com/object")
+            .header(http::header::CONTENT_TYPE, "text/plain")
+            .header("x-proxy-trace", "abc")
+            .body(())?
+            .into_parts()
+            .0;
+        parts.extensions.insert(
+            SigningOverrides::new()
+                .with_signing_time(now)
+                .with_signed_headers([http::header::CONTENT_TYPE])
+                .with_expires_in(Duration::from_secs(60)),
+        );
+
+        RequestSigner::new()
+            .sign_request(&Context::new(), &mut parts, Some(&credential), None)
+            .await?;
+
+        let query = parts.uri.query().expect("query must exist");
+        assert!(query.contains(&format!(
+            "q-key-time={};{}",
+            now.as_second(),
+            now.as_second() + 60
+        )));
+        assert!(query.contains("q-header-list=content-type&"));
+        Ok(())
+    }
 }
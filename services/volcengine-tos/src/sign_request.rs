@@ -21,7 +21,10 @@ use log::debug;
 use percent_encoding::percent_decode_str;
 use reqsign_core::hash::{hex_hmac_sha256, hex_sha256, hmac_sha256};
 use reqsign_core::time::Timestamp;
-use reqsign_core::{Context, Result, SignRequest, SigningCredential, SigningRequest};
+use reqsign_core::{
+    Context, Result, SignRequest, SigningCredential, SigningOverrideField, SigningOverrides,
+    SigningRequest,
+};
 use std::fmt::Write;
 use std::sync::LazyLock;
 use std::time::Duration;
@@ -53,6 +56,9 @@ static HEADER_TOS_SECURITY_TOKEN: LazyLock<HeaderName> =
 /// RequestSigner that implements Volcengine TOS signing.
 ///
 /// - [Volcengine TOS Signature](https://www.volcengine.com/docs/6349/1747874)
+///
+/// Supports the region, signing time, signed headers, and `expires_in`
+/// [`SigningOverrides`]. `host` and `x-tos-*` headers are always signed.
 #[derive(Debug)]
 pub struct RequestSigner {
     region: String,
@@ -95,6 +101,15 @@ impl SignRequest for RequestSigner {
         self.get_time()
     }
 
+    fn required_valid_until_for(
+        &self,
+        req: &http::request::Parts,
+        _credential: &Self::Credential,
+        _expires_in: Option<Duration>,
+    ) -> Timestamp {
+        SigningOverrides::signing_time_or(req, || self.get_time())
+    }
+
     async fn sign_request(
         &self,
         _ctx: &Context,
@@ -106,7 +121,17 @@ impl SignRequest for RequestSigner {
             return Ok(());
         };
 
-        let now = self.get_time();
+        let (expires_in, now) = SigningOverrides::resolve(
+            req,
+            &[
+                SigningOverrideField::Region,
+                SigningOverrideField::SigningTime,
+                SigningOverrideField::SignedHeaders,
+                SigningOverrideField::ExpiresIn,
+            ],
+            expires_in,
+            || self.get_time(),
+        )?;
         if !cred.is_valid_at(now) {
             return Err(reqsign_core::Error::credential_invalid(
                 "credential expires before the requested signing operation deadline",
@@ -115,16 +140,20 @@ impl SignRequest for RequestSigner {
 
         let original_uri = req.uri.clone();
         let mut signing_req = SigningRequest::build(req)?;
+        let overrides = SigningOverrides::from_request(req);
+        let region = overrides
+            .and_then(SigningOverrides::region)
+            .unwrap_or(&self.region);
         validate_authentication_carriers(&signing_req)?;
 
         let date_str = now.format_iso8601();
         let date_only = now.format_date();
 
         // Scope: "<date>/<region>/tos/request"
-        let credential_scope = format!("{}/{}/tos/request", date_only, self.region);
+        let credential_scope = format!("{}/{}/tos/request", date_only, region);
 
         canonicalize_header(&mut signing_req, cred, &date_str, expires_in)?;
-        let signed_headers = signed_header_names(&signing_req, expires_in.is_some());
+        let signed_headers = signed_header_names(&signing_req, expires_in.is_some(), overrides);
         let authentication_query = authentication_query(
             cred,
             &credential_scope,
@@ -169,7 +198,7 @@ impl SignRequest for RequestSigner {
 
         debug!("string to sign: {}", string_to_sign);
 
-        let signing_key = generate_signing_key(&cred.secret_access_key, &date_only, &self.region);
+        let signing_key = generate_signing_key(&cred.secret_access_key, &date_only, region);
         let signature = hex_hmac_sha256(&signing_key, string_to_sign.as_bytes());
 
         let final_uri = if expires_in.is_some() {
@@ -324,12 +353,20 @@ fn append_query_fragment(uri: &http::Uri, fragment: &str) -> Result<http::Uri> {
     })
 }
 
-fn signed_header_names(ctx: &SigningRequest, is_presign: bool) -> Vec<&str> {
+fn signed_header_names<'a>(
+    ctx: &'a SigningRequest,
+    is_presign: bool,
+    overrides: Option<&SigningOverrides>,
+) -> Vec<&'a str> {
     let mut headers = ctx
         .headers
         .keys()
         .map(|k| k.as_str())
-        .filter(|header| !is_presign || *header == "host" || header.starts_with("x-tos-"))
+        .filter(|header| {
+            *header == "host"
+                || header.starts_with("x-tos-")
+                || (!is_presign && overrides.is_none_or(|o| o.signs_header(header)))
+        })
         .collect::<Vec<_>>();
     headers.sort_unstable();
     headers
@@ -598,6 +635,67 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_presign_with_signing_overrides() -> Result<()> {
+        let mut parts = http::Request::get(
+            "https://examplebucket.tos-ap-southeast-1.bytepluses.com/exampleobject",
+        )
+        .header("x-tos-content-sha256", EMPTY_PAYLOAD_SHA256)
+        .header("x-tos-meta-note", "alpha   beta")
+        .body(())?
+        .into_parts()
+        .0;
+        parts.extensions.insert(
+            SigningOverrides::new()
+                .with_region("ap-southeast-1")
+                .with_signing_time(Timestamp::parse_rfc2822("Sat, 1 Jan 2022 00:00:00 GMT")?)
+                .with_expires_in(Duration::from_secs(86400)),
+        );
+
+        RequestSigner::new("cn-beijing")
+            .sign_request(
+                &Context::new(),
+                &mut parts,
+                Some(&Credential::new("testAK", "testSK")),
+                None,
+            )
+            .await?;
+
+        assert_eq!(
+            "https://examplebucket.tos-ap-southeast-1.bytepluses.com/exampleobject?X-Tos-Algorithm=TOS4-HMAC-SHA256&X-Tos-Credential=testAK%2F20220101%2Fap-southeast-1%2Ftos%2Frequest&X-Tos-Date=20220101T000000Z&X-Tos-Expires=86400&X-Tos-SignedHeaders=host%3Bx-tos-content-sha256%3Bx-tos-meta-note&X-Tos-Signature=bb27db860abc9394068d1f22c229f2dfe01e58d8f6f2a65f06f2e78114af9195",
+            parts.uri.to_string(),
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signed_header_override_excludes_unlisted_headers() -> Result<()> {
+        let mut parts = http::Request::put("https://examplebucket.tos-cn-beijing.volces.com/key")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .header("x-proxy-trace", "abc")
+            .body(())?
+            .into_parts()
+            .0;
+        parts
+            .extensions
+            .insert(SigningOverrides::new().with_signed_headers([header::CONTENT_TYPE]));
+
+        RequestSigner::new("cn-beijing")
+            .with_time(Timestamp::parse_rfc2822("Sat, 1 Jan 2022 00:00:00 GMT")?)
+            .sign_request(
+                &Context::new(),
+                &mut parts,
+                Some(&Credential::new("testAK", "testSK")),
+                None,
+            )
+            .await?;
+
+        let authorization = parts.headers[AUTHORIZATION].to_str().unwrap();
+        assert!(authorization.contains("SignedHeaders=content-type;host;x-tos-date,"));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn canonicalization_and_signing_preserve_wire_uri() -> Result<()> {
         const RAW_QUERY: &str = "slash=%2F&hash=%23&amp=%26&equals=%3D&space=%20&encoded-plus=%2B&literal-plus=+&double=%252F&dup=first&dup=second&=empty-key&empty=&flag&flag=&";
@@ -617,7 +715,7 @@ mod tests {
             &date_str,
             Some(Duration::from_secs(60)),
         )?;
-        let signed_headers = signed_header_names(&signing_req, true);
+        let signed_headers = signed_header_names(&signing_req, true, None);
         let authentication_query = authentication_query(
             &credential,
             &credential_scope,